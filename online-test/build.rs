@@ -0,0 +1,4 @@
+// Rebuilds when a migration is added, as they are embedded into the binary.
+fn main() {
+    println!("cargo:rerun-if-changed=migrations");
+}
@@ -0,0 +1,9 @@
+DROP TABLE group_members;
+DROP TABLE review_cards;
+DROP TABLE practice_attempts;
+DROP TABLE attempt_items;
+DROP TABLE scores;
+DROP TABLE question_media;
+DROP TABLE question_tags;
+DROP TABLE question_revisions;
+DROP TABLE questions;
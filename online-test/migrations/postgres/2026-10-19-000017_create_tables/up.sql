@@ -0,0 +1,134 @@
+-- PostgreSQL databases start from the current schema, as there are no earlier ones to upgrade.
+-- Columns mirror the SQLite migrations, so both backends share `src/repository/schema.rs`.
+-- SQLite does not enforce the lengths of VARCHAR columns, so they are TEXT here.
+
+CREATE TABLE questions (
+  id SERIAL PRIMARY KEY,
+  kind INTEGER NOT NULL,
+  content TEXT NOT NULL,
+  option0 TEXT,
+  option1 TEXT,
+  option2 TEXT,
+  option3 TEXT,
+  answer TEXT NOT NULL,
+  format INTEGER NOT NULL DEFAULT 2,
+  revision_id INTEGER NOT NULL DEFAULT 0,
+  retired BOOLEAN NOT NULL DEFAULT FALSE,
+  items TEXT,
+  targets TEXT,
+  explanation TEXT,
+  points INTEGER,
+  -- Stands in for the FTS5 table of SQLite. It is not in the Diesel schema, so it is only
+  -- referenced by raw SQL.
+  search TSVECTOR GENERATED ALWAYS AS (
+    to_tsvector('simple', content || ' ' || coalesce(option0, '') || ' ' || coalesce(option1, '') || ' ' || coalesce(option2, '') || ' ' || coalesce(option3, '') || ' ' || coalesce(items, '') || ' ' || coalesce(targets, ''))
+  ) STORED
+);
+
+CREATE INDEX questions_search ON questions USING GIN (search);
+
+CREATE TABLE question_revisions (
+  id SERIAL PRIMARY KEY,
+  question_id INTEGER NOT NULL,
+  kind INTEGER NOT NULL,
+  format INTEGER NOT NULL,
+  content TEXT NOT NULL,
+  option0 TEXT,
+  option1 TEXT,
+  option2 TEXT,
+  option3 TEXT,
+  answer TEXT NOT NULL,
+  author TEXT NOT NULL,
+  created_at TEXT NOT NULL,
+  items TEXT,
+  targets TEXT,
+  explanation TEXT,
+  points INTEGER
+);
+
+CREATE INDEX question_revisions_question_id ON question_revisions (question_id);
+
+CREATE TABLE question_tags (
+  question_id INTEGER NOT NULL REFERENCES questions(id),
+  tag TEXT NOT NULL,
+  PRIMARY KEY (question_id, tag)
+);
+
+CREATE INDEX question_tags_tag ON question_tags (tag);
+
+CREATE TABLE question_media (
+  id SERIAL PRIMARY KEY,
+  question_id INTEGER NOT NULL,
+  file_name TEXT NOT NULL,
+  mime_type TEXT NOT NULL,
+  size INTEGER NOT NULL,
+  created_at TEXT NOT NULL
+);
+
+CREATE INDEX question_media_question_id ON question_media (question_id);
+
+CREATE TABLE scores (
+  id SERIAL PRIMARY KEY,
+  name TEXT NOT NULL,
+  score REAL NOT NULL,
+  end_time TEXT NOT NULL,
+  duration INTEGER NOT NULL,
+  pending BOOLEAN NOT NULL DEFAULT FALSE,
+  points REAL NOT NULL DEFAULT 0,
+  total_points INTEGER NOT NULL DEFAULT 0,
+  penalty REAL NOT NULL DEFAULT 0,
+  grade TEXT,
+  passed BOOLEAN,
+  certificate TEXT
+);
+
+CREATE UNIQUE INDEX scores_certificate ON scores (certificate);
+
+CREATE TABLE attempt_items (
+  id SERIAL PRIMARY KEY,
+  score_id INTEGER NOT NULL,
+  revision_id INTEGER NOT NULL,
+  correct BOOLEAN NOT NULL,
+  credit REAL NOT NULL DEFAULT 0,
+  pending BOOLEAN NOT NULL DEFAULT FALSE,
+  response TEXT,
+  comment TEXT,
+  grader TEXT,
+  graded_at TEXT,
+  points INTEGER NOT NULL DEFAULT 1,
+  penalty REAL NOT NULL DEFAULT 0
+);
+
+CREATE INDEX attempt_items_score_id ON attempt_items (score_id);
+CREATE INDEX attempt_items_pending ON attempt_items (pending);
+
+CREATE TABLE practice_attempts (
+  id SERIAL PRIMARY KEY,
+  name TEXT NOT NULL,
+  question_id INTEGER NOT NULL,
+  revision_id INTEGER NOT NULL,
+  correct BOOLEAN NOT NULL,
+  answered_at TEXT NOT NULL
+);
+
+CREATE INDEX practice_attempts_name ON practice_attempts (name);
+
+CREATE TABLE review_cards (
+  name TEXT NOT NULL,
+  question_id INTEGER NOT NULL,
+  repetitions INTEGER NOT NULL,
+  interval_days INTEGER NOT NULL,
+  ease REAL NOT NULL,
+  due_at TEXT NOT NULL,
+  attempts INTEGER NOT NULL,
+  correct INTEGER NOT NULL,
+  PRIMARY KEY (name, question_id)
+);
+
+CREATE INDEX review_cards_name_due_at ON review_cards (name, due_at);
+
+CREATE TABLE group_members (
+  group_name TEXT NOT NULL,
+  name TEXT NOT NULL,
+  PRIMARY KEY (group_name, name)
+);
@@ -0,0 +1,9 @@
+DROP INDEX questions_search;
+ALTER TABLE questions DROP COLUMN search;
+ALTER TABLE questions DROP COLUMN search_text;
+
+ALTER TABLE questions ADD COLUMN search TSVECTOR GENERATED ALWAYS AS (
+  to_tsvector('simple', content || ' ' || coalesce(option0, '') || ' ' || coalesce(option1, '') || ' ' || coalesce(option2, '') || ' ' || coalesce(option3, '') || ' ' || coalesce(items, '') || ' ' || coalesce(targets, ''))
+) STORED;
+
+CREATE INDEX questions_search ON questions USING GIN (search);
@@ -0,0 +1,9 @@
+-- The application writes the text without markup into `search_text`, as with SQLite. Existing
+-- questions start empty and are indexed when the migrations are run.
+DROP INDEX questions_search;
+ALTER TABLE questions DROP COLUMN search;
+
+ALTER TABLE questions ADD COLUMN search_text TEXT NOT NULL DEFAULT '';
+ALTER TABLE questions ADD COLUMN search TSVECTOR GENERATED ALWAYS AS (to_tsvector('simple', search_text)) STORED;
+
+CREATE INDEX questions_search ON questions USING GIN (search);
@@ -0,0 +1 @@
+ALTER TABLE scores ADD COLUMN adaptive BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,10 @@
+CREATE TABLE questions (
+  id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+  kind INTEGER NOT NULL,
+  content TEXT NOT NULL,
+  option0 TEXT,
+  option1 TEXT,
+  option2 TEXT,
+  option3 TEXT,
+  answer TEXT NOT NULL
+);
@@ -0,0 +1 @@
+ALTER TABLE questions ADD COLUMN format INTEGER NOT NULL DEFAULT 2;
@@ -0,0 +1,3 @@
+DROP TABLE attempt_items;
+ALTER TABLE questions DROP COLUMN revision_id;
+DROP TABLE question_revisions;
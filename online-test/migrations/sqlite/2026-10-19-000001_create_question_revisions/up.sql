@@ -0,0 +1,34 @@
+CREATE TABLE question_revisions (
+  id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+  question_id INTEGER NOT NULL,
+  kind INTEGER NOT NULL,
+  format INTEGER NOT NULL,
+  content TEXT NOT NULL,
+  option0 TEXT,
+  option1 TEXT,
+  option2 TEXT,
+  option3 TEXT,
+  answer TEXT NOT NULL,
+  author VARCHAR(64) NOT NULL,
+  created_at VARCHAR(32) NOT NULL
+);
+
+CREATE INDEX question_revisions_question_id ON question_revisions (question_id);
+
+INSERT INTO question_revisions (question_id, kind, format, content, option0, option1, option2, option3, answer, author, created_at)
+SELECT id, kind, format, content, option0, option1, option2, option3, answer, 'migration', strftime('%Y-%m-%dT%H:%M:%S+00:00', 'now')
+FROM questions;
+
+ALTER TABLE questions ADD COLUMN revision_id INTEGER NOT NULL DEFAULT 0;
+
+UPDATE questions
+SET revision_id = (SELECT MAX(r.id) FROM question_revisions r WHERE r.question_id = questions.id);
+
+CREATE TABLE attempt_items (
+  id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+  score_id INTEGER NOT NULL,
+  revision_id INTEGER NOT NULL,
+  correct BOOLEAN NOT NULL
+);
+
+CREATE INDEX attempt_items_score_id ON attempt_items (score_id);
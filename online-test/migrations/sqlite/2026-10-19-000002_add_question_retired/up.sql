@@ -0,0 +1 @@
+ALTER TABLE questions ADD COLUMN retired BOOLEAN NOT NULL DEFAULT 0;
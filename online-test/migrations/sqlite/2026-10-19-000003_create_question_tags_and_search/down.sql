@@ -0,0 +1,6 @@
+DROP TRIGGER questions_fts_delete;
+DROP TRIGGER questions_fts_update;
+DROP TRIGGER questions_fts_insert;
+DROP TABLE questions_fts;
+DROP INDEX question_tags_tag;
+DROP TABLE question_tags;
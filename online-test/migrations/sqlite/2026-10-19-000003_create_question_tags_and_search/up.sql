@@ -0,0 +1,27 @@
+CREATE TABLE question_tags (
+    question_id INTEGER NOT NULL REFERENCES questions(id),
+    tag TEXT NOT NULL,
+    PRIMARY KEY (question_id, tag)
+);
+
+CREATE INDEX question_tags_tag ON question_tags(tag);
+
+CREATE VIRTUAL TABLE questions_fts USING fts5(content, options);
+
+INSERT INTO questions_fts(rowid, content, options)
+SELECT id, content, coalesce(option0, '') || ' ' || coalesce(option1, '') || ' ' || coalesce(option2, '') || ' ' || coalesce(option3, '') FROM questions;
+
+CREATE TRIGGER questions_fts_insert AFTER INSERT ON questions BEGIN
+    INSERT INTO questions_fts(rowid, content, options)
+    VALUES (new.id, new.content, coalesce(new.option0, '') || ' ' || coalesce(new.option1, '') || ' ' || coalesce(new.option2, '') || ' ' || coalesce(new.option3, ''));
+END;
+
+CREATE TRIGGER questions_fts_update AFTER UPDATE OF content, option0, option1, option2, option3 ON questions BEGIN
+    DELETE FROM questions_fts WHERE rowid = old.id;
+    INSERT INTO questions_fts(rowid, content, options)
+    VALUES (new.id, new.content, coalesce(new.option0, '') || ' ' || coalesce(new.option1, '') || ' ' || coalesce(new.option2, '') || ' ' || coalesce(new.option3, ''));
+END;
+
+CREATE TRIGGER questions_fts_delete AFTER DELETE ON questions BEGIN
+    DELETE FROM questions_fts WHERE rowid = old.id;
+END;
@@ -0,0 +1,25 @@
+DROP TRIGGER questions_fts_update;
+DROP TRIGGER questions_fts_insert;
+
+DELETE FROM question_tags WHERE question_id IN (SELECT id FROM questions WHERE kind IN (3, 4));
+DELETE FROM question_revisions WHERE kind IN (3, 4);
+DELETE FROM questions WHERE kind IN (3, 4);
+DELETE FROM questions_fts;
+
+INSERT INTO questions_fts(rowid, content, options)
+SELECT id, content, coalesce(option0, '') || ' ' || coalesce(option1, '') || ' ' || coalesce(option2, '') || ' ' || coalesce(option3, '') FROM questions;
+
+CREATE TRIGGER questions_fts_insert AFTER INSERT ON questions BEGIN
+    INSERT INTO questions_fts(rowid, content, options)
+    VALUES (new.id, new.content, coalesce(new.option0, '') || ' ' || coalesce(new.option1, '') || ' ' || coalesce(new.option2, '') || ' ' || coalesce(new.option3, ''));
+END;
+
+CREATE TRIGGER questions_fts_update AFTER UPDATE OF content, option0, option1, option2, option3 ON questions BEGIN
+    DELETE FROM questions_fts WHERE rowid = old.id;
+    INSERT INTO questions_fts(rowid, content, options)
+    VALUES (new.id, new.content, coalesce(new.option0, '') || ' ' || coalesce(new.option1, '') || ' ' || coalesce(new.option2, '') || ' ' || coalesce(new.option3, ''));
+END;
+
+ALTER TABLE attempt_items DROP COLUMN credit;
+ALTER TABLE question_revisions DROP COLUMN items;
+ALTER TABLE questions DROP COLUMN items;
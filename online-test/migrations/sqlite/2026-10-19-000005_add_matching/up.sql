@@ -0,0 +1,16 @@
+ALTER TABLE questions ADD COLUMN targets TEXT;
+ALTER TABLE question_revisions ADD COLUMN targets TEXT;
+
+DROP TRIGGER questions_fts_update;
+DROP TRIGGER questions_fts_insert;
+
+CREATE TRIGGER questions_fts_insert AFTER INSERT ON questions BEGIN
+    INSERT INTO questions_fts(rowid, content, options)
+    VALUES (new.id, new.content, coalesce(new.option0, '') || ' ' || coalesce(new.option1, '') || ' ' || coalesce(new.option2, '') || ' ' || coalesce(new.option3, '') || ' ' || coalesce(new.items, '') || ' ' || coalesce(new.targets, ''));
+END;
+
+CREATE TRIGGER questions_fts_update AFTER UPDATE OF content, option0, option1, option2, option3, items, targets ON questions BEGIN
+    DELETE FROM questions_fts WHERE rowid = old.id;
+    INSERT INTO questions_fts(rowid, content, options)
+    VALUES (new.id, new.content, coalesce(new.option0, '') || ' ' || coalesce(new.option1, '') || ' ' || coalesce(new.option2, '') || ' ' || coalesce(new.option3, '') || ' ' || coalesce(new.items, '') || ' ' || coalesce(new.targets, ''));
+END;
@@ -0,0 +1,14 @@
+DROP INDEX attempt_items_pending;
+
+ALTER TABLE attempt_items DROP COLUMN graded_at;
+ALTER TABLE attempt_items DROP COLUMN grader;
+ALTER TABLE attempt_items DROP COLUMN comment;
+ALTER TABLE attempt_items DROP COLUMN response;
+ALTER TABLE attempt_items DROP COLUMN pending;
+
+ALTER TABLE scores DROP COLUMN pending;
+
+DELETE FROM question_tags WHERE question_id IN (SELECT id FROM questions WHERE kind = 6);
+DELETE FROM question_revisions WHERE kind = 6;
+DELETE FROM questions WHERE kind = 6;
+DELETE FROM questions_fts WHERE rowid NOT IN (SELECT id FROM questions);
@@ -0,0 +1,9 @@
+ALTER TABLE scores ADD COLUMN pending BOOLEAN NOT NULL DEFAULT 0;
+
+ALTER TABLE attempt_items ADD COLUMN pending BOOLEAN NOT NULL DEFAULT 0;
+ALTER TABLE attempt_items ADD COLUMN response TEXT;
+ALTER TABLE attempt_items ADD COLUMN comment TEXT;
+ALTER TABLE attempt_items ADD COLUMN grader VARCHAR(64);
+ALTER TABLE attempt_items ADD COLUMN graded_at VARCHAR(32);
+
+CREATE INDEX attempt_items_pending ON attempt_items (pending);
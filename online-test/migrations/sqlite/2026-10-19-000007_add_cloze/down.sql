@@ -0,0 +1,3 @@
+DELETE FROM question_tags WHERE question_id IN (SELECT id FROM questions WHERE kind = 7);
+DELETE FROM question_revisions WHERE kind = 7;
+DELETE FROM questions WHERE kind = 7;
@@ -0,0 +1,2 @@
+-- Cloze questions (kind 7) keep the accepted answers of every blank as a JSON array of string
+-- arrays in the existing answer column, so no schema change is needed.
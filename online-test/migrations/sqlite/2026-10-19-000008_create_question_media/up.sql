@@ -0,0 +1,10 @@
+CREATE TABLE question_media (
+  id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+  question_id INTEGER NOT NULL,
+  file_name VARCHAR(255) NOT NULL,
+  mime_type VARCHAR(127) NOT NULL,
+  size INTEGER NOT NULL,
+  created_at VARCHAR(32) NOT NULL
+);
+
+CREATE INDEX question_media_question_id ON question_media (question_id);
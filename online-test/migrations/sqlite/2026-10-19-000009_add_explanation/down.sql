@@ -0,0 +1,2 @@
+ALTER TABLE question_revisions DROP COLUMN explanation;
+ALTER TABLE questions DROP COLUMN explanation;
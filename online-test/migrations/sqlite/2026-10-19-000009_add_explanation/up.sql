@@ -0,0 +1,2 @@
+ALTER TABLE questions ADD COLUMN explanation TEXT;
+ALTER TABLE question_revisions ADD COLUMN explanation TEXT;
@@ -0,0 +1,10 @@
+CREATE TABLE practice_attempts (
+  id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+  name VARCHAR(64) NOT NULL,
+  question_id INTEGER NOT NULL,
+  revision_id INTEGER NOT NULL,
+  correct BOOLEAN NOT NULL,
+  answered_at VARCHAR(32) NOT NULL
+);
+
+CREATE INDEX practice_attempts_name ON practice_attempts (name);
@@ -0,0 +1,13 @@
+CREATE TABLE review_cards (
+  name VARCHAR(64) NOT NULL,
+  question_id INTEGER NOT NULL,
+  repetitions INTEGER NOT NULL,
+  interval_days INTEGER NOT NULL,
+  ease FLOAT NOT NULL,
+  due_at VARCHAR(32) NOT NULL,
+  attempts INTEGER NOT NULL,
+  correct INTEGER NOT NULL,
+  PRIMARY KEY (name, question_id)
+);
+
+CREATE INDEX review_cards_name_due_at ON review_cards (name, due_at);
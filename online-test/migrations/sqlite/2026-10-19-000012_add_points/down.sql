@@ -0,0 +1,7 @@
+ALTER TABLE scores DROP COLUMN total_points;
+ALTER TABLE scores DROP COLUMN points;
+
+ALTER TABLE attempt_items DROP COLUMN points;
+
+ALTER TABLE question_revisions DROP COLUMN points;
+ALTER TABLE questions DROP COLUMN points;
@@ -0,0 +1,11 @@
+ALTER TABLE questions ADD COLUMN points INTEGER;
+ALTER TABLE question_revisions ADD COLUMN points INTEGER;
+
+ALTER TABLE attempt_items ADD COLUMN points INTEGER NOT NULL DEFAULT 1;
+
+ALTER TABLE scores ADD COLUMN points REAL NOT NULL DEFAULT 0;
+ALTER TABLE scores ADD COLUMN total_points INTEGER NOT NULL DEFAULT 0;
+
+UPDATE scores SET
+  points = (SELECT COALESCE(SUM(credit), 0) FROM attempt_items WHERE score_id = scores.id),
+  total_points = (SELECT COUNT(*) FROM attempt_items WHERE score_id = scores.id);
@@ -0,0 +1,2 @@
+ALTER TABLE scores DROP COLUMN penalty;
+ALTER TABLE attempt_items DROP COLUMN penalty;
@@ -0,0 +1,2 @@
+ALTER TABLE attempt_items ADD COLUMN penalty REAL NOT NULL DEFAULT 0;
+ALTER TABLE scores ADD COLUMN penalty REAL NOT NULL DEFAULT 0;
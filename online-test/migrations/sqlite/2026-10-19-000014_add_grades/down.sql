@@ -0,0 +1,2 @@
+ALTER TABLE scores DROP COLUMN passed;
+ALTER TABLE scores DROP COLUMN grade;
@@ -0,0 +1,13 @@
+ALTER TABLE scores ADD COLUMN grade VARCHAR(16);
+ALTER TABLE scores ADD COLUMN passed BOOLEAN;
+
+UPDATE scores SET
+  grade = CASE
+    WHEN score >= 90 THEN 'A'
+    WHEN score >= 80 THEN 'B'
+    WHEN score >= 70 THEN 'C'
+    WHEN score >= 60 THEN 'D'
+    ELSE 'F'
+  END,
+  passed = score >= 60
+WHERE pending = 0;
@@ -0,0 +1,2 @@
+DROP INDEX scores_certificate;
+ALTER TABLE scores DROP COLUMN certificate;
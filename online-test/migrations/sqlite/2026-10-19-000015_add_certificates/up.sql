@@ -0,0 +1,3 @@
+ALTER TABLE scores ADD COLUMN certificate VARCHAR(16);
+
+CREATE UNIQUE INDEX scores_certificate ON scores (certificate);
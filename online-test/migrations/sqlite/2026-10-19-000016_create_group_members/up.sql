@@ -0,0 +1,5 @@
+CREATE TABLE group_members (
+  group_name VARCHAR(64) NOT NULL,
+  name VARCHAR(64) NOT NULL,
+  PRIMARY KEY (group_name, name)
+);
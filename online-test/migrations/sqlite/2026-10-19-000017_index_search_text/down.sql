@@ -0,0 +1,26 @@
+DROP TRIGGER questions_fts_delete;
+DROP TRIGGER questions_fts_update;
+DROP TRIGGER questions_fts_insert;
+DROP TABLE questions_fts;
+
+ALTER TABLE questions DROP COLUMN search_text;
+
+CREATE VIRTUAL TABLE questions_fts USING fts5(content, options);
+
+INSERT INTO questions_fts(rowid, content, options)
+SELECT id, content, coalesce(option0, '') || ' ' || coalesce(option1, '') || ' ' || coalesce(option2, '') || ' ' || coalesce(option3, '') || ' ' || coalesce(items, '') || ' ' || coalesce(targets, '') FROM questions;
+
+CREATE TRIGGER questions_fts_insert AFTER INSERT ON questions BEGIN
+    INSERT INTO questions_fts(rowid, content, options)
+    VALUES (new.id, new.content, coalesce(new.option0, '') || ' ' || coalesce(new.option1, '') || ' ' || coalesce(new.option2, '') || ' ' || coalesce(new.option3, '') || ' ' || coalesce(new.items, '') || ' ' || coalesce(new.targets, ''));
+END;
+
+CREATE TRIGGER questions_fts_update AFTER UPDATE OF content, option0, option1, option2, option3, items, targets ON questions BEGIN
+    DELETE FROM questions_fts WHERE rowid = old.id;
+    INSERT INTO questions_fts(rowid, content, options)
+    VALUES (new.id, new.content, coalesce(new.option0, '') || ' ' || coalesce(new.option1, '') || ' ' || coalesce(new.option2, '') || ' ' || coalesce(new.option3, '') || ' ' || coalesce(new.items, '') || ' ' || coalesce(new.targets, ''));
+END;
+
+CREATE TRIGGER questions_fts_delete AFTER DELETE ON questions BEGIN
+    DELETE FROM questions_fts WHERE rowid = old.id;
+END;
@@ -0,0 +1,23 @@
+-- SQLite cannot strip markup, so the application writes the text to index into `search_text`.
+-- Existing questions start empty and are indexed when the migrations are run.
+ALTER TABLE questions ADD COLUMN search_text TEXT NOT NULL DEFAULT '';
+
+DROP TRIGGER questions_fts_delete;
+DROP TRIGGER questions_fts_update;
+DROP TRIGGER questions_fts_insert;
+DROP TABLE questions_fts;
+
+CREATE VIRTUAL TABLE questions_fts USING fts5(text);
+
+CREATE TRIGGER questions_fts_insert AFTER INSERT ON questions BEGIN
+    INSERT INTO questions_fts(rowid, text) VALUES (new.id, new.search_text);
+END;
+
+CREATE TRIGGER questions_fts_update AFTER UPDATE OF search_text ON questions BEGIN
+    DELETE FROM questions_fts WHERE rowid = old.id;
+    INSERT INTO questions_fts(rowid, text) VALUES (new.id, new.search_text);
+END;
+
+CREATE TRIGGER questions_fts_delete AFTER DELETE ON questions BEGIN
+    DELETE FROM questions_fts WHERE rowid = old.id;
+END;
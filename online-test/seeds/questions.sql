@@ -1,13 +1,5 @@
-CREATE TABLE questions (
-  id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
-  kind INTEGER NOT NULL,
-  content TEXT NOT NULL,
-  option0 TEXT,
-  option1 TEXT,
-  option2 TEXT,
-  option3 TEXT,
-  answer TEXT NOT NULL
-);
+-- Sample questions about Linux system administration, loaded by `online-test cli seed`.
+-- Revisions are recorded for them by the seeding step after these statements run.
 
 -- =====================
 -- Single-Selection Part
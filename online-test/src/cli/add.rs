@@ -1,136 +1,513 @@
-use std::io;
 use std::sync::Arc;
 
+use clap::{Args, ValueEnum};
 use snafu::{prelude::*, Whatever};
 
-use crate::domain::repository::question::QuestionRepository;
+use crate::cli::{dedupe, prompt};
+use crate::domain::entity::content::{Content, ContentFormat};
+use crate::domain::entity::question::{ClozeQuestion, QuestionKind};
+use crate::domain::repository::question::{QuestionDraft, QuestionRepository};
 
-pub async fn run_add(repo: Arc<dyn QuestionRepository>) -> Result<(), Whatever> {
-    let mut kind = String::new();
-
-    println!("What kind of question would you like to add?");
-    println!("([s]ingle-selection, [m]ultiple-selection, [c]ompletion)");
+#[derive(Debug, Args)]
+pub struct AddArgs {
+    /// Kind of the question, prompted for if omitted
+    #[clap(value_enum)]
+    kind: Option<KindArg>,
+    /// Format of the content and options
+    #[clap(long, value_enum)]
+    format: Option<FormatArg>,
+    /// Content of the question
+    #[clap(long)]
+    content: Option<String>,
+    /// Option of a selection question, repeated four times in order
+    #[clap(long = "option")]
+    options: Vec<String>,
+    /// Item of an ordering or matching question, repeated for every item
+    #[clap(long = "item")]
+    items: Vec<String>,
+    /// Target of a matching question, repeated for every target
+    #[clap(long = "target")]
+    targets: Vec<String>,
+    /// Answer of the question, given as 1-based option, item or target numbers where applicable
+    #[clap(long)]
+    answer: Option<String>,
+    /// Accepted answers of a cloze blank separated by '|', repeated for every blank in order
+    #[clap(long = "blank")]
+    blanks: Vec<String>,
+    /// Explanation shown to students once their test is graded
+    #[clap(long)]
+    explanation: Option<String>,
+    /// Points the question is worth in a test, the default of its kind if omitted
+    #[clap(long, value_parser = clap::value_parser!(u32).range(1..))]
+    points: Option<u32>,
+    /// Tag of the question, may be repeated
+    #[clap(long = "tag")]
+    tags: Vec<String>,
+    /// Add even if similar questions already exist
+    #[clap(long, short)]
+    yes: bool,
+    /// Print the result as JSON
+    #[clap(long)]
+    json: bool,
+}
 
-    loop {
-        io::stdin()
-            .read_line(&mut kind)
-            .whatever_context("Could not read input")?;
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum KindArg {
+    SingleSelection,
+    MultipleSelection,
+    Completion,
+    TrueFalse,
+    Ordering,
+    Matching,
+    Essay,
+    Cloze,
+}
 
-        match kind.trim().chars().next() {
-            Some('s') | Some('S') => return run_add_single_selection(repo).await,
-            Some('m') | Some('M') => return run_add_multiple_selection(repo).await,
-            Some('c') | Some('C') => return run_add_completion(repo).await,
-            _ => println!("Invalid input: Expected 's', 'm' or 'c'"),
+impl From<KindArg> for QuestionKind {
+    fn from(value: KindArg) -> Self {
+        match value {
+            KindArg::SingleSelection => QuestionKind::SingleSelection,
+            KindArg::MultipleSelection => QuestionKind::MultipleSelection,
+            KindArg::Completion => QuestionKind::Completion,
+            KindArg::TrueFalse => QuestionKind::TrueFalse,
+            KindArg::Ordering => QuestionKind::Ordering,
+            KindArg::Matching => QuestionKind::Matching,
+            KindArg::Essay => QuestionKind::Essay,
+            KindArg::Cloze => QuestionKind::Cloze,
         }
+    }
+}
 
-        kind.clear();
-        println!();
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum FormatArg {
+    Plain,
+    Markdown,
+    Html,
+}
+
+impl From<FormatArg> for ContentFormat {
+    fn from(value: FormatArg) -> Self {
+        match value {
+            FormatArg::Plain => ContentFormat::Plain,
+            FormatArg::Markdown => ContentFormat::Markdown,
+            FormatArg::Html => ContentFormat::Html,
+        }
     }
 }
 
-async fn run_add_single_selection(repo: Arc<dyn QuestionRepository>) -> Result<(), Whatever> {
-    println!("");
-    let content = read_content()?;
-    let options = (1..=4)
-        .map(|i| {
-            println!("");
-            read_option(i)
-        })
-        .collect::<Result<Vec<_>, _>>()?;
-
-    let answer = loop {
-        println!("");
-        println!("What's your question's answer");
-        println!("(Input one integer within [1, 4])");
-        let mut answer = String::with_capacity(8);
-        io::stdin()
-            .read_line(&mut answer)
-            .whatever_context("Could not read input")?;
-        match answer.trim().parse() {
-            Ok(answer) if (1..=4).contains(&answer) => break answer,
-            _ => println!("Invalid input"),
+pub async fn run_add(
+    repo: Arc<dyn QuestionRepository>,
+    author: String,
+    mut args: AddArgs,
+) -> Result<(), Whatever> {
+    let json = args.json;
+    let yes = args.yes;
+    let tags = std::mem::take(&mut args.tags);
+    let kind = match args.kind {
+        Some(kind) => kind.into(),
+        None => {
+            prompt::begin("the kind")?;
+            prompt::read_kind(None)?
         }
     };
 
-    repo.insert_single_selection(content, options, answer)
+    let selection = matches!(
+        kind,
+        QuestionKind::SingleSelection | QuestionKind::MultipleSelection
+    );
+    ensure_whatever!(
+        selection || args.options.is_empty(),
+        "Only selection questions have options"
+    );
+    ensure_whatever!(
+        matches!(kind, QuestionKind::Ordering | QuestionKind::Matching) || args.items.is_empty(),
+        "Only ordering and matching questions have items"
+    );
+    ensure_whatever!(
+        kind == QuestionKind::Matching || args.targets.is_empty(),
+        "Only matching questions have targets"
+    );
+    ensure_whatever!(
+        kind == QuestionKind::Cloze || args.blanks.is_empty(),
+        "Only cloze questions have blanks"
+    );
+    ensure_whatever!(
+        kind != QuestionKind::Cloze || args.answer.is_none(),
+        "Cloze questions take their answers from --blank"
+    );
+
+    let draft = match kind {
+        QuestionKind::SingleSelection => read_single_selection(args)?,
+        QuestionKind::MultipleSelection => read_multiple_selection(args)?,
+        QuestionKind::Completion => read_completion(args)?,
+        QuestionKind::TrueFalse => read_true_false(args)?,
+        QuestionKind::Ordering => read_ordering(args)?,
+        QuestionKind::Matching => read_matching(args)?,
+        QuestionKind::Essay => read_essay(args)?,
+        QuestionKind::Cloze => read_cloze(args)?,
+    };
+
+    let similar = dedupe::find_similar(&repo, &draft).await?;
+    if !similar.is_empty() {
+        eprintln!();
+        for (id, similarity) in &similar {
+            eprintln!("Warning: Question {id} looks similar (similarity {similarity:.2})");
+        }
+        if !yes {
+            // Without a terminal there is nobody to confirm, so the closest match is reported.
+            if !prompt::is_interactive() {
+                let (id, _) = similar
+                    .iter()
+                    .max_by(|a, b| a.1.total_cmp(&b.1))
+                    .unwrap_or_else(|| unreachable!("Similar questions should not be empty"));
+                whatever!("Question is a duplicate of #{id}, pass --yes to add anyway");
+            }
+            if !prompt::confirm("Add anyway?")? {
+                eprintln!("Aborted");
+                return Ok(());
+            }
+        }
+    }
+
+    let id = repo
+        .insert_question(draft, author)
         .await
         .whatever_context("Could not add question")?;
 
+    if !tags.is_empty() {
+        repo.set_tags(id, tags)
+            .await
+            .whatever_context("Could not tag question")?;
+    }
+
+    if json {
+        println!("{}", serde_json::json!({ "id": id.inner() }));
+    } else {
+        println!();
+        println!("Added question {id}");
+    }
+
     Ok(())
 }
 
-async fn run_add_multiple_selection(repo: Arc<dyn QuestionRepository>) -> Result<(), Whatever> {
-    println!("");
-    let content = read_content()?;
-    let options = (1..=4)
-        .map(|i| {
-            println!("");
-            read_option(i)
-        })
-        .collect::<Result<Vec<_>, _>>()?;
-
-    let answer = loop {
-        println!("");
-        println!("What's your question's answer");
-        println!("(Input integers within [1, 4] separated by space)");
-        let mut answer = String::with_capacity(8);
-        io::stdin()
-            .read_line(&mut answer)
-            .whatever_context("Could not read input")?;
-        let answer = answer
-            .trim()
-            .split_whitespace()
-            .map(|x| x.parse())
-            .collect::<Result<Vec<_>, _>>();
-        match answer {
-            Ok(answer) if answer.iter().all(|x| (1..=4).contains(x)) => break answer,
-            _ => println!("Invalid input"),
+fn read_single_selection(args: AddArgs) -> Result<QuestionDraft, Whatever> {
+    let interactive = args.content.is_none();
+    let format = read_format(args.format, interactive)?;
+    let content = read_content(args.content)?;
+    let options = read_options(args.options)?;
+    let answer = match args.answer {
+        Some(answer) => prompt::parse_single_selection_answer(&answer)
+            .whatever_context("Answer must be one integer within [1, 4]")?,
+        None => {
+            prompt::begin("--answer")?;
+            prompt::read_single_selection_answer(None)?
+        }
+    };
+    let explanation = read_explanation(args.explanation, interactive)?;
+    let points = read_weight(args.points, interactive)?;
+
+    Ok(QuestionDraft::SingleSelection {
+        format,
+        content,
+        options,
+        answer,
+        explanation,
+        points,
+    })
+}
+
+fn read_multiple_selection(args: AddArgs) -> Result<QuestionDraft, Whatever> {
+    let interactive = args.content.is_none();
+    let format = read_format(args.format, interactive)?;
+    let content = read_content(args.content)?;
+    let options = read_options(args.options)?;
+    let answer = match args.answer {
+        Some(answer) => prompt::parse_multiple_selection_answer(&answer)
+            .whatever_context("Answer must be integers within [1, 4] separated by space")?,
+        None => {
+            prompt::begin("--answer")?;
+            prompt::read_multiple_selection_answer(None)?
+        }
+    };
+    let explanation = read_explanation(args.explanation, interactive)?;
+    let points = read_weight(args.points, interactive)?;
+
+    Ok(QuestionDraft::MultipleSelection {
+        format,
+        content,
+        options,
+        answer,
+        explanation,
+        points,
+    })
+}
+
+fn read_completion(args: AddArgs) -> Result<QuestionDraft, Whatever> {
+    let interactive = args.content.is_none();
+    let format = read_format(args.format, interactive)?;
+    let content = read_content(args.content)?;
+    let answer = match args.answer {
+        Some(answer) => answer,
+        None => {
+            prompt::begin("--answer")?;
+            prompt::read_completion_answer(None)?
+        }
+    };
+    let explanation = read_explanation(args.explanation, interactive)?;
+    let points = read_weight(args.points, interactive)?;
+
+    Ok(QuestionDraft::Completion {
+        format,
+        content,
+        answer,
+        explanation,
+        points,
+    })
+}
+
+fn read_true_false(args: AddArgs) -> Result<QuestionDraft, Whatever> {
+    let interactive = args.content.is_none();
+    let format = read_format(args.format, interactive)?;
+    let content = read_content(args.content)?;
+    let answer = match args.answer {
+        Some(answer) => prompt::parse_true_false_answer(&answer)
+            .whatever_context("Answer must be 'true' or 'false'")?,
+        None => {
+            prompt::begin("--answer")?;
+            prompt::read_true_false_answer(None)?
+        }
+    };
+    let explanation = read_explanation(args.explanation, interactive)?;
+    let points = read_weight(args.points, interactive)?;
+
+    Ok(QuestionDraft::TrueFalse {
+        format,
+        content,
+        answer,
+        explanation,
+        points,
+    })
+}
+
+fn read_ordering(args: AddArgs) -> Result<QuestionDraft, Whatever> {
+    let interactive = args.content.is_none();
+    let format = read_format(args.format, interactive)?;
+    let content = read_content(args.content)?;
+    let items = read_items(args.items)?;
+    let answer = match args.answer {
+        Some(answer) => {
+            prompt::parse_ordering_answer(&answer, items.len()).with_whatever_context(|| {
+                format!(
+                    "Answer must list every integer within [1, {}] once",
+                    items.len()
+                )
+            })?
+        }
+        None => {
+            prompt::begin("--answer")?;
+            prompt::read_ordering_answer(items.len(), None)?
+        }
+    };
+    let explanation = read_explanation(args.explanation, interactive)?;
+    let points = read_weight(args.points, interactive)?;
+
+    Ok(QuestionDraft::Ordering {
+        format,
+        content,
+        items,
+        answer,
+        explanation,
+        points,
+    })
+}
+
+fn read_matching(args: AddArgs) -> Result<QuestionDraft, Whatever> {
+    let interactive = args.content.is_none();
+    let format = read_format(args.format, interactive)?;
+    let content = read_content(args.content)?;
+    let items = read_items(args.items)?;
+    let targets = read_targets(args.targets)?;
+    let answer = match args.answer {
+        Some(answer) => prompt::parse_matching_answer(&answer, items.len(), targets.len())
+            .with_whatever_context(|| {
+                format!(
+                    "Answer must be {} integers within [1, {}]",
+                    items.len(),
+                    targets.len()
+                )
+            })?,
+        None => {
+            prompt::begin("--answer")?;
+            prompt::read_matching_answer(items.len(), targets.len(), None)?
+        }
+    };
+    let explanation = read_explanation(args.explanation, interactive)?;
+    let points = read_weight(args.points, interactive)?;
+
+    Ok(QuestionDraft::Matching {
+        format,
+        content,
+        items,
+        targets,
+        answer,
+        explanation,
+        points,
+    })
+}
+
+fn read_essay(args: AddArgs) -> Result<QuestionDraft, Whatever> {
+    let interactive = args.content.is_none();
+    let format = read_format(args.format, interactive)?;
+    let content = read_content(args.content)?;
+    let answer = match args.answer {
+        Some(answer) => answer,
+        None => {
+            prompt::begin("--answer")?;
+            prompt::read_essay_answer(None)?
         }
     };
+    let explanation = read_explanation(args.explanation, interactive)?;
+    let points = read_weight(args.points, interactive)?;
+
+    Ok(QuestionDraft::Essay {
+        format,
+        content,
+        answer,
+        explanation,
+        points,
+    })
+}
+
+fn read_cloze(args: AddArgs) -> Result<QuestionDraft, Whatever> {
+    let interactive = args.content.is_none();
+    let format = read_format(args.format, interactive)?;
+    let content = read_content(args.content)?;
+    let count = ClozeQuestion::blanks(&Content::sanitized(format, &content));
+    ensure_whatever!(
+        count > 0,
+        "Content must mark every blank with at least three underscores"
+    );
+
+    let answer = if args.blanks.is_empty() {
+        (1..=count)
+            .map(|i| {
+                prompt::begin("--blank")?;
+                prompt::read_blank(i, None)
+            })
+            .collect::<Result<_, _>>()?
+    } else {
+        ensure_whatever!(
+            args.blanks.len() == count,
+            "Content has {count} blanks but {} were given",
+            args.blanks.len()
+        );
+        args.blanks
+            .iter()
+            .map(|b| prompt::parse_blank(b).whatever_context("Blank must accept an answer"))
+            .collect::<Result<_, _>>()?
+    };
+    let explanation = read_explanation(args.explanation, interactive)?;
+    let points = read_weight(args.points, interactive)?;
 
-    if !(2..=3).contains(&answer.len()) {
-        println!("Warning: Expect the number of correct options to be 2 or 3");
+    Ok(QuestionDraft::Cloze {
+        format,
+        content,
+        answer,
+        explanation,
+        points,
+    })
+}
+
+// Questions given on the command line are in the default format unless told otherwise.
+fn read_format(format: Option<FormatArg>, interactive: bool) -> Result<ContentFormat, Whatever> {
+    match format {
+        Some(format) => Ok(format.into()),
+        None if interactive => {
+            prompt::begin("--format")?;
+            prompt::read_format(ContentFormat::default())
+        }
+        None => Ok(ContentFormat::default()),
     }
+}
 
-    repo.insert_multiple_selection(content, options, answer)
-        .await
-        .whatever_context("Could not add question")?;
+fn read_content(content: Option<String>) -> Result<String, Whatever> {
+    match content {
+        Some(content) => Ok(content),
+        None => {
+            prompt::begin("--content")?;
+            prompt::read_content(None)
+        }
+    }
+}
 
-    Ok(())
+// Only prompted for while authoring interactively, as it is optional.
+fn read_explanation(
+    explanation: Option<String>,
+    interactive: bool,
+) -> Result<Option<String>, Whatever> {
+    match explanation {
+        Some(explanation) => Ok(Some(explanation)),
+        None if interactive => {
+            prompt::begin("--explanation")?;
+            prompt::read_explanation(None)
+        }
+        None => Ok(None),
+    }
 }
 
-async fn run_add_completion(repo: Arc<dyn QuestionRepository>) -> Result<(), Whatever> {
-    println!("");
-    let content = read_content()?;
+fn read_weight(points: Option<u32>, interactive: bool) -> Result<Option<u32>, Whatever> {
+    match points {
+        Some(points) => Ok(Some(points)),
+        None if interactive => {
+            prompt::begin("--points")?;
+            prompt::read_weight(None)
+        }
+        None => Ok(None),
+    }
+}
 
-    println!("");
-    println!("What's your question's answer");
-    let mut answer = String::with_capacity(64);
-    io::stdin()
-        .read_line(&mut answer)
-        .whatever_context("Could not read input")?;
+fn read_options(mut options: Vec<String>) -> Result<Vec<String>, Whatever> {
+    ensure_whatever!(options.len() <= 4, "Question must have four options");
 
-    repo.insert_completion(content, answer.trim().to_owned())
-        .await
-        .whatever_context("Could not add question")?;
+    for i in options.len() + 1..=4 {
+        prompt::begin("--option")?;
+        options.push(prompt::read_option(i, None)?);
+    }
 
-    Ok(())
+    Ok(options)
 }
 
-fn read_content() -> Result<String, Whatever> {
-    println!("What's your question's content?");
-    let mut content = String::with_capacity(256);
-    io::stdin()
-        .read_line(&mut content)
-        .whatever_context("Could not read input")?;
-    Ok(content.trim().to_owned())
+fn read_items(items: Vec<String>) -> Result<Vec<String>, Whatever> {
+    if !items.is_empty() {
+        ensure_whatever!(items.len() >= 2, "Question must have at least two items");
+        return Ok(items);
+    }
+
+    prompt::begin("--item")?;
+    let count = prompt::read_item_count(None)?;
+    (1..=count)
+        .map(|i| {
+            prompt::begin("--item")?;
+            prompt::read_item(i, None)
+        })
+        .collect()
 }
 
-fn read_option(index: usize) -> Result<String, Whatever> {
-    println!("What's your question's option #{index}");
-    let mut option = String::with_capacity(64);
-    io::stdin()
-        .read_line(&mut option)
-        .whatever_context("Could not read input")?;
-    Ok(option.trim().to_owned())
+fn read_targets(targets: Vec<String>) -> Result<Vec<String>, Whatever> {
+    if !targets.is_empty() {
+        ensure_whatever!(
+            targets.len() >= 2,
+            "Question must have at least two targets"
+        );
+        return Ok(targets);
+    }
+
+    prompt::begin("--target")?;
+    let count = prompt::read_target_count(None)?;
+    (1..=count)
+        .map(|i| {
+            prompt::begin("--target")?;
+            prompt::read_target(i, None)
+        })
+        .collect()
 }
@@ -0,0 +1,50 @@
+use std::sync::Arc;
+
+use comfy_table::Table;
+use snafu::{prelude::*, Whatever};
+
+use crate::domain::entity::certificate::VerificationCode;
+use crate::domain::repository::score::ScoreRepository;
+
+pub async fn run_verify_certificate(
+    repo: Arc<dyn ScoreRepository>,
+    code: String,
+) -> Result<(), Whatever> {
+    let code = VerificationCode::try_new(&code)
+        .with_whatever_context(|_| format!("Invalid verification code {code}"))?;
+
+    let certificate = repo
+        .find_certificate(&code)
+        .await
+        .with_whatever_context(|_| format!("Could not verify certificate {code}"))?;
+
+    let points = &certificate.points;
+    let mut table = Table::new();
+    table.set_header(vec!["Field", "Value"]);
+    table.add_row(vec!["Code".to_owned(), certificate.code.to_string()]);
+    table.add_row(vec!["User".to_owned(), certificate.user.to_string()]);
+    table.add_row(vec![
+        "Score".to_owned(),
+        format!("{:.1}%", certificate.score.inner()),
+    ]);
+    table.add_row(vec![
+        "Points".to_owned(),
+        format!("{} / {}", points.net(), points.total()),
+    ]);
+    table.add_row(vec![
+        "Grade".to_owned(),
+        certificate.grade.letter().to_owned(),
+    ]);
+    table.add_row(vec![
+        "Completed".to_owned(),
+        certificate
+            .end_time
+            .format("%Y-%m-%d %H:%M:%S UTC")
+            .to_string(),
+    ]);
+
+    println!("{table}");
+    println!();
+    println!("Certificate {code} is valid");
+    Ok(())
+}
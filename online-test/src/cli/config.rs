@@ -0,0 +1,28 @@
+use snafu::{prelude::*, Whatever};
+
+use crate::config::Config;
+
+pub fn run_config_check(mut config: Config) -> Result<(), Whatever> {
+    config
+        .validate()
+        .whatever_context("Configuration is invalid")?;
+
+    // Directories are only needed by some commands, so their absence is not an error.
+    for (key, dir) in [
+        ("media.dir", &config.media.dir),
+        ("server.static_dir", &config.server.static_dir),
+    ] {
+        if !dir.is_dir() {
+            eprintln!("Warning: `{key}` {} is not a directory", dir.display());
+        }
+    }
+
+    if config.server.admin_token.is_some() {
+        config.server.admin_token = Some("********".to_owned());
+    }
+
+    let text = toml::to_string(&config).whatever_context("Could not print configuration")?;
+    println!("{text}");
+    println!("Configuration is valid");
+    Ok(())
+}
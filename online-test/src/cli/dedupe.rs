@@ -0,0 +1,230 @@
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+
+use clap::Args;
+use comfy_table::Table;
+use snafu::{prelude::*, Whatever};
+
+use crate::cli::prompt;
+use crate::domain::entity::content::Content;
+use crate::domain::entity::id::Id;
+use crate::domain::entity::question::{AbstractQuestion, QuestionKind};
+use crate::domain::repository::question::{
+    ListFilter, QuestionDraft, QuestionRepository, QuestionSummary,
+};
+use crate::domain::service::similarity::{self, Fingerprint};
+
+const PAGE_SIZE: usize = 500;
+
+#[derive(Debug, Args)]
+pub struct DedupeArgs {
+    /// Minimum similarity within [0, 1] for two questions to be reported
+    #[clap(long, default_value_t = similarity::DEFAULT_THRESHOLD)]
+    threshold: f64,
+    /// Keep the oldest question of each cluster, moving the tags of the others onto it and
+    /// retiring them
+    #[clap(long)]
+    merge: bool,
+    /// Merge without asking for confirmation
+    #[clap(long, short)]
+    yes: bool,
+}
+
+pub async fn run_dedupe(
+    repo: Arc<dyn QuestionRepository>,
+    args: DedupeArgs,
+) -> Result<(), Whatever> {
+    ensure_whatever!(
+        (0.0..=1.0).contains(&args.threshold),
+        "Threshold must be within [0, 1]"
+    );
+
+    let questions = load_all(&repo, None).await?;
+    let fingerprints: Vec<Fingerprint> = questions
+        .iter()
+        .map(|q| Fingerprint::from(&q.question))
+        .collect();
+
+    // Only questions of the same kind are duplicates, however alike their wording is.
+    let mut by_kind: HashMap<QuestionKind, Vec<usize>> = HashMap::new();
+    for (i, q) in questions.iter().enumerate() {
+        by_kind.entry(q.question.kind()).or_default().push(i);
+    }
+    let mut clusters: Vec<Vec<usize>> = by_kind
+        .values()
+        .flat_map(|indices| {
+            let kind_fingerprints: Vec<Fingerprint> =
+                indices.iter().map(|&i| fingerprints[i].clone()).collect();
+            similarity::clusters(&kind_fingerprints, args.threshold)
+                .into_iter()
+                .map(|cluster| cluster.into_iter().map(|i| indices[i]).collect())
+        })
+        .collect();
+    clusters.sort();
+
+    if clusters.is_empty() {
+        println!("No suspected duplicates");
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.set_header(vec!["Cluster", "ID", "Kind", "Similarity", "Content"]);
+    for (n, cluster) in clusters.iter().enumerate() {
+        let keeper = &fingerprints[cluster[0]];
+        for &i in cluster {
+            let q = &questions[i].question;
+            table.add_row(vec![
+                (n + 1).to_string(),
+                q.id().to_string(),
+                prompt::kind_name(q.kind()).to_owned(),
+                format!("{:.2}", keeper.similarity(&fingerprints[i])),
+                preview(q.content()),
+            ]);
+        }
+    }
+    println!("{table}");
+
+    if !args.merge {
+        return Ok(());
+    }
+
+    println!();
+    if !args.yes && !prompt::confirm("Merge every cluster into its oldest question?")? {
+        println!("Aborted");
+        return Ok(());
+    }
+
+    for cluster in clusters {
+        let keeper = &questions[cluster[0]];
+        let tags: BTreeSet<String> = cluster
+            .iter()
+            .flat_map(|&i| questions[i].tags.iter().cloned())
+            .collect();
+
+        if tags.len() > keeper.tags.len() {
+            repo.set_tags(keeper.question.id(), tags.into_iter().collect())
+                .await
+                .whatever_context("Could not tag question")?;
+        }
+
+        let merged: Vec<Id> = cluster[1..]
+            .iter()
+            .map(|&i| questions[i].question.id())
+            .collect();
+        repo.remove_questions(merged.clone())
+            .await
+            .whatever_context("Could not retire questions")?;
+        for id in merged {
+            println!("Merged question {id} into {}", keeper.question.id());
+        }
+    }
+
+    Ok(())
+}
+
+pub(super) async fn find_similar(
+    repo: &Arc<dyn QuestionRepository>,
+    draft: &QuestionDraft,
+) -> Result<Vec<(Id, f64)>, Whatever> {
+    let fingerprint = match draft {
+        QuestionDraft::SingleSelection {
+            format,
+            content,
+            options,
+            ..
+        }
+        | QuestionDraft::MultipleSelection {
+            format,
+            content,
+            options,
+            ..
+        }
+        | QuestionDraft::Ordering {
+            format,
+            content,
+            items: options,
+            ..
+        } => Fingerprint::new(
+            &Content::sanitized(*format, content),
+            &options
+                .iter()
+                .map(|o| Content::sanitized(*format, o))
+                .collect::<Vec<_>>(),
+        ),
+        QuestionDraft::Matching {
+            format,
+            content,
+            items,
+            targets,
+            ..
+        } => Fingerprint::new(
+            &Content::sanitized(*format, content),
+            &items
+                .iter()
+                .chain(targets)
+                .map(|o| Content::sanitized(*format, o))
+                .collect::<Vec<_>>(),
+        ),
+        QuestionDraft::Completion {
+            format, content, ..
+        }
+        | QuestionDraft::TrueFalse {
+            format, content, ..
+        }
+        | QuestionDraft::Essay {
+            format, content, ..
+        }
+        | QuestionDraft::Cloze {
+            format, content, ..
+        } => Fingerprint::new(&Content::sanitized(*format, content), &[]),
+    };
+
+    let similar = load_all(repo, Some(draft.kind()))
+        .await?
+        .into_iter()
+        .map(|q| {
+            let similarity = fingerprint.similarity(&Fingerprint::from(&q.question));
+            (q.question.id(), similarity)
+        })
+        .filter(|(_, similarity)| *similarity >= similarity::DEFAULT_THRESHOLD)
+        .collect();
+
+    Ok(similar)
+}
+
+async fn load_all(
+    repo: &Arc<dyn QuestionRepository>,
+    kind: Option<QuestionKind>,
+) -> Result<Vec<QuestionSummary>, Whatever> {
+    let mut questions = Vec::new();
+
+    loop {
+        let page = repo
+            .list_questions(ListFilter {
+                kind,
+                tag: None,
+                search: None,
+                limit: PAGE_SIZE,
+                offset: questions.len(),
+            })
+            .await
+            .whatever_context("Could not load questions")?;
+
+        let last = page.len() < PAGE_SIZE;
+        questions.extend(page);
+        if last {
+            // Pages run from the newest questions, so the oldest come first once sorted.
+            questions.sort_by_key(|q| q.question.id());
+            return Ok(questions);
+        }
+    }
+}
+
+fn preview(content: &Content) -> String {
+    let source = content.source();
+    let mut preview = source.chars().take(65).collect::<String>();
+    if source.chars().count() > 65 {
+        preview.push_str("...");
+    }
+    preview
+}
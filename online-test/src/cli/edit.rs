@@ -0,0 +1,327 @@
+use std::sync::Arc;
+
+use snafu::{prelude::*, Whatever};
+
+use crate::cli::prompt;
+use crate::domain::entity::content::{Content, ContentFormat};
+use crate::domain::entity::id::Id;
+use crate::domain::entity::question::{AbstractQuestion, ClozeQuestion, Question, QuestionKind};
+use crate::domain::repository::question::{QuestionDraft, QuestionRepository};
+
+pub async fn run_edit(
+    repo: Arc<dyn QuestionRepository>,
+    id: usize,
+    author: String,
+) -> Result<(), Whatever> {
+    let id: Id = id.into();
+    let question = repo
+        .select_questions_by_id(vec![id])
+        .await
+        .whatever_context("Could not load question")?
+        .pop()
+        .whatever_context(format!("Could not find question with ID {id}"))?;
+    let prefill = Prefill::from(&question);
+
+    println!(
+        "Editing question {id} ({})",
+        prompt::kind_name(question.kind())
+    );
+    println!();
+    let kind = prompt::read_kind(Some(question.kind()))?;
+    println!();
+    let format = prompt::read_format(prefill.format)?;
+    println!();
+    let content = prompt::read_content(Some(&prefill.content))?;
+
+    let draft = match kind {
+        QuestionKind::SingleSelection => {
+            let options = read_options(prefill.options.as_deref())?;
+            println!();
+            let answer = prompt::read_single_selection_answer(prefill.single_selection)?;
+            println!();
+            let explanation = prompt::read_explanation(prefill.explanation.as_deref())?;
+            println!();
+            let points = prompt::read_weight(prefill.points)?;
+
+            QuestionDraft::SingleSelection {
+                format,
+                content,
+                options,
+                answer,
+                explanation,
+                points,
+            }
+        }
+        QuestionKind::MultipleSelection => {
+            let options = read_options(prefill.options.as_deref())?;
+            println!();
+            let answer =
+                prompt::read_multiple_selection_answer(prefill.multiple_selection.as_deref())?;
+            println!();
+            let explanation = prompt::read_explanation(prefill.explanation.as_deref())?;
+            println!();
+            let points = prompt::read_weight(prefill.points)?;
+
+            QuestionDraft::MultipleSelection {
+                format,
+                content,
+                options,
+                answer,
+                explanation,
+                points,
+            }
+        }
+        QuestionKind::Completion => {
+            println!();
+            let answer = prompt::read_completion_answer(prefill.completion.as_deref())?;
+            println!();
+            let explanation = prompt::read_explanation(prefill.explanation.as_deref())?;
+            println!();
+            let points = prompt::read_weight(prefill.points)?;
+
+            QuestionDraft::Completion {
+                format,
+                content,
+                answer,
+                explanation,
+                points,
+            }
+        }
+        QuestionKind::TrueFalse => {
+            println!();
+            let answer = prompt::read_true_false_answer(prefill.true_false)?;
+            println!();
+            let explanation = prompt::read_explanation(prefill.explanation.as_deref())?;
+            println!();
+            let points = prompt::read_weight(prefill.points)?;
+
+            QuestionDraft::TrueFalse {
+                format,
+                content,
+                answer,
+                explanation,
+                points,
+            }
+        }
+        QuestionKind::Ordering => {
+            let items = read_items(prefill.items.as_deref())?;
+            println!();
+            let default = prefill
+                .ordering
+                .as_deref()
+                .filter(|a| a.len() == items.len());
+            let answer = prompt::read_ordering_answer(items.len(), default)?;
+            println!();
+            let explanation = prompt::read_explanation(prefill.explanation.as_deref())?;
+            println!();
+            let points = prompt::read_weight(prefill.points)?;
+
+            QuestionDraft::Ordering {
+                format,
+                content,
+                items,
+                answer,
+                explanation,
+                points,
+            }
+        }
+        QuestionKind::Matching => {
+            let items = read_items(prefill.items.as_deref())?;
+            let targets = read_targets(prefill.targets.as_deref())?;
+            println!();
+            let default = prefill.matching.as_deref().filter(|a| {
+                a.len() == items.len() && a.iter().all(|t| (*t as usize) < targets.len())
+            });
+            let answer = prompt::read_matching_answer(items.len(), targets.len(), default)?;
+            println!();
+            let explanation = prompt::read_explanation(prefill.explanation.as_deref())?;
+            println!();
+            let points = prompt::read_weight(prefill.points)?;
+
+            QuestionDraft::Matching {
+                format,
+                content,
+                items,
+                targets,
+                answer,
+                explanation,
+                points,
+            }
+        }
+        QuestionKind::Essay => {
+            println!();
+            let answer = prompt::read_essay_answer(prefill.essay.as_deref())?;
+            println!();
+            let explanation = prompt::read_explanation(prefill.explanation.as_deref())?;
+            println!();
+            let points = prompt::read_weight(prefill.points)?;
+
+            QuestionDraft::Essay {
+                format,
+                content,
+                answer,
+                explanation,
+                points,
+            }
+        }
+        QuestionKind::Cloze => {
+            let count = ClozeQuestion::blanks(&Content::sanitized(format, &content));
+            ensure_whatever!(
+                count > 0,
+                "Content must mark every blank with at least three underscores"
+            );
+            let answer = (1..=count)
+                .map(|i| {
+                    println!();
+                    let default = prefill.cloze.as_ref().and_then(|c| c.get(i - 1));
+                    prompt::read_blank(i, default.map(Vec::as_slice))
+                })
+                .collect::<Result<_, _>>()?;
+            println!();
+            let explanation = prompt::read_explanation(prefill.explanation.as_deref())?;
+            println!();
+            let points = prompt::read_weight(prefill.points)?;
+
+            QuestionDraft::Cloze {
+                format,
+                content,
+                answer,
+                explanation,
+                points,
+            }
+        }
+    };
+
+    repo.update_question(id, draft, author)
+        .await
+        .whatever_context("Could not edit question")?;
+
+    Ok(())
+}
+
+fn read_options(defaults: Option<&[String]>) -> Result<Vec<String>, Whatever> {
+    (1..=4)
+        .map(|i| {
+            println!();
+            let default = defaults.and_then(|d| d.get(i - 1)).map(String::as_str);
+            prompt::read_option(i, default)
+        })
+        .collect()
+}
+
+fn read_items(defaults: Option<&[String]>) -> Result<Vec<String>, Whatever> {
+    println!();
+    let count = prompt::read_item_count(defaults.map(<[String]>::len))?;
+    (1..=count)
+        .map(|i| {
+            println!();
+            let default = defaults.and_then(|d| d.get(i - 1)).map(String::as_str);
+            prompt::read_item(i, default)
+        })
+        .collect()
+}
+
+fn read_targets(defaults: Option<&[String]>) -> Result<Vec<String>, Whatever> {
+    println!();
+    let count = prompt::read_target_count(defaults.map(<[String]>::len))?;
+    (1..=count)
+        .map(|i| {
+            println!();
+            let default = defaults.and_then(|d| d.get(i - 1)).map(String::as_str);
+            prompt::read_target(i, default)
+        })
+        .collect()
+}
+
+// Answers are carried over to another kind only when the conversion is lossless,
+// otherwise they have to be re-entered.
+struct Prefill {
+    format: ContentFormat,
+    content: String,
+    options: Option<Vec<String>>,
+    single_selection: Option<u32>,
+    multiple_selection: Option<Vec<u32>>,
+    completion: Option<String>,
+    true_false: Option<bool>,
+    items: Option<Vec<String>>,
+    ordering: Option<Vec<u32>>,
+    targets: Option<Vec<String>>,
+    matching: Option<Vec<u32>>,
+    essay: Option<String>,
+    cloze: Option<Vec<Vec<String>>>,
+    explanation: Option<String>,
+    points: Option<u32>,
+}
+
+impl From<&Question> for Prefill {
+    fn from(question: &Question) -> Self {
+        let mut prefill = Self {
+            format: question.content().format(),
+            content: question.content().source().to_owned(),
+            options: None,
+            single_selection: None,
+            multiple_selection: None,
+            completion: None,
+            true_false: None,
+            items: None,
+            ordering: None,
+            targets: None,
+            matching: None,
+            essay: None,
+            cloze: None,
+            explanation: question.explanation().map(|e| e.source().to_owned()),
+            points: question.points(),
+        };
+
+        match question {
+            Question::SingleSelection(q) => {
+                prefill.options = Some(sources(q.options()));
+                prefill.single_selection = Some(q.answer().value());
+                prefill.multiple_selection = Some(vec![q.answer().value()]);
+            }
+            Question::MultipleSelection(q) => {
+                prefill.options = Some(sources(q.options()));
+                prefill.multiple_selection = Some(q.answer().value().to_vec());
+                if let [answer] = q.answer().value() {
+                    prefill.single_selection = Some(*answer);
+                }
+            }
+            Question::Completion(q) => {
+                prefill.completion = Some(q.answer().value().to_owned());
+                prefill.essay = Some(q.answer().value().to_owned());
+                prefill.cloze = Some(vec![vec![q.answer().value().to_owned()]]);
+            }
+            Question::TrueFalse(q) => {
+                prefill.true_false = Some(q.answer().value());
+            }
+            Question::Ordering(q) => {
+                prefill.items = Some(sources(q.items()));
+                prefill.ordering = Some(q.answer().value().to_vec());
+            }
+            Question::Matching(q) => {
+                prefill.items = Some(sources(q.items()));
+                prefill.targets = Some(sources(q.targets()));
+                prefill.matching = Some(q.answer().targets());
+            }
+            Question::Essay(q) => {
+                let answer = q.answer().value();
+                prefill.essay = Some(answer.to_owned());
+                prefill.completion = (!answer.is_empty()).then(|| answer.to_owned());
+            }
+            Question::Cloze(q) => {
+                prefill.cloze = Some(q.answer().value().to_vec());
+                if let [blank] = q.answer().value() {
+                    if let [answer] = blank.as_slice() {
+                        prefill.completion = Some(answer.to_owned());
+                    }
+                }
+            }
+        }
+
+        prefill
+    }
+}
+
+fn sources(contents: &[Content]) -> Vec<String> {
+    contents.iter().map(|c| c.source().to_owned()).collect()
+}
@@ -0,0 +1,208 @@
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use chrono::{NaiveDate, SecondsFormat, TimeDelta};
+use clap::{Args, ValueEnum};
+use rust_xlsxwriter::{Format, Workbook, XlsxError};
+use snafu::{prelude::*, Whatever};
+
+use crate::domain::entity::user::User;
+use crate::domain::repository::group::GroupRepository;
+use crate::domain::repository::score::{Record, RecordFilter, ScoreRepository};
+
+const HEADER: [&str; 10] = [
+    "name",
+    "score",
+    "points",
+    "penalty",
+    "total_points",
+    "grade",
+    "passed",
+    "pending",
+    "end_time",
+    "duration",
+];
+
+#[derive(Debug, Args)]
+pub struct ExportArgs {
+    /// Only export attempts of these users
+    #[clap(long = "user", value_name = "USER")]
+    users: Vec<String>,
+    /// Only export attempts of members of this group
+    #[clap(long)]
+    group: Option<String>,
+    /// Only export attempts ending on or after this date (UTC, YYYY-MM-DD)
+    #[clap(long)]
+    since: Option<NaiveDate>,
+    /// Only export attempts ending on or before this date (UTC, YYYY-MM-DD)
+    #[clap(long)]
+    until: Option<NaiveDate>,
+    /// Output format, inferred from the output file extension if omitted
+    #[clap(long, value_enum)]
+    format: Option<ExportFormat>,
+    /// File to write to instead of standard output
+    #[clap(long, short)]
+    output: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ExportFormat {
+    Csv,
+    Xlsx,
+}
+
+pub async fn run_export_scores(
+    score_repo: Arc<dyn ScoreRepository>,
+    group_repo: Arc<dyn GroupRepository>,
+    args: ExportArgs,
+) -> Result<(), Whatever> {
+    let format = args.format.unwrap_or_else(|| {
+        let xlsx = args
+            .output
+            .as_deref()
+            .and_then(Path::extension)
+            .is_some_and(|e| e.eq_ignore_ascii_case("xlsx"));
+        if xlsx {
+            ExportFormat::Xlsx
+        } else {
+            ExportFormat::Csv
+        }
+    });
+    ensure_whatever!(
+        format == ExportFormat::Csv || args.output.is_some(),
+        "XLSX could not be written to standard output, use --output to give a file"
+    );
+
+    let mut users = args
+        .users
+        .iter()
+        .map(|u| User::try_new(u).with_whatever_context(|_| format!("Invalid username {u}")))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // Given both, only the listed users who are also members of the group are exported.
+    if let Some(group) = &args.group {
+        let members = group_repo
+            .list_members(group)
+            .await
+            .whatever_context("Could not load group members")?;
+
+        users = if users.is_empty() {
+            members
+        } else {
+            users.into_iter().filter(|u| members.contains(u)).collect()
+        };
+        ensure_whatever!(!users.is_empty(), "No listed user is in group {group}");
+    }
+
+    let filter = RecordFilter {
+        users: (!users.is_empty()).then_some(users),
+        since: args.since.map(|d| d.and_time(Default::default()).and_utc()),
+        until: args.until.map(|d| {
+            (d + TimeDelta::days(1))
+                .and_time(Default::default())
+                .and_utc()
+        }),
+    };
+
+    let records = score_repo
+        .list_records(filter)
+        .await
+        .whatever_context("Could not load scores")?;
+    let rows: Vec<[String; 10]> = records.iter().map(|(u, r)| row(u, r)).collect();
+
+    match (format, &args.output) {
+        (ExportFormat::Csv, None) => write_csv(io::stdout().lock(), &rows)?,
+        (ExportFormat::Csv, Some(path)) => {
+            let file = File::create(path)
+                .with_whatever_context(|_| format!("Could not create {}", path.display()))?;
+            write_csv(file, &rows)?;
+        }
+        (ExportFormat::Xlsx, Some(path)) => write_xlsx(path, &records)?,
+        (ExportFormat::Xlsx, None) => unreachable!("XLSX should be written to a file"),
+    }
+
+    if let Some(path) = &args.output {
+        println!("Exported {} attempts to {}", rows.len(), path.display());
+    }
+    Ok(())
+}
+
+fn row(user: &User, record: &Record) -> [String; 10] {
+    [
+        user.to_string(),
+        record.score.inner().to_string(),
+        record.points.earned().to_string(),
+        record.points.penalty().to_string(),
+        record.points.total().to_string(),
+        record
+            .grade
+            .as_ref()
+            .map(|g| g.letter().to_owned())
+            .unwrap_or_default(),
+        record
+            .grade
+            .as_ref()
+            .map(|g| g.passed().to_string())
+            .unwrap_or_default(),
+        record.pending.to_string(),
+        record.end_time.to_rfc3339_opts(SecondsFormat::Secs, true),
+        record.duration.as_secs().to_string(),
+    ]
+}
+
+fn write_csv(writer: impl Write, rows: &[[String; 10]]) -> Result<(), Whatever> {
+    let mut writer = csv::Writer::from_writer(writer);
+
+    writer
+        .write_record(HEADER)
+        .whatever_context("Could not write CSV")?;
+    for row in rows {
+        writer
+            .write_record(row)
+            .whatever_context("Could not write CSV")?;
+    }
+
+    writer.flush().whatever_context("Could not write CSV")
+}
+
+fn write_xlsx(path: &Path, records: &[(User, Record)]) -> Result<(), Whatever> {
+    new_workbook(records)
+        .and_then(|mut workbook| workbook.save(path))
+        .with_whatever_context(|_| format!("Could not write {}", path.display()))
+}
+
+// Numbers and flags are written as typed cells, so that they can be computed on directly.
+fn new_workbook(records: &[(User, Record)]) -> Result<Workbook, XlsxError> {
+    let mut workbook = Workbook::new();
+    let bold = Format::new().set_bold();
+    let worksheet = workbook.add_worksheet().set_name("Scores")?;
+
+    for (col, title) in HEADER.iter().enumerate() {
+        worksheet.write_string_with_format(0, col as u16, *title, &bold)?;
+    }
+
+    for (i, (user, record)) in records.iter().enumerate() {
+        let row = i as u32 + 1;
+        worksheet.write_string(row, 0, user.inner())?;
+        worksheet.write_number(row, 1, record.score.inner())?;
+        worksheet.write_number(row, 2, record.points.earned())?;
+        worksheet.write_number(row, 3, record.points.penalty())?;
+        worksheet.write_number(row, 4, record.points.total())?;
+        if let Some(grade) = &record.grade {
+            worksheet.write_string(row, 5, grade.letter())?;
+            worksheet.write_boolean(row, 6, grade.passed())?;
+        }
+        worksheet.write_boolean(row, 7, record.pending)?;
+        worksheet.write_string(
+            row,
+            8,
+            record.end_time.to_rfc3339_opts(SecondsFormat::Secs, true),
+        )?;
+        worksheet.write_number(row, 9, record.duration.as_secs() as f64)?;
+    }
+
+    worksheet.autofit();
+    Ok(workbook)
+}
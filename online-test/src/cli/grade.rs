@@ -0,0 +1,118 @@
+use clap::Args;
+use comfy_table::Table;
+use snafu::{prelude::*, Whatever};
+
+use crate::cli::prompt;
+use crate::domain::entity::question::{AbstractQuestion, Question};
+use crate::domain::service::grading::GradingService;
+
+#[derive(Debug, Args)]
+pub struct GradeArgs {
+    /// ID of the pending item to grade
+    id: usize,
+    /// Points within [0, the points the item is worth] awarded to the item, prompted for if omitted
+    #[clap(long)]
+    points: Option<f32>,
+    /// Comment on the item, prompted for if points are omitted
+    #[clap(long)]
+    comment: Option<String>,
+}
+
+pub async fn run_grade_list(service: GradingService) -> Result<(), Whatever> {
+    let mut table = Table::new();
+    table.set_header(vec![
+        "Item",
+        "User",
+        "Question",
+        "Points",
+        "Submitted At",
+        "Response",
+    ]);
+
+    service
+        .pending()
+        .await
+        .whatever_context("Could not list pending items")?
+        .into_iter()
+        .for_each(|r| {
+            let mut response = r.item.response.chars().take(65).collect::<String>();
+            if r.item.response.chars().count() > 65 {
+                response.push_str("...");
+            }
+
+            table.add_row(vec![
+                r.item.id.to_string(),
+                r.item.user.to_string(),
+                r.question.id().to_string(),
+                r.item.points.to_string(),
+                r.item.end_time.to_rfc3339(),
+                response,
+            ]);
+        });
+
+    println!("{table}");
+    Ok(())
+}
+
+pub async fn run_grade(
+    service: GradingService,
+    grader: String,
+    args: GradeArgs,
+) -> Result<(), Whatever> {
+    let (points, comment) = match args.points {
+        Some(points) => (points, args.comment),
+        None => {
+            let review = service
+                .pending()
+                .await
+                .whatever_context("Could not list pending items")?
+                .into_iter()
+                .find(|r| r.item.id == args.id.into())
+                .whatever_context(format!("Could not find pending item {}", args.id))?;
+
+            println!(
+                "Item {} by {} on question {}, worth {} points",
+                review.item.id,
+                review.item.user,
+                review.question.id(),
+                review.item.points
+            );
+            println!();
+            println!("Question:");
+            println!("{}", review.question.content().source());
+            if let Question::Essay(q) = &review.question {
+                if !q.answer().value().is_empty() {
+                    println!();
+                    println!("Reference answer:");
+                    println!("{}", q.answer().value());
+                }
+            }
+            println!();
+            println!("Response:");
+            println!("{}", review.item.response);
+            println!();
+
+            let points = prompt::read_points(review.item.points as f32)?;
+            println!();
+            let comment = prompt::read_comment(args.comment.as_deref())?;
+            (points, Some(comment))
+        }
+    };
+
+    let score = service
+        .grade(args.id.into(), points, comment, grader)
+        .await
+        .whatever_context("Could not grade item")?;
+
+    println!();
+    match score {
+        Some(score) => println!(
+            "Graded item {}, attempt completed with score {:.1}",
+            args.id,
+            score.inner()
+        ),
+        None => println!("Graded item {}, attempt still has pending items", args.id),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,86 @@
+use std::sync::Arc;
+
+use clap::Args;
+use comfy_table::Table;
+use snafu::{prelude::*, Whatever};
+
+use crate::domain::entity::user::User;
+use crate::domain::repository::group::GroupRepository;
+
+#[derive(Debug, Args)]
+pub struct GroupMembersArgs {
+    /// Name of the group
+    group: String,
+    /// Usernames of the members
+    #[clap(required = true)]
+    users: Vec<String>,
+}
+
+impl GroupMembersArgs {
+    fn users(&self) -> Result<Vec<User>, Whatever> {
+        self.users
+            .iter()
+            .map(|u| User::try_new(u).with_whatever_context(|_| format!("Invalid username {u}")))
+            .collect()
+    }
+}
+
+pub async fn run_group_add(
+    repo: Arc<dyn GroupRepository>,
+    args: GroupMembersArgs,
+) -> Result<(), Whatever> {
+    ensure_whatever!(
+        !args.group.trim().is_empty(),
+        "Group name should not be empty"
+    );
+
+    repo.add_members(&args.group, args.users()?)
+        .await
+        .whatever_context("Could not add group members")?;
+
+    Ok(())
+}
+
+pub async fn run_group_remove(
+    repo: Arc<dyn GroupRepository>,
+    args: GroupMembersArgs,
+) -> Result<(), Whatever> {
+    let removed = repo
+        .remove_members(&args.group, args.users()?)
+        .await
+        .whatever_context("Could not remove group members")?;
+
+    ensure_whatever!(removed > 0, "None of the users is in group {}", args.group);
+    Ok(())
+}
+
+// Lists every group, or the members of one group when it is given.
+pub async fn run_group_list(
+    repo: Arc<dyn GroupRepository>,
+    group: Option<String>,
+) -> Result<(), Whatever> {
+    let mut table = Table::new();
+
+    if let Some(group) = group {
+        table.set_header(vec!["User"]);
+        repo.list_members(&group)
+            .await
+            .whatever_context("Could not list group members")?
+            .into_iter()
+            .for_each(|user| {
+                table.add_row(vec![user.to_string()]);
+            });
+    } else {
+        table.set_header(vec!["Group", "Members"]);
+        repo.list_groups()
+            .await
+            .whatever_context("Could not list groups")?
+            .into_iter()
+            .for_each(|(group, members)| {
+                table.add_row(vec![group, members.to_string()]);
+            });
+    }
+
+    println!("{table}");
+    Ok(())
+}
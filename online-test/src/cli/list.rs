@@ -1,22 +1,97 @@
 use std::sync::Arc;
 
+use clap::Args;
 use comfy_table::Table;
 use snafu::{prelude::*, Whatever};
 
-use crate::domain::repository::question::QuestionRepository;
+use crate::cli::add::KindArg;
+use crate::cli::prompt;
+use crate::domain::entity::content::Content;
+use crate::domain::entity::question::{AbstractQuestion, Question};
+use crate::domain::repository::question::{ListFilter, QuestionRepository};
+
+#[derive(Debug, Args)]
+pub struct ListArgs {
+    /// Only list questions of this kind
+    #[clap(long, value_enum)]
+    kind: Option<KindArg>,
+    /// Only list questions with this tag
+    #[clap(long)]
+    tag: Option<String>,
+    /// Only list questions whose content or options contain all of these words
+    #[clap(long)]
+    search: Option<String>,
+    /// Maximum number of questions to list
+    #[clap(long, default_value_t = 20)]
+    limit: usize,
+    /// Number of newest questions to skip
+    #[clap(long, default_value_t = 0)]
+    offset: usize,
+    /// Show untruncated content along with options and answers
+    #[clap(long)]
+    full: bool,
+}
+
+pub async fn run_list(repo: Arc<dyn QuestionRepository>, args: ListArgs) -> Result<(), Whatever> {
+    let filter = ListFilter {
+        kind: args.kind.map(Into::into),
+        tag: args.tag,
+        search: args.search,
+        limit: args.limit,
+        offset: args.offset,
+    };
 
-pub async fn run_list(repo: Arc<dyn QuestionRepository>) -> Result<(), Whatever> {
     let mut table = Table::new();
-    table.set_header(vec!["ID", "Kind", "Content"]);
+    if args.full {
+        table.set_header(vec!["ID", "Kind", "Tags", "Content", "Options", "Answer"]);
+    } else {
+        table.set_header(vec!["ID", "Kind", "Tags", "Content"]);
+    }
 
-    repo.list_questions(20)
+    repo.list_questions(filter)
         .await
         .whatever_context("Could not list questions")?
         .into_iter()
-        .for_each(|q| {
-            table.add_row(q);
+        .for_each(|s| {
+            let q = &s.question;
+            let mut row = vec![
+                q.id().to_string(),
+                prompt::kind_name(q.kind()).to_owned(),
+                s.tags.join(", "),
+            ];
+
+            if args.full {
+                row.push(q.content().source().to_owned());
+                row.push(options_text(q));
+                row.push(prompt::answer_text(q));
+            } else {
+                let source = q.content().source();
+                let mut content = source.chars().take(65).collect::<String>();
+                if source.chars().count() > 65 {
+                    content.push_str("...");
+                }
+                row.push(content);
+            }
+
+            table.add_row(row);
         });
 
     println!("{table}");
     Ok(())
 }
+
+fn options_text(question: &Question) -> String {
+    let text = |contents: &[Content]| {
+        contents
+            .iter()
+            .enumerate()
+            .map(|(i, o)| format!("{}. {}", i + 1, o.source()))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    match question.targets() {
+        [] => text(question.choices()),
+        targets => format!("{}\n---\n{}", text(question.choices()), text(targets)),
+    }
+}
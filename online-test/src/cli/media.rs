@@ -0,0 +1,90 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use clap::Args;
+use comfy_table::Table;
+use snafu::{prelude::*, Whatever};
+
+use crate::domain::repository::media::{Media, MediaRepository};
+
+#[derive(Debug, Args)]
+pub struct MediaAddArgs {
+    /// ID of the question the file is attached to
+    question: usize,
+    /// Path of the file to upload
+    path: PathBuf,
+    /// File name shown to users (defaults to the name of the uploaded file)
+    #[clap(long)]
+    name: Option<String>,
+    /// Print the result as JSON
+    #[clap(long)]
+    json: bool,
+}
+
+pub async fn run_media_add(
+    repo: Arc<dyn MediaRepository>,
+    args: MediaAddArgs,
+) -> Result<(), Whatever> {
+    let file_name = match args.name {
+        Some(name) => name,
+        None => args
+            .path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .whatever_context("Could not determine file name, use --name to give one")?
+            .to_owned(),
+    };
+
+    let data = tokio::fs::read(&args.path)
+        .await
+        .with_whatever_context(|_| format!("Could not read {}", args.path.display()))?;
+
+    let id = repo
+        .insert_media(args.question.into(), file_name, data)
+        .await
+        .whatever_context("Could not add media")?;
+
+    if args.json {
+        println!("{}", serde_json::json!({ "id": id.inner() }));
+    } else {
+        println!(
+            "Added media {id}, reference it in content as {}:{id}",
+            Media::SCHEME
+        );
+    }
+
+    Ok(())
+}
+
+pub async fn run_media_list(
+    repo: Arc<dyn MediaRepository>,
+    question: usize,
+) -> Result<(), Whatever> {
+    let mut table = Table::new();
+    table.set_header(vec!["ID", "File", "Type", "Size", "Added At"]);
+
+    repo.list_media(question.into())
+        .await
+        .whatever_context("Could not list media")?
+        .into_iter()
+        .for_each(|m| {
+            table.add_row(vec![
+                m.id.to_string(),
+                m.file_name,
+                m.mime_type,
+                m.size.to_string(),
+                m.created_at.to_rfc3339(),
+            ]);
+        });
+
+    println!("{table}");
+    Ok(())
+}
+
+pub async fn run_media_remove(repo: Arc<dyn MediaRepository>, id: usize) -> Result<(), Whatever> {
+    repo.remove_media(id.into())
+        .await
+        .whatever_context("Could not remove media")?;
+
+    Ok(())
+}
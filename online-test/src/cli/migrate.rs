@@ -0,0 +1,61 @@
+use comfy_table::Table;
+use snafu::{prelude::*, Whatever};
+
+use crate::repository::migration::{MigrationError, Migrator};
+
+pub async fn run_migrate_status(migrator: Migrator) -> Result<(), Whatever> {
+    let migrations = migrator
+        .status()
+        .await
+        .whatever_context("Could not read migration status")?;
+
+    let mut table = Table::new();
+    table.set_header(vec!["Migration", "Applied"]);
+    for migration in migrations {
+        let applied = if migration.applied { "yes" } else { "no" };
+        table.add_row(vec![migration.name, applied.to_owned()]);
+    }
+
+    println!("{table}");
+    Ok(())
+}
+
+pub async fn run_migrate_up(migrator: Migrator) -> Result<(), Whatever> {
+    let applied = migrator
+        .run_pending()
+        .await
+        .whatever_context("Could not apply migrations")?;
+
+    if applied.is_empty() {
+        println!("Database is up to date");
+    }
+    for name in applied {
+        println!("Applied migration {name}");
+    }
+    Ok(())
+}
+
+pub async fn run_migrate_down(migrator: Migrator, steps: usize) -> Result<(), Whatever> {
+    let reverted = migrator
+        .revert(steps)
+        .await
+        .whatever_context("Could not revert migrations")?;
+
+    for name in reverted {
+        println!("Reverted migration {name}");
+    }
+    Ok(())
+}
+
+pub async fn run_seed(migrator: Migrator, force: bool) -> Result<(), Whatever> {
+    match migrator.seed(force).await {
+        Ok(count) => {
+            println!("Added {count} sample questions");
+            Ok(())
+        }
+        Err(MigrationError::NotEmpty { count }) => {
+            whatever!("Database already has {count} questions, pass --force to seed anyway")
+        }
+        Err(err) => Err(err).whatever_context("Could not seed database"),
+    }
+}
@@ -1,7 +1,32 @@
 mod add;
+mod certificate;
+mod config;
+mod dedupe;
+mod edit;
+mod export;
+mod grade;
+mod group;
 mod list;
+mod media;
+mod migrate;
+mod prompt;
 mod remove;
+mod report;
+mod revisions;
+mod tag;
 
-pub use add::run_add;
-pub use list::run_list;
-pub use remove::run_remove;
+pub use add::{run_add, AddArgs};
+pub use certificate::run_verify_certificate;
+pub use config::run_config_check;
+pub use dedupe::{run_dedupe, DedupeArgs};
+pub use edit::run_edit;
+pub use export::{run_export_scores, ExportArgs};
+pub use grade::{run_grade, run_grade_list, GradeArgs};
+pub use group::{run_group_add, run_group_list, run_group_remove, GroupMembersArgs};
+pub use list::{run_list, ListArgs};
+pub use media::{run_media_add, run_media_list, run_media_remove, MediaAddArgs};
+pub use migrate::{run_migrate_down, run_migrate_status, run_migrate_up, run_seed};
+pub use remove::{run_remove, RemoveArgs};
+pub use report::{run_report, ReportArgs};
+pub use revisions::{run_revisions_diff, run_revisions_list};
+pub use tag::{run_tag, TagArgs};
@@ -0,0 +1,551 @@
+use std::io::{self, IsTerminal};
+
+use snafu::{prelude::*, Whatever};
+
+use crate::domain::entity::content::ContentFormat;
+use crate::domain::entity::question::{Question, QuestionKind};
+
+// Prompts are written to stderr, so that the output of commands such as `--json` stays on
+// stdout alone.
+
+// Starts a prompt for a value missing from the command line. Nobody could answer it without a
+// terminal, so the missing flag is reported instead.
+pub fn begin(missing: &str) -> Result<(), Whatever> {
+    ensure_whatever!(
+        is_interactive(),
+        "Missing {missing}, which cannot be prompted for without a terminal"
+    );
+    eprintln!();
+    Ok(())
+}
+
+pub fn is_interactive() -> bool {
+    io::stdin().is_terminal()
+}
+
+pub fn read_kind(default: Option<QuestionKind>) -> Result<QuestionKind, Whatever> {
+    eprintln!("What's your question's kind?");
+    eprintln!(
+        "([s]ingle-selection, [m]ultiple-selection, [c]ompletion, [t]rue-false, [o]rdering, matc[h]ing, [e]ssay, clo[z]e)"
+    );
+    print_default(default.map(kind_name));
+
+    loop {
+        match (read_line()?.chars().next(), default) {
+            (Some('s') | Some('S'), _) => return Ok(QuestionKind::SingleSelection),
+            (Some('m') | Some('M'), _) => return Ok(QuestionKind::MultipleSelection),
+            (Some('c') | Some('C'), _) => return Ok(QuestionKind::Completion),
+            (Some('t') | Some('T'), _) => return Ok(QuestionKind::TrueFalse),
+            (Some('o') | Some('O'), _) => return Ok(QuestionKind::Ordering),
+            (Some('h') | Some('H'), _) => return Ok(QuestionKind::Matching),
+            (Some('e') | Some('E'), _) => return Ok(QuestionKind::Essay),
+            (Some('z') | Some('Z'), _) => return Ok(QuestionKind::Cloze),
+            (None, Some(default)) => return Ok(default),
+            _ => eprintln!("Invalid input: Expected 's', 'm', 'c', 't', 'o', 'h', 'e' or 'z'"),
+        }
+
+        eprintln!();
+    }
+}
+
+pub fn read_format(default: ContentFormat) -> Result<ContentFormat, Whatever> {
+    eprintln!("What's your question's format?");
+    eprintln!("([p]lain, [m]arkdown, [h]tml)");
+    print_default(Some(format_name(default)));
+
+    loop {
+        match read_line()?.chars().next() {
+            Some('p') | Some('P') => return Ok(ContentFormat::Plain),
+            Some('m') | Some('M') => return Ok(ContentFormat::Markdown),
+            Some('h') | Some('H') => return Ok(ContentFormat::Html),
+            None => return Ok(default),
+            _ => eprintln!("Invalid input: Expected 'p', 'm' or 'h'"),
+        }
+    }
+}
+
+pub fn read_content(default: Option<&str>) -> Result<String, Whatever> {
+    eprintln!("What's your question's content?");
+    print_default(default);
+    read_text(default)
+}
+
+pub fn read_option(index: usize, default: Option<&str>) -> Result<String, Whatever> {
+    eprintln!("What's your question's option #{index}");
+    print_default(default);
+    read_text(default)
+}
+
+pub fn read_single_selection_answer(default: Option<u32>) -> Result<u32, Whatever> {
+    eprintln!("What's your question's answer");
+    eprintln!("(Input one integer within [1, 4])");
+    print_default(default.map(|a| (a + 1).to_string()).as_deref());
+
+    loop {
+        let answer = read_line()?;
+
+        if answer.is_empty() {
+            if let Some(default) = default {
+                return Ok(default);
+            }
+        }
+
+        match parse_single_selection_answer(&answer) {
+            Some(answer) => return Ok(answer),
+            None => eprintln!("Invalid input"),
+        }
+
+        eprintln!();
+    }
+}
+
+pub fn read_multiple_selection_answer(default: Option<&[u32]>) -> Result<Vec<u32>, Whatever> {
+    eprintln!("What's your question's answer");
+    eprintln!("(Input integers within [1, 4] separated by space)");
+    let default_text = default.map(|a| {
+        a.iter()
+            .map(|x| (x + 1).to_string())
+            .collect::<Vec<_>>()
+            .join(" ")
+    });
+    print_default(default_text.as_deref());
+
+    let answer = loop {
+        let answer = read_line()?;
+
+        if answer.is_empty() {
+            if let Some(default) = default {
+                break default.to_vec();
+            }
+        }
+
+        match parse_multiple_selection_answer(&answer) {
+            Some(answer) => break answer,
+            None => eprintln!("Invalid input"),
+        }
+
+        eprintln!();
+    };
+
+    if !(2..=3).contains(&answer.len()) {
+        eprintln!("Warning: Expect the number of correct options to be 2 or 3");
+    }
+
+    Ok(answer)
+}
+
+pub fn read_true_false_answer(default: Option<bool>) -> Result<bool, Whatever> {
+    eprintln!("What's your question's answer");
+    eprintln!("([t]rue, [f]alse)");
+    print_default(default.map(|a| a.to_string()).as_deref());
+
+    loop {
+        let answer = read_line()?;
+
+        if answer.is_empty() {
+            if let Some(default) = default {
+                return Ok(default);
+            }
+        }
+
+        match parse_true_false_answer(&answer) {
+            Some(answer) => return Ok(answer),
+            None => eprintln!("Invalid input: Expected 't' or 'f'"),
+        }
+
+        eprintln!();
+    }
+}
+
+pub fn read_item_count(default: Option<usize>) -> Result<usize, Whatever> {
+    read_count("items", default)
+}
+
+pub fn read_target_count(default: Option<usize>) -> Result<usize, Whatever> {
+    read_count("targets", default)
+}
+
+fn read_count(noun: &str, default: Option<usize>) -> Result<usize, Whatever> {
+    eprintln!("How many {noun} does your question have?");
+    eprintln!("(Input one integer no less than 2)");
+    print_default(default.map(|c| c.to_string()).as_deref());
+
+    loop {
+        let count = read_line()?;
+
+        if count.is_empty() {
+            if let Some(default) = default {
+                return Ok(default);
+            }
+        }
+
+        match count.parse::<usize>() {
+            Ok(count) if count >= 2 => return Ok(count),
+            _ => eprintln!("Invalid input"),
+        }
+
+        eprintln!();
+    }
+}
+
+pub fn read_item(index: usize, default: Option<&str>) -> Result<String, Whatever> {
+    eprintln!("What's your question's item #{index}");
+    print_default(default);
+    read_text(default)
+}
+
+pub fn read_target(index: usize, default: Option<&str>) -> Result<String, Whatever> {
+    eprintln!("What's your question's target #{index}");
+    print_default(default);
+    read_text(default)
+}
+
+pub fn read_ordering_answer(count: usize, default: Option<&[u32]>) -> Result<Vec<u32>, Whatever> {
+    eprintln!("What's your question's answer");
+    eprintln!("(Input every integer within [1, {count}] once, in the correct order)");
+    let default_text = default.map(indices_text);
+    print_default(default_text.as_deref());
+
+    loop {
+        let answer = read_line()?;
+
+        if answer.is_empty() {
+            if let Some(default) = default {
+                return Ok(default.to_vec());
+            }
+        }
+
+        match parse_ordering_answer(&answer, count) {
+            Some(answer) => return Ok(answer),
+            None => eprintln!("Invalid input"),
+        }
+
+        eprintln!();
+    }
+}
+
+pub fn read_matching_answer(
+    items: usize,
+    targets: usize,
+    default: Option<&[u32]>,
+) -> Result<Vec<u32>, Whatever> {
+    eprintln!("What's your question's answer");
+    eprintln!("(Input {items} integers within [1, {targets}], the target of every item in order)");
+    let default_text = default.map(indices_text);
+    print_default(default_text.as_deref());
+
+    loop {
+        let answer = read_line()?;
+
+        if answer.is_empty() {
+            if let Some(default) = default {
+                return Ok(default.to_vec());
+            }
+        }
+
+        match parse_matching_answer(&answer, items, targets) {
+            Some(answer) => return Ok(answer),
+            None => eprintln!("Invalid input"),
+        }
+
+        eprintln!();
+    }
+}
+
+pub fn read_blank(index: usize, default: Option<&[String]>) -> Result<Vec<String>, Whatever> {
+    eprintln!("What are the accepted answers of blank #{index}?");
+    eprintln!("(Input alternatives separated by '|')");
+    let default_text = default.map(|d| d.join(" | "));
+    print_default(default_text.as_deref());
+
+    loop {
+        let answer = read_line()?;
+
+        if answer.is_empty() {
+            if let Some(default) = default {
+                return Ok(default.to_vec());
+            }
+        }
+
+        match parse_blank(&answer) {
+            Some(answer) => return Ok(answer),
+            None => eprintln!("Invalid input"),
+        }
+
+        eprintln!();
+    }
+}
+
+pub fn parse_blank(answer: &str) -> Option<Vec<String>> {
+    let answer: Vec<String> = answer
+        .split('|')
+        .map(str::trim)
+        .filter(|x| !x.is_empty())
+        .map(str::to_owned)
+        .collect();
+
+    (!answer.is_empty()).then_some(answer)
+}
+
+pub fn parse_true_false_answer(answer: &str) -> Option<bool> {
+    match answer.trim().to_lowercase().as_str() {
+        "t" | "true" => Some(true),
+        "f" | "false" => Some(false),
+        _ => None,
+    }
+}
+
+pub fn parse_ordering_answer(answer: &str, count: usize) -> Option<Vec<u32>> {
+    let answer = answer
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|x| !x.is_empty())
+        .map(|x| x.parse())
+        .collect::<Result<Vec<u32>, _>>()
+        .ok()?;
+
+    let mut sorted = answer.clone();
+    sorted.sort();
+    if sorted.len() != count || !sorted.iter().enumerate().all(|(i, x)| *x as usize == i + 1) {
+        return None;
+    }
+
+    Some(answer.into_iter().map(|x| x - 1).collect())
+}
+
+pub fn parse_matching_answer(answer: &str, items: usize, targets: usize) -> Option<Vec<u32>> {
+    let answer = answer
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|x| !x.is_empty())
+        .map(|x| x.parse())
+        .collect::<Result<Vec<u32>, _>>()
+        .ok()?;
+
+    if answer.len() != items || !answer.iter().all(|x| (1..=targets as u32).contains(x)) {
+        return None;
+    }
+
+    Some(answer.into_iter().map(|x| x - 1).collect())
+}
+
+pub fn parse_single_selection_answer(answer: &str) -> Option<u32> {
+    match answer.trim().parse::<u32>() {
+        Ok(answer) if (1..=4).contains(&answer) => Some(answer - 1),
+        _ => None,
+    }
+}
+
+pub fn parse_multiple_selection_answer(answer: &str) -> Option<Vec<u32>> {
+    let answer = answer
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|x| !x.is_empty())
+        .map(|x| x.parse())
+        .collect::<Result<Vec<u32>, _>>()
+        .ok()?;
+
+    if answer.is_empty() || !answer.iter().all(|x| (1..=4).contains(x)) {
+        return None;
+    }
+
+    Some(answer.into_iter().map(|x| x - 1).collect())
+}
+
+pub fn read_completion_answer(default: Option<&str>) -> Result<String, Whatever> {
+    eprintln!("What's your question's answer");
+    print_default(default);
+    read_text(default)
+}
+
+pub fn read_essay_answer(default: Option<&str>) -> Result<String, Whatever> {
+    eprintln!("What's your question's reference answer for graders?");
+    eprintln!("(May be left empty)");
+    print_default(default);
+    read_text(default)
+}
+
+pub fn read_explanation(default: Option<&str>) -> Result<Option<String>, Whatever> {
+    eprintln!("Why is the answer correct? (Shown to students after grading)");
+    eprintln!("(May be left empty)");
+    print_default(default);
+    read_text(default).map(|e| (!e.is_empty()).then_some(e))
+}
+
+// `None` leaves the weight of the question to the default of its kind.
+pub fn read_weight(default: Option<u32>) -> Result<Option<u32>, Whatever> {
+    eprintln!("How many points is your question worth in a test?");
+    eprintln!("(Input one positive integer, or 'default' for the default of its kind)");
+    print_default(default.map(|w| w.to_string()).as_deref());
+
+    loop {
+        let weight = read_line()?;
+
+        if weight.is_empty() {
+            return Ok(default);
+        }
+        if weight == "default" {
+            return Ok(None);
+        }
+
+        match weight.parse::<u32>() {
+            Ok(weight) if weight > 0 => return Ok(Some(weight)),
+            _ => eprintln!("Invalid input"),
+        }
+
+        eprintln!();
+    }
+}
+
+pub fn read_points(max: f32) -> Result<f32, Whatever> {
+    eprintln!("How many points does the response earn?");
+    eprintln!("(Input one number within [0, {max}])");
+
+    loop {
+        match read_line()?.parse::<f32>() {
+            Ok(points) if (0.0..=max).contains(&points) => return Ok(points),
+            _ => eprintln!("Invalid input"),
+        }
+
+        eprintln!();
+    }
+}
+
+pub fn read_comment(default: Option<&str>) -> Result<String, Whatever> {
+    eprintln!("Any comment on the response?");
+    eprintln!("(May be left empty)");
+    print_default(default);
+    read_text(default)
+}
+
+pub fn confirm(message: &str) -> Result<bool, Whatever> {
+    eprintln!("{message} [y/N]");
+    Ok(matches!(read_line()?.chars().next(), Some('y') | Some('Y')))
+}
+
+pub fn kind_name(kind: QuestionKind) -> &'static str {
+    match kind {
+        QuestionKind::SingleSelection => "Single-Selection",
+        QuestionKind::MultipleSelection => "Multiple-Selection",
+        QuestionKind::Completion => "Completion",
+        QuestionKind::TrueFalse => "True-False",
+        QuestionKind::Ordering => "Ordering",
+        QuestionKind::Matching => "Matching",
+        QuestionKind::Essay => "Essay",
+        QuestionKind::Cloze => "Cloze",
+    }
+}
+
+pub fn format_name(format: ContentFormat) -> &'static str {
+    match format {
+        ContentFormat::Plain => "Plain",
+        ContentFormat::Markdown => "Markdown",
+        ContentFormat::Html => "HTML",
+    }
+}
+
+fn print_default(default: Option<&str>) {
+    if let Some(default) = default {
+        eprintln!("(Press Enter to keep: {default})");
+    }
+}
+
+fn read_text(default: Option<&str>) -> Result<String, Whatever> {
+    let text = read_line()?;
+
+    match default {
+        Some(default) if text.is_empty() => Ok(default.to_owned()),
+        _ => Ok(text),
+    }
+}
+
+fn read_line() -> Result<String, Whatever> {
+    let mut line = String::with_capacity(256);
+    let read = io::stdin()
+        .read_line(&mut line)
+        .whatever_context("Could not read input")?;
+    ensure_whatever!(read > 0, "Unexpected end of input");
+    Ok(line.trim().to_owned())
+}
+
+pub fn answer_text(question: &Question) -> String {
+    match question {
+        Question::SingleSelection(q) => (q.answer().value() + 1).to_string(),
+        Question::MultipleSelection(q) => q
+            .answer()
+            .value()
+            .iter()
+            .map(|x| (x + 1).to_string())
+            .collect::<Vec<_>>()
+            .join(" "),
+        Question::Completion(q) => q.answer().value().to_owned(),
+        Question::TrueFalse(q) => q.answer().value().to_string(),
+        Question::Ordering(q) => indices_text(q.answer().value()),
+        Question::Matching(q) => indices_text(&q.answer().targets()),
+        Question::Essay(q) => q.answer().value().to_owned(),
+        Question::Cloze(q) => q
+            .answer()
+            .value()
+            .iter()
+            .map(|b| b.join(" | "))
+            .collect::<Vec<_>>()
+            .join("; "),
+    }
+}
+
+fn indices_text(answer: &[u32]) -> String {
+    answer
+        .iter()
+        .map(|x| (x + 1).to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn selection_answers_are_one_based() {
+        assert_eq!(parse_single_selection_answer(" 4 "), Some(3));
+        assert_eq!(parse_single_selection_answer("0"), None);
+        assert_eq!(parse_single_selection_answer("5"), None);
+        assert_eq!(parse_single_selection_answer("a"), None);
+
+        assert_eq!(
+            parse_multiple_selection_answer("1, 3 4"),
+            Some(vec![0, 2, 3])
+        );
+        assert_eq!(parse_multiple_selection_answer(""), None);
+        assert_eq!(parse_multiple_selection_answer("1 5"), None);
+    }
+
+    #[test]
+    fn true_false_answer_accepts_words_and_letters() {
+        assert_eq!(parse_true_false_answer(" True"), Some(true));
+        assert_eq!(parse_true_false_answer("f"), Some(false));
+        assert_eq!(parse_true_false_answer("yes"), None);
+    }
+
+    #[test]
+    fn ordering_answer_is_a_permutation() {
+        assert_eq!(parse_ordering_answer("3 1 2", 3), Some(vec![2, 0, 1]));
+        assert_eq!(parse_ordering_answer("3,1,2", 3), Some(vec![2, 0, 1]));
+        assert_eq!(parse_ordering_answer("1 1 2", 3), None);
+        assert_eq!(parse_ordering_answer("1 2", 3), None);
+        assert_eq!(parse_ordering_answer("0 1 2", 3), None);
+    }
+
+    #[test]
+    fn matching_answer_has_a_target_per_item() {
+        assert_eq!(parse_matching_answer("2 2 1", 3, 2), Some(vec![1, 1, 0]));
+        assert_eq!(parse_matching_answer("2 1", 3, 2), None);
+        assert_eq!(parse_matching_answer("1 2 3", 3, 2), None);
+    }
+
+    #[test]
+    fn blank_answer_splits_alternatives() {
+        assert_eq!(
+            parse_blank(" ls | dir |"),
+            Some(vec!["ls".to_owned(), "dir".to_owned()])
+        );
+        assert_eq!(parse_blank(" | "), None);
+    }
+}
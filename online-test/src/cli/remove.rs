@@ -1,27 +1,69 @@
 use std::io;
 use std::sync::Arc;
 
+use clap::Args;
 use snafu::{prelude::*, Whatever};
 
+use crate::cli::prompt;
 use crate::domain::repository::question::QuestionRepository;
 
-pub async fn run_remove(repo: Arc<dyn QuestionRepository>) -> Result<(), Whatever> {
-    let id: usize = loop {
-        println!("What's the question's ID");
-        let mut id = String::with_capacity(8);
-        io::stdin()
-            .read_line(&mut id)
-            .whatever_context("Could not read input")?;
-        match id.trim().parse() {
-            Ok(id) => break id,
-            _ => println!("Invalid input"),
-        }
-        println!("");
+#[derive(Debug, Args)]
+pub struct RemoveArgs {
+    /// IDs of the questions to retire, prompted for if omitted
+    ids: Vec<usize>,
+    /// Retire without asking for confirmation
+    #[clap(long, short)]
+    yes: bool,
+    /// Print the result as JSON
+    #[clap(long)]
+    json: bool,
+}
+
+pub async fn run_remove(
+    repo: Arc<dyn QuestionRepository>,
+    args: RemoveArgs,
+) -> Result<(), Whatever> {
+    let ids = if args.ids.is_empty() {
+        vec![read_id()?]
+    } else {
+        args.ids
     };
 
-    repo.remove_question(id.into())
+    if !args.yes {
+        let list = ids
+            .iter()
+            .map(usize::to_string)
+            .collect::<Vec<_>>()
+            .join(", ");
+        if !prompt::confirm(&format!("Retire question(s) {list}?"))? {
+            eprintln!("Aborted");
+            return Ok(());
+        }
+    }
+
+    repo.remove_questions(ids.iter().map(|&id| id.into()).collect())
         .await
         .whatever_context("Could not remove question")?;
 
+    if args.json {
+        println!("{}", serde_json::json!({ "retired": ids }));
+    }
+
     Ok(())
 }
+
+// Prompts go to stderr, so that only the result is printed with `--json`.
+fn read_id() -> Result<usize, Whatever> {
+    loop {
+        eprintln!("What's the question's ID");
+        let mut id = String::with_capacity(8);
+        io::stdin()
+            .read_line(&mut id)
+            .whatever_context("Could not read input")?;
+        match id.trim().parse() {
+            Ok(id) => return Ok(id),
+            _ => eprintln!("Invalid input"),
+        }
+        eprintln!();
+    }
+}
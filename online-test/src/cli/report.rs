@@ -0,0 +1,89 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use clap::Args;
+use comfy_table::Table;
+use snafu::{prelude::*, Whatever};
+
+use crate::domain::repository::score::ScoreRepository;
+
+#[derive(Debug, Args)]
+pub struct ReportArgs {
+    /// Break pass rates down by user instead of by grade
+    #[clap(long)]
+    by_user: bool,
+}
+
+#[derive(Debug, Default)]
+struct Tally {
+    attempts: usize,
+    passed: usize,
+}
+
+impl Tally {
+    fn rate(&self) -> String {
+        if self.attempts == 0 {
+            return "-".to_owned();
+        }
+
+        format!("{:.1}%", 100.0 * self.passed as f64 / self.attempts as f64)
+    }
+}
+
+pub async fn run_report(repo: Arc<dyn ScoreRepository>, args: ReportArgs) -> Result<(), Whatever> {
+    let grades = repo
+        .list_grades()
+        .await
+        .whatever_context("Could not load grades")?;
+
+    let mut total = Tally::default();
+    let mut groups: BTreeMap<String, Tally> = BTreeMap::new();
+    for record in &grades {
+        let key = if args.by_user {
+            record.user.to_string()
+        } else {
+            record.grade.letter().to_owned()
+        };
+
+        for tally in [&mut total, groups.entry(key).or_default()] {
+            tally.attempts += 1;
+            tally.passed += usize::from(record.grade.passed());
+        }
+    }
+
+    let mut table = Table::new();
+    if args.by_user {
+        table.set_header(vec!["User", "Attempts", "Passed", "Pass Rate"]);
+        for (user, tally) in &groups {
+            table.add_row(vec![
+                user.clone(),
+                tally.attempts.to_string(),
+                tally.passed.to_string(),
+                tally.rate(),
+            ]);
+        }
+    } else {
+        table.set_header(vec!["Grade", "Attempts", "Share", "Passing"]);
+        for (letter, tally) in &groups {
+            table.add_row(vec![
+                letter.clone(),
+                tally.attempts.to_string(),
+                format!(
+                    "{:.1}%",
+                    100.0 * tally.attempts as f64 / total.attempts as f64
+                ),
+                if tally.passed > 0 { "yes" } else { "no" }.to_owned(),
+            ]);
+        }
+    }
+
+    println!("{table}");
+    println!();
+    println!(
+        "{} of {} graded attempts passed ({})",
+        total.passed,
+        total.attempts,
+        total.rate()
+    );
+    Ok(())
+}
@@ -0,0 +1,159 @@
+use std::sync::Arc;
+
+use comfy_table::Table;
+use snafu::{prelude::*, Whatever};
+
+use crate::cli::prompt;
+use crate::domain::entity::id::Id;
+use crate::domain::entity::question::{AbstractQuestion, Question};
+use crate::domain::repository::question::{QuestionRepository, QuestionRevision};
+
+pub async fn run_revisions_list(
+    repo: Arc<dyn QuestionRepository>,
+    id: usize,
+) -> Result<(), Whatever> {
+    let mut table = Table::new();
+    table.set_header(vec!["Revision", "Author", "Created At", "Content"]);
+
+    repo.list_revisions(id.into())
+        .await
+        .whatever_context("Could not list question revisions")?
+        .into_iter()
+        .for_each(|r| {
+            table.add_row(vec![
+                r.question.revision().to_string(),
+                r.author,
+                r.created_at.to_rfc3339(),
+                r.question.content().source().to_owned(),
+            ]);
+        });
+
+    println!("{table}");
+    Ok(())
+}
+
+pub async fn run_revisions_diff(
+    repo: Arc<dyn QuestionRepository>,
+    id: usize,
+    from: Option<usize>,
+    to: Option<usize>,
+) -> Result<(), Whatever> {
+    let revisions = repo
+        .list_revisions(id.into())
+        .await
+        .whatever_context("Could not list question revisions")?;
+
+    let to = match to {
+        Some(to) => find(&revisions, to.into())?,
+        None => revisions
+            .last()
+            .whatever_context("Question has no revisions")?,
+    };
+    let from = match from {
+        Some(from) => find(&revisions, from.into())?,
+        None => {
+            let index = revisions.len().saturating_sub(2);
+            &revisions[index]
+        }
+    };
+
+    println!(
+        "--- revision {} ({}, {})",
+        from.question.revision(),
+        from.author,
+        from.created_at.to_rfc3339()
+    );
+    println!(
+        "+++ revision {} ({}, {})",
+        to.question.revision(),
+        to.author,
+        to.created_at.to_rfc3339()
+    );
+
+    let choices = from
+        .question
+        .choices()
+        .len()
+        .max(to.question.choices().len());
+    let targets = from
+        .question
+        .targets()
+        .len()
+        .max(to.question.targets().len());
+    let from = fields(&from.question, choices, targets);
+    let to = fields(&to.question, choices, targets);
+    let mut changed = false;
+
+    for ((name, old), (_, new)) in from.iter().zip(to.iter()) {
+        if old != new {
+            changed = true;
+            println!();
+            println!("{name}:");
+            println!("- {old}");
+            println!("+ {new}");
+        }
+    }
+
+    if !changed {
+        println!();
+        println!("No changes");
+    }
+
+    Ok(())
+}
+
+fn find(revisions: &[QuestionRevision], revision: Id) -> Result<&QuestionRevision, Whatever> {
+    revisions
+        .iter()
+        .find(|r| r.question.revision() == revision)
+        .whatever_context(format!("Could not find revision {revision}"))
+}
+
+fn fields(question: &Question, choices: usize, targets: usize) -> Vec<(String, String)> {
+    let label = match question {
+        Question::Ordering(_) | Question::Matching(_) => "item",
+        _ => "option",
+    };
+
+    let mut fields = vec![
+        (
+            "kind".to_owned(),
+            prompt::kind_name(question.kind()).to_owned(),
+        ),
+        (
+            "format".to_owned(),
+            prompt::format_name(question.content().format()).to_owned(),
+        ),
+        ("content".to_owned(), question.content().source().to_owned()),
+    ];
+    for i in 0..choices {
+        let choice = question
+            .choices()
+            .get(i)
+            .map(|c| c.source())
+            .unwrap_or_default();
+        fields.push((format!("{label} #{}", i + 1), choice.to_owned()));
+    }
+    for i in 0..targets {
+        let target = question
+            .targets()
+            .get(i)
+            .map(|t| t.source())
+            .unwrap_or_default();
+        fields.push((format!("target #{}", i + 1), target.to_owned()));
+    }
+    fields.push(("answer".to_owned(), prompt::answer_text(question)));
+    fields.push((
+        "explanation".to_owned(),
+        question
+            .explanation()
+            .map(|e| e.source().to_owned())
+            .unwrap_or_default(),
+    ));
+    fields.push((
+        "points".to_owned(),
+        question.points().map(|p| p.to_string()).unwrap_or_default(),
+    ));
+
+    fields
+}
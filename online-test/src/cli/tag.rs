@@ -0,0 +1,22 @@
+use std::sync::Arc;
+
+use clap::Args;
+use snafu::{prelude::*, Whatever};
+
+use crate::domain::repository::question::QuestionRepository;
+
+#[derive(Debug, Args)]
+pub struct TagArgs {
+    /// ID of the question to tag
+    id: usize,
+    /// Tags replacing the current ones, none to clear them
+    tags: Vec<String>,
+}
+
+pub async fn run_tag(repo: Arc<dyn QuestionRepository>, args: TagArgs) -> Result<(), Whatever> {
+    repo.set_tags(args.id.into(), args.tags)
+        .await
+        .whatever_context("Could not tag question")?;
+
+    Ok(())
+}
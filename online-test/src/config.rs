@@ -0,0 +1,890 @@
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize};
+use snafu::prelude::*;
+
+use crate::domain::entity::grade::{GradeBand, GradeScale};
+use crate::domain::entity::test::{Penalty, Weights};
+use crate::domain::repository::question::SelectCount;
+use crate::domain::session::config::{SessionConfig, Timeouts};
+
+// Read when no path is given explicitly, but only if it exists.
+pub const DEFAULT_CONFIG_PATH: &str = "online-test.toml";
+
+// Settings of the whole application. Values are layered in this order, later ones winning:
+// built-in defaults, the TOML file, environment variables and command line flags.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub database: DatabaseConfig,
+    pub media: MediaConfig,
+    pub server: ServerConfig,
+    pub session: SessionTimeoutsConfig,
+    pub test: TestConfig,
+    pub practice: PracticeConfig,
+    pub adaptive: AdaptiveConfig,
+    pub shutdown: ShutdownConfig,
+}
+
+impl Config {
+    // Loads `path`, or `DEFAULT_CONFIG_PATH` falling back to the defaults when it is absent.
+    pub fn load(path: Option<&Path>) -> Result<Self, ConfigError> {
+        let path = match path {
+            Some(path) => path,
+            None if Path::new(DEFAULT_CONFIG_PATH).exists() => Path::new(DEFAULT_CONFIG_PATH),
+            None => return Ok(Self::default()),
+        };
+
+        let text = std::fs::read_to_string(path).context(ReadSnafu { path })?;
+        toml::from_str(&text).context(ParseSnafu { path })
+    }
+
+    // Overrides values with the environment variables that are set.
+    pub fn with_env<F>(mut self, var: F) -> Result<Self, ConfigError>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        if let Some(url) = var("DATABASE_URL") {
+            self.database.url = url;
+        }
+        if let Some(dir) = var("MEDIA_DIR") {
+            self.media.dir = dir.into();
+        }
+        if let Some(ip) = var("LISTENING_IP") {
+            self.server.ip = parse_env("LISTENING_IP", ip)?;
+        }
+        if let Some(port) = var("LISTENING_PORT") {
+            self.server.port = parse_env("LISTENING_PORT", port)?;
+        }
+        if let Some(dir) = var("STATIC_DIR") {
+            self.server.static_dir = dir.into();
+        }
+        if let Some(token) = var("ADMIN_TOKEN") {
+            self.server.admin_token = Some(token);
+        }
+        Ok(self)
+    }
+
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        ensure!(
+            !self.database.url.trim().is_empty(),
+            InvalidSnafu {
+                key: "database.url",
+                message: "should not be empty",
+            }
+        );
+        ensure!(
+            self.server.port != 0,
+            InvalidSnafu {
+                key: "server.port",
+                message: "should not be 0",
+            }
+        );
+
+        for (key, timeouts) in [
+            ("session.login", &self.session.login),
+            ("session.test", &self.session.test),
+            ("session.practice", &self.session.practice),
+            ("session.adaptive", &self.session.adaptive),
+        ] {
+            ensure!(
+                timeouts.expire_secs > 0,
+                InvalidSnafu {
+                    key: format!("{key}.expire_secs"),
+                    message: "should be positive",
+                }
+            );
+        }
+
+        // A test is cancelled together with the login it was started from.
+        ensure!(
+            self.session.test.expire_secs <= self.session.login.expire_secs,
+            InvalidSnafu {
+                key: "session.test.expire_secs",
+                message: "should not exceed session.login.expire_secs",
+            }
+        );
+        ensure!(
+            self.shutdown.timeout_secs > 0,
+            InvalidSnafu {
+                key: "shutdown.timeout_secs",
+                message: "should be positive",
+            }
+        );
+        for (kind, points) in self.test.points.points() {
+            ensure!(
+                points > 0,
+                InvalidSnafu {
+                    key: format!("test.points.{kind}"),
+                    message: "should be positive",
+                }
+            );
+        }
+        for (kind, rate) in self.test.penalty.rates() {
+            ensure!(
+                (0.0..=1.0).contains(&rate),
+                InvalidSnafu {
+                    key: format!("test.penalty.{kind}"),
+                    message: "should be between 0 and 1",
+                }
+            );
+        }
+
+        let grades = &self.test.grades;
+        ensure!(
+            (0.0..=100.0).contains(&grades.pass),
+            InvalidSnafu {
+                key: "test.grades.pass",
+                message: "should be between 0 and 100",
+            }
+        );
+        ensure!(
+            !grades.bands.is_empty(),
+            InvalidSnafu {
+                key: "test.grades.bands",
+                message: "should not be empty",
+            }
+        );
+        for (i, band) in grades.bands.iter().enumerate() {
+            ensure!(
+                !band.letter.trim().is_empty(),
+                InvalidSnafu {
+                    key: format!("test.grades.bands[{i}].letter"),
+                    message: "should not be empty",
+                }
+            );
+            ensure!(
+                (0.0..=100.0).contains(&band.min),
+                InvalidSnafu {
+                    key: format!("test.grades.bands[{i}].min"),
+                    message: "should be between 0 and 100",
+                }
+            );
+            // Scores are graded by the first band they reach.
+            ensure!(
+                i == 0 || band.min < grades.bands[i - 1].min,
+                InvalidSnafu {
+                    key: format!("test.grades.bands[{i}].min"),
+                    message: "should be below the minimum of the band before",
+                }
+            );
+        }
+
+        for (key, questions) in [
+            ("test.questions", &self.test.questions),
+            ("practice.questions", &self.practice.questions),
+            ("adaptive.questions", &self.adaptive.questions),
+        ] {
+            ensure!(
+                questions.total() > 0,
+                InvalidSnafu {
+                    key,
+                    message: "should select at least one question",
+                }
+            );
+        }
+        Ok(())
+    }
+
+    pub fn listening_addr(&self) -> SocketAddr {
+        SocketAddr::new(self.server.ip, self.server.port)
+    }
+
+    pub fn session_config(&self) -> SessionConfig {
+        SessionConfig {
+            login: self.session.login.into(),
+            test: self.session.test.into(),
+            practice: self.session.practice.into(),
+            adaptive: self.session.adaptive.into(),
+            test_questions: self.test.questions.into(),
+            test_points: self.test.points.into(),
+            test_penalty: self.test.penalty.into(),
+            grade_scale: self.test.grades.clone().into(),
+            practice_questions: self.practice.questions.into(),
+            adaptive_questions: self.adaptive.questions.into(),
+        }
+    }
+}
+
+fn parse_env<T: FromStr>(name: &str, value: String) -> Result<T, ConfigError> {
+    value.parse().ok().context(EnvSnafu { name, value })
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DatabaseConfig {
+    pub url: String,
+}
+
+impl Default for DatabaseConfig {
+    fn default() -> Self {
+        Self {
+            url: "production/data.db".to_owned(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MediaConfig {
+    pub dir: PathBuf,
+}
+
+impl Default for MediaConfig {
+    fn default() -> Self {
+        Self {
+            dir: "production/media".into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServerConfig {
+    pub ip: IpAddr,
+    pub port: u16,
+    pub static_dir: PathBuf,
+    // Admin API is only served when a token is given.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub admin_token: Option<String>,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            ip: IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+            port: 8080,
+            static_dir: "static".into(),
+            admin_token: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SessionTimeoutsConfig {
+    pub login: TimeoutsConfig,
+    pub test: TimeoutsConfig,
+    pub practice: TimeoutsConfig,
+    pub adaptive: TimeoutsConfig,
+}
+
+impl Default for SessionTimeoutsConfig {
+    fn default() -> Self {
+        let config = SessionConfig::default();
+        Self {
+            login: config.login.into(),
+            test: config.test.into(),
+            practice: config.practice.into(),
+            adaptive: config.adaptive.into(),
+        }
+    }
+}
+
+// Timeouts of one kind of session. `expire_secs` has no sensible default shared by every
+// kind, so it has to be given whenever the table is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TimeoutsConfig {
+    pub expire_secs: u64,
+    #[serde(default = "default_cancel_await_secs")]
+    pub cancel_await_secs: u64,
+}
+
+fn default_cancel_await_secs() -> u64 {
+    5
+}
+
+impl From<Timeouts> for TimeoutsConfig {
+    fn from(value: Timeouts) -> Self {
+        Self {
+            expire_secs: value.expire.as_secs(),
+            cancel_await_secs: value.cancel_await.as_secs(),
+        }
+    }
+}
+
+impl From<TimeoutsConfig> for Timeouts {
+    fn from(value: TimeoutsConfig) -> Self {
+        Timeouts::from_secs(value.expire_secs, value.cancel_await_secs)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TestConfig {
+    pub questions: QuestionCountConfig,
+    pub points: PointsConfig,
+    pub penalty: PenaltyConfig,
+    pub grades: GradeScaleConfig,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PracticeConfig {
+    #[serde(deserialize_with = "deserialize_practice_questions")]
+    pub questions: QuestionCountConfig,
+}
+
+impl Default for PracticeConfig {
+    fn default() -> Self {
+        Self {
+            questions: SessionConfig::default().practice_questions.into(),
+        }
+    }
+}
+
+fn deserialize_practice_questions<'de, D>(deserializer: D) -> Result<QuestionCountConfig, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserialize_questions(deserializer, PracticeConfig::default().questions)
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AdaptiveConfig {
+    #[serde(deserialize_with = "deserialize_adaptive_questions")]
+    pub questions: QuestionCountConfig,
+}
+
+impl Default for AdaptiveConfig {
+    fn default() -> Self {
+        Self {
+            questions: SessionConfig::default().adaptive_questions.into(),
+        }
+    }
+}
+
+fn deserialize_adaptive_questions<'de, D>(deserializer: D) -> Result<QuestionCountConfig, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserialize_questions(deserializer, AdaptiveConfig::default().questions)
+}
+
+// Kinds left out of a `questions` table outside of `[test]` keep the defaults of their own
+// table instead of those of a test.
+fn deserialize_questions<'de, D>(
+    deserializer: D,
+    defaults: QuestionCountConfig,
+) -> Result<QuestionCountConfig, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let mut table = toml::Table::try_from(defaults).map_err(D::Error::custom)?;
+    table.extend(toml::Table::deserialize(deserializer)?);
+    table.try_into().map_err(D::Error::custom)
+}
+
+// Questions of each kind in a generated test. Kinds left out keep their default count, which is
+// zero for every kind the sample questions lack, as tests cannot start without enough of them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct QuestionCountConfig {
+    pub single_selection: usize,
+    pub multiple_selection: usize,
+    pub completion: usize,
+    pub true_false: usize,
+    pub ordering: usize,
+    pub matching: usize,
+    pub essay: usize,
+    pub cloze: usize,
+}
+
+impl QuestionCountConfig {
+    pub fn total(&self) -> usize {
+        self.single_selection
+            + self.multiple_selection
+            + self.completion
+            + self.true_false
+            + self.ordering
+            + self.matching
+            + self.essay
+            + self.cloze
+    }
+}
+
+impl Default for QuestionCountConfig {
+    fn default() -> Self {
+        SessionConfig::default().test_questions.into()
+    }
+}
+
+impl From<SelectCount> for QuestionCountConfig {
+    fn from(value: SelectCount) -> Self {
+        Self {
+            single_selection: value.single_selection,
+            multiple_selection: value.multiple_selection,
+            completion: value.completion,
+            true_false: value.true_false,
+            ordering: value.ordering,
+            matching: value.matching,
+            essay: value.essay,
+            cloze: value.cloze,
+        }
+    }
+}
+
+impl From<QuestionCountConfig> for SelectCount {
+    fn from(value: QuestionCountConfig) -> Self {
+        Self {
+            single_selection: value.single_selection,
+            multiple_selection: value.multiple_selection,
+            completion: value.completion,
+            true_false: value.true_false,
+            ordering: value.ordering,
+            matching: value.matching,
+            essay: value.essay,
+            cloze: value.cloze,
+        }
+    }
+}
+
+// Points a test question is worth by kind, unless the question has a weight of its own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PointsConfig {
+    pub single_selection: u32,
+    pub multiple_selection: u32,
+    pub completion: u32,
+    pub true_false: u32,
+    pub ordering: u32,
+    pub matching: u32,
+    pub essay: u32,
+    pub cloze: u32,
+}
+
+impl PointsConfig {
+    fn points(&self) -> [(&'static str, u32); 8] {
+        [
+            ("single_selection", self.single_selection),
+            ("multiple_selection", self.multiple_selection),
+            ("completion", self.completion),
+            ("true_false", self.true_false),
+            ("ordering", self.ordering),
+            ("matching", self.matching),
+            ("essay", self.essay),
+            ("cloze", self.cloze),
+        ]
+    }
+}
+
+impl Default for PointsConfig {
+    fn default() -> Self {
+        SessionConfig::default().test_points.into()
+    }
+}
+
+impl From<Weights> for PointsConfig {
+    fn from(value: Weights) -> Self {
+        Self {
+            single_selection: value.single_selection,
+            multiple_selection: value.multiple_selection,
+            completion: value.completion,
+            true_false: value.true_false,
+            ordering: value.ordering,
+            matching: value.matching,
+            essay: value.essay,
+            cloze: value.cloze,
+        }
+    }
+}
+
+impl From<PointsConfig> for Weights {
+    fn from(value: PointsConfig) -> Self {
+        Self {
+            single_selection: value.single_selection,
+            multiple_selection: value.multiple_selection,
+            completion: value.completion,
+            true_false: value.true_false,
+            ordering: value.ordering,
+            matching: value.matching,
+            essay: value.essay,
+            cloze: value.cloze,
+        }
+    }
+}
+
+// Share of a question's points deducted for a wrong answer in a test, by kind. Unanswered
+// questions lose nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PenaltyConfig {
+    pub single_selection: f32,
+    pub multiple_selection: f32,
+    pub completion: f32,
+    pub true_false: f32,
+    pub ordering: f32,
+    pub matching: f32,
+    pub essay: f32,
+    pub cloze: f32,
+}
+
+impl PenaltyConfig {
+    fn rates(&self) -> [(&'static str, f32); 8] {
+        [
+            ("single_selection", self.single_selection),
+            ("multiple_selection", self.multiple_selection),
+            ("completion", self.completion),
+            ("true_false", self.true_false),
+            ("ordering", self.ordering),
+            ("matching", self.matching),
+            ("essay", self.essay),
+            ("cloze", self.cloze),
+        ]
+    }
+}
+
+impl Default for PenaltyConfig {
+    fn default() -> Self {
+        SessionConfig::default().test_penalty.into()
+    }
+}
+
+impl From<Penalty> for PenaltyConfig {
+    fn from(value: Penalty) -> Self {
+        Self {
+            single_selection: value.single_selection,
+            multiple_selection: value.multiple_selection,
+            completion: value.completion,
+            true_false: value.true_false,
+            ordering: value.ordering,
+            matching: value.matching,
+            essay: value.essay,
+            cloze: value.cloze,
+        }
+    }
+}
+
+impl From<PenaltyConfig> for Penalty {
+    fn from(value: PenaltyConfig) -> Self {
+        Self {
+            single_selection: value.single_selection,
+            multiple_selection: value.multiple_selection,
+            completion: value.completion,
+            true_false: value.true_false,
+            ordering: value.ordering,
+            matching: value.matching,
+            essay: value.essay,
+            cloze: value.cloze,
+        }
+    }
+}
+
+// Letter grades of a test score, from the highest minimum score down, and the score needed to
+// pass. Bands replace the defaults as a whole when given.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GradeScaleConfig {
+    pub pass: f32,
+    pub bands: Vec<GradeBandConfig>,
+}
+
+impl Default for GradeScaleConfig {
+    fn default() -> Self {
+        SessionConfig::default().grade_scale.into()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GradeBandConfig {
+    pub letter: String,
+    pub min: f32,
+}
+
+impl From<GradeScale> for GradeScaleConfig {
+    fn from(value: GradeScale) -> Self {
+        Self {
+            pass: value.pass,
+            bands: value
+                .bands
+                .into_iter()
+                .map(|b| GradeBandConfig {
+                    letter: b.letter,
+                    min: b.min,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<GradeScaleConfig> for GradeScale {
+    fn from(value: GradeScaleConfig) -> Self {
+        Self {
+            bands: value
+                .bands
+                .into_iter()
+                .map(|b| GradeBand::new(b.letter, b.min))
+                .collect(),
+            pass: value.pass,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ShutdownConfig {
+    // Time given to requests in flight and then to sessions to finish.
+    pub timeout_secs: u64,
+    // Whether tests still open are graded from their last draft.
+    pub auto_grade: bool,
+}
+
+impl ShutdownConfig {
+    pub fn timeout(&self) -> Duration {
+        Duration::from_secs(self.timeout_secs)
+    }
+}
+
+impl Default for ShutdownConfig {
+    fn default() -> Self {
+        Self {
+            timeout_secs: 30,
+            auto_grade: true,
+        }
+    }
+}
+
+#[derive(Debug, Snafu)]
+#[non_exhaustive]
+pub enum ConfigError {
+    #[snafu(display("Could not read config file {}", path.display()))]
+    Read {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[snafu(display("Could not parse config file {}", path.display()))]
+    Parse {
+        path: PathBuf,
+        source: toml::de::Error,
+    },
+    #[snafu(display("Environment variable {name} has invalid value {value:?}"))]
+    Env { name: String, value: String },
+    #[snafu(display("Invalid `{key}`: {message}"))]
+    Invalid { key: String, message: String },
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    #[test]
+    fn config_default() {
+        let config = Config::default();
+        config.validate().unwrap();
+        assert_eq!(config.session_config(), SessionConfig::default());
+        assert_eq!(config.listening_addr().to_string(), "0.0.0.0:8080");
+    }
+
+    #[test]
+    fn config_parse_partial() {
+        let config: Config = toml::from_str(
+            r#"
+            [server]
+            port = 3000
+
+            [session.test]
+            expire_secs = 600
+
+            [test.questions]
+            completion = 4
+            essay = 1
+
+            [test.points]
+            essay = 3
+
+            [test.penalty]
+            single_selection = 0.5
+            "#,
+        )
+        .unwrap();
+        config.validate().unwrap();
+
+        assert_eq!(config.server.port, 3000);
+        assert_eq!(config.server.static_dir, PathBuf::from("static"));
+        assert_eq!(config.database, DatabaseConfig::default());
+
+        let session = config.session_config();
+        assert_eq!(session.test, Timeouts::from_secs(600, 5));
+        assert_eq!(session.login, SessionConfig::default().login);
+        assert_eq!(session.test_questions.single_selection, 8);
+        assert_eq!(session.test_questions.completion, 4);
+        assert_eq!(session.test_questions.essay, 1);
+        assert_eq!(session.test_points.essay, 3);
+        assert_eq!(session.test_points.completion, 1);
+        assert_eq!(session.test_penalty.single_selection, 0.5);
+        assert_eq!(session.test_penalty.true_false, 0.0);
+    }
+
+    #[test]
+    fn config_parse_partial_questions() {
+        let config: Config = toml::from_str(
+            r#"
+            [practice.questions]
+            true_false = 2
+
+            [adaptive.questions]
+            completion = 6
+            "#,
+        )
+        .unwrap();
+        config.validate().unwrap();
+
+        let session = config.session_config();
+        let defaults = SessionConfig::default();
+        assert_eq!(session.practice_questions.single_selection, 4);
+        assert_eq!(session.practice_questions.true_false, 2);
+        assert_eq!(session.adaptive_questions.completion, 6);
+        assert_eq!(
+            session.adaptive_questions.single_selection,
+            defaults.adaptive_questions.single_selection
+        );
+        assert_eq!(session.test_questions, defaults.test_questions);
+
+        let res = toml::from_str::<Config>("[practice.questions]\nsingle = 1\n");
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn config_parse_grades() {
+        let config: Config = toml::from_str(
+            r#"
+            [test.grades]
+            pass = 50.0
+            bands = [
+                { letter = "Distinction", min = 85.0 },
+                { letter = "Pass", min = 50.0 },
+                { letter = "Fail", min = 0.0 },
+            ]
+            "#,
+        )
+        .unwrap();
+        config.validate().unwrap();
+
+        let scale = config.session_config().grade_scale;
+        assert_eq!(scale.pass, 50.0);
+        assert_eq!(scale.bands[0], GradeBand::new("Distinction", 85.0));
+        assert_eq!(scale.bands.len(), 3);
+    }
+
+    #[test]
+    fn config_parse_unknown_field() {
+        let res = toml::from_str::<Config>("[server]\nlistening_port = 3000\n");
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn config_with_env() {
+        let vars = HashMap::from([
+            ("DATABASE_URL", "/tmp/data.db"),
+            ("LISTENING_PORT", "9000"),
+            ("ADMIN_TOKEN", "secret"),
+        ]);
+        let config = Config::default()
+            .with_env(|name| vars.get(name).map(|v| v.to_string()))
+            .unwrap();
+
+        assert_eq!(config.database.url, "/tmp/data.db");
+        assert_eq!(config.server.port, 9000);
+        assert_eq!(config.server.admin_token.as_deref(), Some("secret"));
+        assert_eq!(config.server.ip, ServerConfig::default().ip);
+    }
+
+    #[test]
+    fn config_with_env_invalid() {
+        let res = Config::default()
+            .with_env(|name| (name == "LISTENING_IP").then(|| "localhost".to_owned()));
+        assert!(matches!(res, Err(ConfigError::Env { name, .. }) if name == "LISTENING_IP"));
+    }
+
+    #[test]
+    fn config_validate_invalid() {
+        let mut config = Config::default();
+        config.test.questions = QuestionCountConfig {
+            single_selection: 0,
+            multiple_selection: 0,
+            completion: 0,
+            ..QuestionCountConfig::default()
+        };
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::Invalid { key, .. }) if key == "test.questions"
+        ));
+
+        let mut config = Config::default();
+        config.adaptive.questions = QuestionCountConfig {
+            single_selection: 0,
+            multiple_selection: 0,
+            completion: 0,
+            ..QuestionCountConfig::default()
+        };
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::Invalid { key, .. }) if key == "adaptive.questions"
+        ));
+
+        let mut config = Config::default();
+        config.test.points.matching = 0;
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::Invalid { key, .. }) if key == "test.points.matching"
+        ));
+
+        let mut config = Config::default();
+        config.test.penalty.true_false = 1.5;
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::Invalid { key, .. }) if key == "test.penalty.true_false"
+        ));
+
+        let mut config = Config::default();
+        config.test.penalty.cloze = -0.25;
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::Invalid { key, .. }) if key == "test.penalty.cloze"
+        ));
+
+        let mut config = Config::default();
+        config.test.grades.pass = 101.0;
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::Invalid { key, .. }) if key == "test.grades.pass"
+        ));
+
+        let mut config = Config::default();
+        config.test.grades.bands.swap(0, 1);
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::Invalid { key, .. }) if key == "test.grades.bands[1].min"
+        ));
+
+        let mut config = Config::default();
+        config.test.grades.bands.clear();
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::Invalid { key, .. }) if key == "test.grades.bands"
+        ));
+
+        let mut config = Config::default();
+        config.session.test.expire_secs = config.session.login.expire_secs + 1;
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::Invalid { key, .. }) if key == "session.test.expire_secs"
+        ));
+
+        let mut config = Config::default();
+        config.session.practice.expire_secs = 0;
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::Invalid { key, .. }) if key == "session.practice.expire_secs"
+        ));
+    }
+}
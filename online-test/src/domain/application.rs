@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
 use snafu::prelude::*;
@@ -7,50 +8,85 @@ use tokio::sync::mpsc::{self, Receiver, Sender};
 use tokio::sync::oneshot;
 use tokio::sync::{Mutex, RwLock};
 
+use crate::domain::entity::answer::{Answer, SubmissionSource};
+use crate::domain::entity::certificate::Certificate;
 use crate::domain::entity::id::{Id, SequentialIdAllocator};
+use crate::domain::entity::question::Question;
+use crate::domain::entity::score::Score;
 use crate::domain::entity::test::{Submission, Test};
 use crate::domain::entity::user::User;
+use crate::domain::repository::media::{Media, MediaRepository, MediaRepositoryError};
+use crate::domain::repository::practice::PracticeRepository;
 use crate::domain::repository::question::QuestionRepository;
+use crate::domain::repository::review::ReviewRepository;
 use crate::domain::repository::score::{Record, ScoreRepository};
+use crate::domain::service::grading::{GradingError, GradingService, PendingReview};
+use crate::domain::service::review::ReviewService;
+use crate::domain::session::adaptive::{AdaptiveProgress, AdaptiveSummary};
 use crate::domain::session::base::{Command, Report, Session, SessionBase};
+use crate::domain::session::config::SessionConfig;
 use crate::domain::session::login::{
     LoginSession, LoginSessionCommand, LoginSessionError, QueryKind,
 };
+use crate::domain::session::practice::PracticeFeedback;
 use crate::domain::session::test::TestSummary;
 
 #[derive(Debug)]
 pub struct Core {
     question_repository: Arc<dyn QuestionRepository>,
     score_repository: Arc<dyn ScoreRepository>,
+    media_repository: Arc<dyn MediaRepository>,
+    practice_repository: Arc<dyn PracticeRepository>,
+    grading: GradingService,
+    review: ReviewService,
     id_allocator: Arc<SequentialIdAllocator>,
+    session_config: Arc<SessionConfig>,
     sessions: RwLock<HashMap<Id, Sender<Command<LoginSession>>>>,
     report: Mutex<Receiver<Report>>,
     sub_reporter: Sender<Report>,
+    // Set once shutdown begins, after which no login or test may start.
+    closed: AtomicBool,
 }
 
 impl Core {
     pub fn new(
         question_repository: Arc<dyn QuestionRepository>,
         score_repository: Arc<dyn ScoreRepository>,
+        media_repository: Arc<dyn MediaRepository>,
+        practice_repository: Arc<dyn PracticeRepository>,
+        review_repository: Arc<dyn ReviewRepository>,
+        session_config: SessionConfig,
     ) -> Self {
         let (sub_reporter, report) = mpsc::channel(128);
 
         Self {
+            grading: GradingService::new(
+                Arc::clone(&question_repository),
+                Arc::clone(&score_repository),
+                session_config.grade_scale.clone(),
+            ),
+            review: ReviewService::new(review_repository),
             question_repository,
             score_repository,
+            media_repository,
+            practice_repository,
             sessions: RwLock::new(HashMap::new()),
             id_allocator: Arc::new(SequentialIdAllocator::new()),
+            session_config: Arc::new(session_config),
             report: Mutex::new(report),
             sub_reporter,
+            closed: AtomicBool::new(false),
         }
     }
 
     pub async fn login(&self, user: User) -> Result<Id, CoreError> {
+        ensure!(!self.is_closed(), ShuttingDownSnafu);
         self.handle_exited_sessions().await;
 
         let (commander, command) = mpsc::channel(4);
         let base = SessionBase::new(
             Arc::clone(&self.id_allocator),
+            Arc::clone(&self.session_config),
             command,
             self.sub_reporter.clone(),
         )
@@ -60,6 +96,8 @@ impl Core {
             base,
             Arc::clone(&self.question_repository),
             Arc::clone(&self.score_repository),
+            Arc::clone(&self.practice_repository),
+            self.review.clone(),
         );
         let id = session.id();
         self.sessions.write().await.insert(id, commander.clone());
@@ -83,6 +121,7 @@ impl Core {
     }
 
     pub async fn start(&self, login_id: Id) -> Result<Test, CoreError> {
+        ensure!(!self.is_closed(), ShuttingDownSnafu);
         self.handle_exited_sessions().await;
 
         let (responder, respond) = oneshot::channel();
@@ -135,6 +174,37 @@ impl Core {
         Ok(res)
     }
 
+    // Keeps the answers of a running test, so that they can be graded if the server shuts down
+    // before the test is submitted.
+    pub async fn save_draft(
+        &self,
+        login_id: Id,
+        test_id: Id,
+        submission: Submission,
+    ) -> Result<(), CoreError> {
+        self.handle_exited_sessions().await;
+
+        let (responder, respond) = oneshot::channel();
+
+        let _ = self
+            .sessions
+            .read()
+            .await
+            .get(&login_id)
+            .context(SessionNotFoundSnafu { id: login_id })?
+            .send(Command::Extra(LoginSessionCommand::Draft {
+                test_id,
+                submission,
+                responder,
+            }))
+            .await;
+
+        respond
+            .await
+            .unwrap_or_else(|_| unreachable!("LoginSession should send response back"))
+            .context(LoginSessionSnafu { id: login_id })
+    }
+
     pub async fn query(&self, login_id: Id, kind: QueryKind) -> Result<Record, CoreError> {
         self.handle_exited_sessions().await;
 
@@ -182,6 +252,332 @@ impl Core {
         Ok(res)
     }
 
+    // Issues a certificate for the best passed attempt of the logged-in user.
+    pub async fn certificate(&self, login_id: Id) -> Result<Certificate, CoreError> {
+        self.handle_exited_sessions().await;
+
+        let (responder, respond) = oneshot::channel();
+
+        let _ = self
+            .sessions
+            .read()
+            .await
+            .get(&login_id)
+            .context(SessionNotFoundSnafu { id: login_id })?
+            .send(Command::Extra(LoginSessionCommand::Certificate {
+                responder,
+            }))
+            .await;
+
+        let res = respond
+            .await
+            .unwrap_or_else(|_| unreachable!("LoginSession should send response back"))
+            .context(LoginSessionSnafu { id: login_id })?;
+
+        Ok(res)
+    }
+
+    // A review serves only the questions due for the user instead of random ones.
+    pub async fn practice_start(&self, login_id: Id, review: bool) -> Result<Id, CoreError> {
+        ensure!(!self.is_closed(), ShuttingDownSnafu);
+        self.handle_exited_sessions().await;
+
+        let (responder, respond) = oneshot::channel();
+
+        let _ = self
+            .sessions
+            .read()
+            .await
+            .get(&login_id)
+            .context(SessionNotFoundSnafu { id: login_id })?
+            .send(Command::Extra(LoginSessionCommand::Practice {
+                review,
+                responder,
+            }))
+            .await;
+
+        let res = respond
+            .await
+            .unwrap_or_else(|_| unreachable!("LoginSession should send response back"))
+            .context(LoginSessionSnafu { id: login_id })?;
+
+        Ok(res)
+    }
+
+    pub async fn practice_next(
+        &self,
+        login_id: Id,
+        practice_id: Id,
+    ) -> Result<Option<Question>, CoreError> {
+        self.handle_exited_sessions().await;
+
+        let (responder, respond) = oneshot::channel();
+
+        let _ = self
+            .sessions
+            .read()
+            .await
+            .get(&login_id)
+            .context(SessionNotFoundSnafu { id: login_id })?
+            .send(Command::Extra(LoginSessionCommand::PracticeNext {
+                practice_id,
+                responder,
+            }))
+            .await;
+
+        let res = respond
+            .await
+            .unwrap_or_else(|_| unreachable!("LoginSession should send response back"))
+            .context(LoginSessionSnafu { id: login_id })?;
+
+        Ok(res)
+    }
+
+    pub async fn practice_answer(
+        &self,
+        login_id: Id,
+        practice_id: Id,
+        answer: Answer<SubmissionSource>,
+    ) -> Result<PracticeFeedback, CoreError> {
+        self.handle_exited_sessions().await;
+
+        let (responder, respond) = oneshot::channel();
+
+        let _ = self
+            .sessions
+            .read()
+            .await
+            .get(&login_id)
+            .context(SessionNotFoundSnafu { id: login_id })?
+            .send(Command::Extra(LoginSessionCommand::PracticeAnswer {
+                practice_id,
+                answer,
+                responder,
+            }))
+            .await;
+
+        let res = respond
+            .await
+            .unwrap_or_else(|_| unreachable!("LoginSession should send response back"))
+            .context(LoginSessionSnafu { id: login_id })?;
+
+        Ok(res)
+    }
+
+    pub async fn adaptive_start(&self, login_id: Id) -> Result<Id, CoreError> {
+        ensure!(!self.is_closed(), ShuttingDownSnafu);
+        self.handle_exited_sessions().await;
+
+        let (responder, respond) = oneshot::channel();
+
+        let _ = self
+            .sessions
+            .read()
+            .await
+            .get(&login_id)
+            .context(SessionNotFoundSnafu { id: login_id })?
+            .send(Command::Extra(LoginSessionCommand::Adaptive { responder }))
+            .await;
+
+        let res = respond
+            .await
+            .unwrap_or_else(|_| unreachable!("LoginSession should send response back"))
+            .context(LoginSessionSnafu { id: login_id })?;
+
+        Ok(res)
+    }
+
+    pub async fn adaptive_next(
+        &self,
+        login_id: Id,
+        test_id: Id,
+    ) -> Result<Option<Question>, CoreError> {
+        self.handle_exited_sessions().await;
+
+        let (responder, respond) = oneshot::channel();
+
+        let _ = self
+            .sessions
+            .read()
+            .await
+            .get(&login_id)
+            .context(SessionNotFoundSnafu { id: login_id })?
+            .send(Command::Extra(LoginSessionCommand::AdaptiveNext {
+                test_id,
+                responder,
+            }))
+            .await;
+
+        let res = respond
+            .await
+            .unwrap_or_else(|_| unreachable!("LoginSession should send response back"))
+            .context(LoginSessionSnafu { id: login_id })?;
+
+        Ok(res)
+    }
+
+    pub async fn adaptive_answer(
+        &self,
+        login_id: Id,
+        test_id: Id,
+        answer: Answer<SubmissionSource>,
+    ) -> Result<AdaptiveProgress, CoreError> {
+        self.handle_exited_sessions().await;
+
+        let (responder, respond) = oneshot::channel();
+
+        let _ = self
+            .sessions
+            .read()
+            .await
+            .get(&login_id)
+            .context(SessionNotFoundSnafu { id: login_id })?
+            .send(Command::Extra(LoginSessionCommand::AdaptiveAnswer {
+                test_id,
+                answer,
+                responder,
+            }))
+            .await;
+
+        let res = respond
+            .await
+            .unwrap_or_else(|_| unreachable!("LoginSession should send response back"))
+            .context(LoginSessionSnafu { id: login_id })?;
+
+        Ok(res)
+    }
+
+    pub async fn adaptive_finish(
+        &self,
+        login_id: Id,
+        test_id: Id,
+    ) -> Result<AdaptiveSummary, CoreError> {
+        self.handle_exited_sessions().await;
+
+        let (responder, respond) = oneshot::channel();
+
+        let _ = self
+            .sessions
+            .read()
+            .await
+            .get(&login_id)
+            .context(SessionNotFoundSnafu { id: login_id })?
+            .send(Command::Extra(LoginSessionCommand::AdaptiveFinish {
+                test_id,
+                responder,
+            }))
+            .await;
+
+        let res = respond
+            .await
+            .unwrap_or_else(|_| unreachable!("LoginSession should send response back"))
+            .context(LoginSessionSnafu { id: login_id })?;
+
+        Ok(res)
+    }
+
+    // Media is only served to sessions whose running test includes the question it belongs to.
+    pub async fn media(&self, login_id: Id, id: Id) -> Result<(Media, Vec<u8>), CoreError> {
+        self.handle_exited_sessions().await;
+
+        let media = self
+            .media_repository
+            .find_media(id)
+            .await
+            .context(MediaSnafu)?;
+
+        let (responder, respond) = oneshot::channel();
+
+        let _ = self
+            .sessions
+            .read()
+            .await
+            .get(&login_id)
+            .context(SessionNotFoundSnafu { id: login_id })?
+            .send(Command::Extra(LoginSessionCommand::Access {
+                question: media.question,
+                responder,
+            }))
+            .await;
+
+        respond
+            .await
+            .unwrap_or_else(|_| unreachable!("LoginSession should send response back"))
+            .context(LoginSessionSnafu { id: login_id })?;
+
+        let data = self
+            .media_repository
+            .read_media(id)
+            .await
+            .context(MediaSnafu)?;
+
+        Ok((media, data))
+    }
+
+    pub async fn pending_reviews(&self) -> Result<Vec<PendingReview>, CoreError> {
+        self.grading.pending().await.context(GradingSnafu)
+    }
+
+    pub async fn grade(
+        &self,
+        item: Id,
+        points: f32,
+        comment: Option<String>,
+        grader: String,
+    ) -> Result<Option<Score>, CoreError> {
+        self.grading
+            .grade(item, points, comment, grader)
+            .await
+            .context(GradingSnafu)
+    }
+
+    // Stops accepting new logins and tests, while requests of existing sessions are served.
+    pub fn close(&self) {
+        self.closed.store(true, Ordering::Release);
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::Acquire)
+    }
+
+    // Cancels every session and waits for them to finalize. With `auto_grade`, open tests are
+    // graded from their last draft first.
+    pub async fn shutdown(&self, auto_grade: bool) -> ShutdownSummary {
+        self.close();
+        self.handle_exited_sessions().await;
+
+        let sessions: Vec<_> = self.sessions.write().await.drain().collect();
+        let mut summary = ShutdownSummary::default();
+
+        if auto_grade {
+            for (_, commander) in &sessions {
+                let (responder, respond) = oneshot::channel();
+                let command = LoginSessionCommand::Finish { responder };
+
+                if commander.send(Command::Extra(command)).await.is_ok() {
+                    summary.graded += respond.await.unwrap_or(0);
+                }
+            }
+        }
+
+        for (_, commander) in sessions {
+            if commander.send(Command::Cancel).await.is_ok() {
+                summary.sessions += 1;
+            }
+        }
+
+        let mut report = self.report.lock().await;
+        let mut running = summary.sessions;
+        while running > 0 {
+            match report.recv().await {
+                Some(Report::Exited { .. }) => running -= 1,
+                None => break,
+            }
+        }
+
+        summary
+    }
+
     async fn handle_exited_sessions(&self) {
         loop {
             let report = self.report.lock().await.try_recv();
@@ -195,6 +591,14 @@ impl Core {
     }
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ShutdownSummary {
+    // Login sessions cancelled.
+    pub sessions: usize,
+    // Open tests graded from their drafts.
+    pub graded: usize,
+}
+
 #[derive(Debug, Snafu)]
 #[non_exhaustive]
 pub enum CoreError {
@@ -202,4 +606,10 @@ pub enum CoreError {
     LoginSession { id: Id, source: LoginSessionError },
     #[snafu(display("Could not find session {id}"))]
     SessionNotFound { id: Id },
+    #[snafu(display("Error occurred while grading"))]
+    Grading { source: GradingError },
+    #[snafu(display("Could not load media"))]
+    Media { source: MediaRepositoryError },
+    #[snafu(display("Could not start while shutting down"))]
+    ShuttingDown,
 }
@@ -0,0 +1,87 @@
+use serde::Serialize;
+
+// Abilities and difficulties share the logit scale of the Rasch model, on which a student
+// answers an item correctly with probability `1 / (1 + e^(difficulty - ability))`.
+const ESTIMATE_BOUND: f32 = 4.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Difficulty(f32);
+
+impl Difficulty {
+    // Log-odds of a wrong answer, smoothed by one of each so that items never attempted
+    // start at 0.
+    pub fn calibrate(attempts: usize, correct: usize) -> Self {
+        let wrong = attempts.saturating_sub(correct);
+        let logit = ((wrong as f32 + 1.0) / (correct as f32 + 1.0)).ln();
+        Self(logit.clamp(-ESTIMATE_BOUND, ESTIMATE_BOUND))
+    }
+
+    pub fn inner(&self) -> f32 {
+        self.0
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct Ability {
+    estimate: f32,
+    answered: usize,
+}
+
+impl Ability {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn estimate(&self) -> f32 {
+        self.estimate
+    }
+
+    pub fn answered(&self) -> usize {
+        self.answered
+    }
+
+    pub fn probability(&self, difficulty: Difficulty) -> f32 {
+        1.0 / (1.0 + (difficulty.0 - self.estimate).exp())
+    }
+
+    // Elo-style step towards the observed result, shrinking as more answers are seen.
+    pub fn update(&mut self, difficulty: Difficulty, correct: bool) {
+        let k = 2.0 / (2.0 + self.answered as f32);
+        let observed = if correct { 1.0 } else { 0.0 };
+
+        self.estimate += k * (observed - self.probability(difficulty));
+        self.estimate = self.estimate.clamp(-ESTIMATE_BOUND, ESTIMATE_BOUND);
+        self.answered += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn difficulty_calibrate() {
+        assert_eq!(Difficulty::calibrate(0, 0), Difficulty(0.0));
+        assert!(Difficulty::calibrate(10, 9).inner() < 0.0);
+        assert!(Difficulty::calibrate(10, 1).inner() > 0.0);
+        assert_eq!(
+            Difficulty::calibrate(100_000, 0),
+            Difficulty(ESTIMATE_BOUND)
+        );
+    }
+
+    #[test]
+    fn ability_update() {
+        let mut ability = Ability::new();
+        assert_eq!(ability.probability(Difficulty(0.0)), 0.5);
+
+        ability.update(Difficulty(0.0), true);
+        assert_eq!(ability.estimate(), 0.5);
+        assert_eq!(ability.answered(), 1);
+
+        let before = ability.estimate();
+        ability.update(Difficulty(2.0), false);
+        assert!(ability.estimate() < before);
+        assert!(ability.estimate() > 0.0);
+    }
+}
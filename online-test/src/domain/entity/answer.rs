@@ -1,6 +1,8 @@
 use serde::Serialize;
 use snafu::prelude::*;
 
+use crate::domain::entity::question::QuestionKind;
+
 pub trait AnswerSourceMarker: Clone + Copy + PartialEq + Eq + Default {}
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -31,11 +33,17 @@ impl From<SubmissionSource> for AnswerSource {
 
 impl AnswerSourceMarker for SubmissionSource {}
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", bound = "")]
 pub enum Answer<Src: AnswerSourceMarker> {
     SingleSelection(SingleSelectionAnswer<Src>),
     MultipleSelection(MultipleSelectionAnswer<Src>),
     Completion(CompletionAnswer<Src>),
+    TrueFalse(TrueFalseAnswer<Src>),
+    Ordering(OrderingAnswer<Src>),
+    Matching(MatchingAnswer<Src>),
+    Essay(EssayAnswer<Src>),
+    Cloze(ClozeAnswer<Src>),
 }
 
 impl Answer<StandardSource> {
@@ -44,6 +52,11 @@ impl Answer<StandardSource> {
             Self::SingleSelection(s) => s.source(),
             Self::MultipleSelection(s) => s.source(),
             Self::Completion(s) => s.source(),
+            Self::TrueFalse(s) => s.source(),
+            Self::Ordering(s) => s.source(),
+            Self::Matching(s) => s.source(),
+            Self::Essay(s) => s.source(),
+            Self::Cloze(s) => s.source(),
         }
     }
 
@@ -52,6 +65,11 @@ impl Answer<StandardSource> {
             Self::SingleSelection(s) => s.check(submission),
             Self::MultipleSelection(s) => s.check(submission),
             Self::Completion(s) => s.check(submission),
+            Self::TrueFalse(s) => s.check(submission),
+            Self::Ordering(s) => s.check(submission),
+            Self::Matching(s) => s.check(submission),
+            Self::Essay(s) => s.check(submission),
+            Self::Cloze(s) => s.check(submission),
         }
     }
 }
@@ -62,8 +80,31 @@ impl Answer<SubmissionSource> {
             Self::SingleSelection(s) => s.source(),
             Self::MultipleSelection(s) => s.source(),
             Self::Completion(s) => s.source(),
+            Self::TrueFalse(s) => s.source(),
+            Self::Ordering(s) => s.source(),
+            Self::Matching(s) => s.source(),
+            Self::Essay(s) => s.source(),
+            Self::Cloze(s) => s.source(),
+        }
+    }
+
+    pub fn kind(&self) -> QuestionKind {
+        match self {
+            Self::SingleSelection(_) => QuestionKind::SingleSelection,
+            Self::MultipleSelection(_) => QuestionKind::MultipleSelection,
+            Self::Completion(_) => QuestionKind::Completion,
+            Self::TrueFalse(_) => QuestionKind::TrueFalse,
+            Self::Ordering(_) => QuestionKind::Ordering,
+            Self::Matching(_) => QuestionKind::Matching,
+            Self::Essay(_) => QuestionKind::Essay,
+            Self::Cloze(_) => QuestionKind::Cloze,
         }
     }
+
+    // Selecting no option at all is how an untouched multiple-selection question is submitted.
+    pub fn is_blank(&self) -> bool {
+        matches!(self, Self::MultipleSelection(s) if s.value().is_empty())
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
@@ -80,6 +121,10 @@ impl<Src: AnswerSourceMarker> SingleSelectionAnswer<Src> {
             source: Default::default(),
         })
     }
+
+    pub fn value(&self) -> u32 {
+        self.value
+    }
 }
 
 impl<Src: AnswerSourceMarker> From<SingleSelectionAnswer<Src>> for Answer<Src> {
@@ -114,6 +159,12 @@ pub struct MultipleSelectionAnswer<Src: AnswerSourceMarker> {
     source: Src,
 }
 
+impl<Src: AnswerSourceMarker> MultipleSelectionAnswer<Src> {
+    pub fn value(&self) -> &[u32] {
+        &self.value
+    }
+}
+
 impl<Src: AnswerSourceMarker> From<MultipleSelectionAnswer<Src>> for Answer<Src> {
     fn from(value: MultipleSelectionAnswer<Src>) -> Self {
         Answer::MultipleSelection(value)
@@ -171,6 +222,12 @@ pub struct CompletionAnswer<Src: AnswerSourceMarker> {
     source: Src,
 }
 
+impl<Src: AnswerSourceMarker> CompletionAnswer<Src> {
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+}
+
 impl<Src: AnswerSourceMarker> From<CompletionAnswer<Src>> for Answer<Src> {
     fn from(value: CompletionAnswer<Src>) -> Self {
         Answer::Completion(value)
@@ -211,6 +268,334 @@ impl CompletionAnswer<SubmissionSource> {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TrueFalseAnswer<Src: AnswerSourceMarker> {
+    value: bool,
+    #[serde(skip)]
+    source: Src,
+}
+
+impl<Src: AnswerSourceMarker> TrueFalseAnswer<Src> {
+    pub fn try_new(value: bool) -> Result<Self, TryNewAnswerError> {
+        Ok(TrueFalseAnswer {
+            value,
+            source: Default::default(),
+        })
+    }
+
+    pub fn value(&self) -> bool {
+        self.value
+    }
+}
+
+impl<Src: AnswerSourceMarker> From<TrueFalseAnswer<Src>> for Answer<Src> {
+    fn from(value: TrueFalseAnswer<Src>) -> Self {
+        Answer::TrueFalse(value)
+    }
+}
+
+impl TrueFalseAnswer<StandardSource> {
+    pub fn source(&self) -> AnswerSource {
+        self.source.into()
+    }
+
+    pub fn check(&self, submission: &Answer<SubmissionSource>) -> bool {
+        match submission {
+            Answer::TrueFalse(submission) => self.value == submission.value,
+            _ => false,
+        }
+    }
+}
+
+impl TrueFalseAnswer<SubmissionSource> {
+    pub fn source(&self) -> AnswerSource {
+        self.source.into()
+    }
+}
+
+// The value lists item indices in their correct order.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct OrderingAnswer<Src: AnswerSourceMarker> {
+    value: Vec<u32>,
+    #[serde(skip)]
+    source: Src,
+}
+
+impl<Src: AnswerSourceMarker> OrderingAnswer<Src> {
+    pub fn value(&self) -> &[u32] {
+        &self.value
+    }
+}
+
+impl<Src: AnswerSourceMarker> From<OrderingAnswer<Src>> for Answer<Src> {
+    fn from(value: OrderingAnswer<Src>) -> Self {
+        Answer::Ordering(value)
+    }
+}
+
+impl OrderingAnswer<StandardSource> {
+    pub fn try_new(value: Vec<u32>) -> Result<Self, TryNewAnswerError> {
+        ensure!(value.len() >= 2, ItemInsufficientSnafu);
+        let mut sorted = value.clone();
+        sorted.sort();
+        ensure!(
+            sorted.iter().enumerate().all(|(i, x)| i as u32 == *x),
+            ItemMismatchedSnafu
+        );
+        Ok(OrderingAnswer {
+            value,
+            source: Default::default(),
+        })
+    }
+
+    pub fn source(&self) -> AnswerSource {
+        self.source.into()
+    }
+
+    pub fn check(&self, submission: &Answer<SubmissionSource>) -> bool {
+        match submission {
+            Answer::Ordering(submission) => self.value == submission.value,
+            _ => false,
+        }
+    }
+
+    // Fraction of item pairs placed in the same relative order as the answer. Items missing
+    // from the submission count against every pair they belong to.
+    pub fn credit(&self, submission: &Answer<SubmissionSource>) -> f32 {
+        let Answer::Ordering(submission) = submission else {
+            return 0.0;
+        };
+
+        let position = |item: u32| submission.value.iter().position(|x| *x == item);
+        let pairs = self.value.len() * (self.value.len() - 1) / 2;
+        let concordant = self
+            .value
+            .iter()
+            .enumerate()
+            .flat_map(|(i, a)| self.value[i + 1..].iter().map(move |b| (*a, *b)))
+            .filter(|(a, b)| matches!((position(*a), position(*b)), (Some(x), Some(y)) if x < y))
+            .count();
+
+        concordant as f32 / pairs as f32
+    }
+}
+
+impl OrderingAnswer<SubmissionSource> {
+    pub fn try_new(value: Vec<u32>) -> Result<Self, TryNewAnswerError> {
+        let mut sorted = value.clone();
+        sorted.sort();
+        ensure!(
+            sorted
+                .iter()
+                .zip(sorted.iter().skip(1))
+                .all(|(x, y)| x != y),
+            OptionDuplicatedSnafu
+        );
+        Ok(OrderingAnswer {
+            value,
+            source: Default::default(),
+        })
+    }
+
+    pub fn source(&self) -> AnswerSource {
+        self.source.into()
+    }
+}
+
+// The value holds, for every item in order, the index of the target it is matched to. Items
+// left unmatched in a submission are `None`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MatchingAnswer<Src: AnswerSourceMarker> {
+    value: Vec<Option<u32>>,
+    #[serde(skip)]
+    source: Src,
+}
+
+impl<Src: AnswerSourceMarker> MatchingAnswer<Src> {
+    pub fn value(&self) -> &[Option<u32>] {
+        &self.value
+    }
+}
+
+impl<Src: AnswerSourceMarker> From<MatchingAnswer<Src>> for Answer<Src> {
+    fn from(value: MatchingAnswer<Src>) -> Self {
+        Answer::Matching(value)
+    }
+}
+
+impl MatchingAnswer<StandardSource> {
+    pub fn try_new(value: Vec<u32>) -> Result<Self, TryNewAnswerError> {
+        ensure!(value.len() >= 2, PairInsufficientSnafu);
+        Ok(MatchingAnswer {
+            value: value.into_iter().map(Some).collect(),
+            source: Default::default(),
+        })
+    }
+
+    pub fn targets(&self) -> Vec<u32> {
+        self.value.iter().flatten().copied().collect()
+    }
+
+    pub fn source(&self) -> AnswerSource {
+        self.source.into()
+    }
+
+    pub fn check(&self, submission: &Answer<SubmissionSource>) -> bool {
+        match submission {
+            Answer::Matching(submission) => self.value == submission.value,
+            _ => false,
+        }
+    }
+
+    // Fraction of items matched to their correct target.
+    pub fn credit(&self, submission: &Answer<SubmissionSource>) -> f32 {
+        let Answer::Matching(submission) = submission else {
+            return 0.0;
+        };
+
+        let matched = self
+            .value
+            .iter()
+            .zip(submission.value.iter())
+            .filter(|(x, y)| x == y)
+            .count();
+
+        matched as f32 / self.value.len() as f32
+    }
+}
+
+impl MatchingAnswer<SubmissionSource> {
+    pub fn try_new(value: Vec<Option<u32>>) -> Result<Self, TryNewAnswerError> {
+        Ok(MatchingAnswer {
+            value,
+            source: Default::default(),
+        })
+    }
+
+    pub fn source(&self) -> AnswerSource {
+        self.source.into()
+    }
+}
+
+// The standard value is a reference answer for graders, which may be left empty.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct EssayAnswer<Src: AnswerSourceMarker> {
+    value: String,
+    #[serde(skip)]
+    source: Src,
+}
+
+impl<Src: AnswerSourceMarker> EssayAnswer<Src> {
+    pub fn try_new<S: AsRef<str>>(value: S) -> Result<Self, TryNewAnswerError> {
+        Ok(EssayAnswer {
+            value: value.as_ref().into(),
+            source: Default::default(),
+        })
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+}
+
+impl<Src: AnswerSourceMarker> From<EssayAnswer<Src>> for Answer<Src> {
+    fn from(value: EssayAnswer<Src>) -> Self {
+        Answer::Essay(value)
+    }
+}
+
+impl EssayAnswer<StandardSource> {
+    pub fn source(&self) -> AnswerSource {
+        self.source.into()
+    }
+
+    // Essays are never graded automatically.
+    pub fn check(&self, _submission: &Answer<SubmissionSource>) -> bool {
+        false
+    }
+}
+
+impl EssayAnswer<SubmissionSource> {
+    pub fn source(&self) -> AnswerSource {
+        self.source.into()
+    }
+}
+
+// The value holds the accepted answers of every blank in order. A submission fills each blank
+// with exactly one answer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ClozeAnswer<Src: AnswerSourceMarker> {
+    value: Vec<Vec<String>>,
+    #[serde(skip)]
+    source: Src,
+}
+
+impl<Src: AnswerSourceMarker> ClozeAnswer<Src> {
+    pub fn value(&self) -> &[Vec<String>] {
+        &self.value
+    }
+}
+
+impl<Src: AnswerSourceMarker> From<ClozeAnswer<Src>> for Answer<Src> {
+    fn from(value: ClozeAnswer<Src>) -> Self {
+        Answer::Cloze(value)
+    }
+}
+
+impl ClozeAnswer<StandardSource> {
+    pub fn try_new(value: Vec<Vec<String>>) -> Result<Self, TryNewAnswerError> {
+        ensure!(!value.is_empty(), BlankInsufficientSnafu);
+        ensure!(
+            value
+                .iter()
+                .all(|b| !b.is_empty() && b.iter().all(|a| !a.is_empty())),
+            ContentEmptySnafu
+        );
+        Ok(ClozeAnswer {
+            value,
+            source: Default::default(),
+        })
+    }
+
+    pub fn source(&self) -> AnswerSource {
+        self.source.into()
+    }
+
+    pub fn check(&self, submission: &Answer<SubmissionSource>) -> bool {
+        self.credit(submission) == 1.0
+    }
+
+    // Fraction of blanks filled with one of their accepted answers. Surrounding whitespace of
+    // the submission is ignored.
+    pub fn credit(&self, submission: &Answer<SubmissionSource>) -> f32 {
+        let Answer::Cloze(submission) = submission else {
+            return 0.0;
+        };
+
+        let filled = self
+            .value
+            .iter()
+            .zip(submission.value.iter())
+            .filter(|(accepted, filled)| accepted.iter().any(|a| filled[0].trim() == a))
+            .count();
+
+        filled as f32 / self.value.len() as f32
+    }
+}
+
+impl ClozeAnswer<SubmissionSource> {
+    pub fn try_new(value: Vec<String>) -> Result<Self, TryNewAnswerError> {
+        Ok(ClozeAnswer {
+            value: value.into_iter().map(|a| vec![a]).collect(),
+            source: Default::default(),
+        })
+    }
+
+    pub fn source(&self) -> AnswerSource {
+        self.source.into()
+    }
+}
+
 #[derive(Debug, Clone, Snafu, PartialEq, Eq)]
 pub enum TryNewAnswerError {
     #[snafu(display("Multiple selection has duplicated options"))]
@@ -219,6 +604,14 @@ pub enum TryNewAnswerError {
     OptionEmpty,
     #[snafu(display("Completion must have non-empty content as answer"))]
     ContentEmpty,
+    #[snafu(display("Ordering must have at least two items"))]
+    ItemInsufficient,
+    #[snafu(display("Ordering must place every item exactly once"))]
+    ItemMismatched,
+    #[snafu(display("Matching must have at least two pairs"))]
+    PairInsufficient,
+    #[snafu(display("Cloze must have at least one blank"))]
+    BlankInsufficient,
 }
 
 #[cfg(test)]
@@ -255,6 +648,90 @@ mod tests {
         assert!(CompletionAnswer::<SubmissionSource>::try_new("").is_ok());
     }
 
+    #[test]
+    fn answer_try_new_ordering() {
+        assert!(matches!(
+            OrderingAnswer::<StandardSource>::try_new(vec![0]),
+            Err(TryNewAnswerError::ItemInsufficient),
+        ));
+        assert!(matches!(
+            OrderingAnswer::<StandardSource>::try_new(vec![0, 2]),
+            Err(TryNewAnswerError::ItemMismatched),
+        ));
+        assert!(matches!(
+            OrderingAnswer::<SubmissionSource>::try_new(vec![1, 1]),
+            Err(TryNewAnswerError::OptionDuplicated),
+        ));
+        assert!(OrderingAnswer::<StandardSource>::try_new(vec![2, 0, 1]).is_ok());
+    }
+
+    #[test]
+    fn answer_ordering_credit() {
+        let answer = OrderingAnswer::<StandardSource>::try_new(vec![2, 0, 1, 3]).unwrap();
+        let credit = |value: Vec<u32>| {
+            answer.credit(
+                &OrderingAnswer::<SubmissionSource>::try_new(value)
+                    .unwrap()
+                    .into(),
+            )
+        };
+
+        assert_eq!(credit(vec![2, 0, 1, 3]), 1.0);
+        assert_eq!(credit(vec![3, 1, 0, 2]), 0.0);
+        assert_eq!(credit(vec![0, 2, 1, 3]), 5.0 / 6.0);
+        assert_eq!(credit(vec![2, 0]), 1.0 / 6.0);
+    }
+
+    #[test]
+    fn answer_matching_credit() {
+        assert!(matches!(
+            MatchingAnswer::<StandardSource>::try_new(vec![0]),
+            Err(TryNewAnswerError::PairInsufficient),
+        ));
+
+        let answer = MatchingAnswer::<StandardSource>::try_new(vec![1, 0, 2, 2]).unwrap();
+        let credit = |value: Vec<Option<u32>>| {
+            answer.credit(
+                &MatchingAnswer::<SubmissionSource>::try_new(value)
+                    .unwrap()
+                    .into(),
+            )
+        };
+
+        assert_eq!(credit(vec![Some(1), Some(0), Some(2), Some(2)]), 1.0);
+        assert_eq!(credit(vec![Some(0), Some(1), Some(2), None]), 0.25);
+        assert_eq!(credit(vec![Some(1)]), 0.25);
+        assert_eq!(credit(vec![]), 0.0);
+    }
+
+    #[test]
+    fn answer_cloze_credit() {
+        assert!(matches!(
+            ClozeAnswer::<StandardSource>::try_new(vec![]),
+            Err(TryNewAnswerError::BlankInsufficient),
+        ));
+        assert!(matches!(
+            ClozeAnswer::<StandardSource>::try_new(vec![vec!["ls".into()], vec![]]),
+            Err(TryNewAnswerError::ContentEmpty),
+        ));
+
+        let answer = ClozeAnswer::<StandardSource>::try_new(vec![
+            vec!["ls".into()],
+            vec!["cd".into(), "chdir".into()],
+        ])
+        .unwrap();
+        let submit = |value: Vec<&str>| -> Answer<SubmissionSource> {
+            ClozeAnswer::<SubmissionSource>::try_new(value.into_iter().map(Into::into).collect())
+                .unwrap()
+                .into()
+        };
+
+        assert!(answer.check(&submit(vec!["ls", " chdir "])));
+        assert_eq!(answer.credit(&submit(vec!["ls", "pwd"])), 0.5);
+        assert_eq!(answer.credit(&submit(vec!["cd"])), 0.0);
+        assert_eq!(answer.credit(&submit(vec![])), 0.0);
+    }
+
     #[test]
     fn answer_matches() {
         let a1 = SingleSelectionAnswer::<StandardSource>::try_new(0).unwrap();
@@ -0,0 +1,126 @@
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+use chrono::{DateTime, Utc};
+use snafu::prelude::*;
+
+use crate::domain::entity::grade::Grade;
+use crate::domain::entity::score::{Points, Score};
+use crate::domain::entity::user::User;
+
+// Digits and letters that cannot be mistaken for each other when typed from a printout.
+const ALPHABET: &[u8] = b"23456789ABCDEFGHJKLMNPQRSTUVWXYZ";
+const GROUP_COUNT: usize = 3;
+const GROUP_LENGTH: usize = 4;
+
+// Code printed on a certificate, written as dash-separated groups such as `7KQF-2M9X-HC4T`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct VerificationCode(String);
+
+impl VerificationCode {
+    // Drawn from the operating system, as codes must not be guessable from earlier ones. The
+    // alphabet has 32 characters, so every byte maps to one without bias.
+    pub fn generate() -> Result<Self, GenerateVerificationCodeError> {
+        let mut bytes = [0; GROUP_COUNT * GROUP_LENGTH];
+        getrandom::fill(&mut bytes).context(RandomSnafu)?;
+
+        let code: String = bytes
+            .iter()
+            .map(|b| ALPHABET[*b as usize % ALPHABET.len()] as char)
+            .collect();
+        Ok(Self(group(&code)))
+    }
+
+    // Case and dashes are ignored, so that codes typed by hand are accepted.
+    pub fn try_new<S: AsRef<str>>(code: S) -> Result<Self, TryNewVerificationCodeError> {
+        let code: String = code
+            .as_ref()
+            .chars()
+            .filter(|c| *c != '-' && !c.is_whitespace())
+            .map(|c| c.to_ascii_uppercase())
+            .collect();
+
+        ensure!(
+            code.len() == GROUP_COUNT * GROUP_LENGTH && code.bytes().all(|c| ALPHABET.contains(&c)),
+            InvalidSnafu
+        );
+        Ok(Self(group(&code)))
+    }
+
+    pub fn inner(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for VerificationCode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{}", self.0)
+    }
+}
+
+fn group(code: &str) -> String {
+    code.as_bytes()
+        .chunks(GROUP_LENGTH)
+        .map(|c| String::from_utf8_lossy(c))
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+#[derive(Debug, Clone, Snafu, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TryNewVerificationCodeError {
+    #[snafu(display(
+        "Verification code should be {GROUP_COUNT} groups of {GROUP_LENGTH} letters and digits"
+    ))]
+    Invalid,
+}
+
+#[derive(Debug, Snafu)]
+#[non_exhaustive]
+pub enum GenerateVerificationCodeError {
+    #[snafu(display("Could not draw random verification code"))]
+    Random { source: getrandom::Error },
+}
+
+// Proof that a user passed the attempt ending at `end_time`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Certificate {
+    pub code: VerificationCode,
+    pub user: User,
+    pub score: Score,
+    pub points: Points,
+    pub grade: Grade,
+    pub end_time: DateTime<Utc>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verification_code_generate() {
+        let code = VerificationCode::generate().unwrap();
+        assert_eq!(code.inner().len(), 14);
+        assert_eq!(VerificationCode::try_new(code.inner()).unwrap(), code);
+    }
+
+    #[test]
+    fn try_new_verification_code() {
+        let expected = VerificationCode("7KQF-2M9X-HC4T".into());
+        assert_eq!(
+            VerificationCode::try_new("7KQF-2M9X-HC4T").unwrap(),
+            expected
+        );
+        assert_eq!(
+            VerificationCode::try_new(" 7kqf2m9xhc4t ").unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn try_new_verification_code_invalid() {
+        for code in ["", "7KQF-2M9X", "7KQF-2M9X-HC4T-A", "7KQF-2M9X-HC40"] {
+            let actual = VerificationCode::try_new(code);
+            assert!(matches!(actual, Err(TryNewVerificationCodeError::Invalid)));
+        }
+    }
+}
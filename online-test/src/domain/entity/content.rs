@@ -0,0 +1,511 @@
+use std::collections::{HashMap, HashSet};
+
+use ammonia::Builder;
+use pulldown_cmark::{html, Options, Parser};
+use serde::{Serialize, Serializer};
+use snafu::prelude::*;
+
+const ALLOWED_TAGS: &[&str] = &[
+    "a",
+    "b",
+    "blockquote",
+    "br",
+    "code",
+    "del",
+    "em",
+    "hr",
+    "i",
+    "img",
+    "kbd",
+    "li",
+    "ol",
+    "p",
+    "pre",
+    "s",
+    "samp",
+    "strong",
+    "sub",
+    "sup",
+    "table",
+    "tbody",
+    "td",
+    "th",
+    "thead",
+    "tr",
+    "u",
+    "ul",
+];
+
+// `media` references attachments by ID, see `Media`.
+const ALLOWED_URL_SCHEMES: &[&str] = &["http", "https", "mailto", "media"];
+
+const KNOWN_TAGS: &[&str] = &[
+    "a",
+    "abbr",
+    "address",
+    "area",
+    "article",
+    "aside",
+    "audio",
+    "b",
+    "base",
+    "bdi",
+    "bdo",
+    "blockquote",
+    "body",
+    "br",
+    "button",
+    "canvas",
+    "caption",
+    "cite",
+    "code",
+    "col",
+    "colgroup",
+    "data",
+    "datalist",
+    "dd",
+    "del",
+    "details",
+    "dfn",
+    "dialog",
+    "div",
+    "dl",
+    "dt",
+    "em",
+    "embed",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "head",
+    "header",
+    "hr",
+    "html",
+    "i",
+    "iframe",
+    "img",
+    "input",
+    "ins",
+    "kbd",
+    "label",
+    "legend",
+    "li",
+    "link",
+    "main",
+    "map",
+    "mark",
+    "math",
+    "menu",
+    "meta",
+    "meter",
+    "nav",
+    "noscript",
+    "object",
+    "ol",
+    "optgroup",
+    "option",
+    "output",
+    "p",
+    "param",
+    "picture",
+    "pre",
+    "progress",
+    "q",
+    "rp",
+    "rt",
+    "ruby",
+    "s",
+    "samp",
+    "script",
+    "section",
+    "select",
+    "slot",
+    "small",
+    "source",
+    "span",
+    "strong",
+    "style",
+    "sub",
+    "summary",
+    "sup",
+    "svg",
+    "table",
+    "tbody",
+    "td",
+    "template",
+    "textarea",
+    "tfoot",
+    "th",
+    "thead",
+    "time",
+    "title",
+    "tr",
+    "track",
+    "u",
+    "ul",
+    "var",
+    "video",
+    "wbr",
+];
+
+const KNOWN_ATTRIBUTES: &[&str] = &[
+    "action",
+    "alt",
+    "class",
+    "formaction",
+    "height",
+    "href",
+    "id",
+    "lang",
+    "name",
+    "rel",
+    "src",
+    "srcdoc",
+    "srcset",
+    "style",
+    "target",
+    "title",
+    "type",
+    "value",
+    "width",
+];
+
+const KNOWN_ATTRIBUTE_PREFIXES: &[&str] = &["on", "data-", "aria-"];
+
+const KNOWN_URL_SCHEMES: &[&str] = &[
+    "data",
+    "file",
+    "ftp",
+    "http",
+    "https",
+    "javascript",
+    "mailto",
+    "media",
+    "vbscript",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ContentFormat {
+    Plain,
+    Markdown,
+    #[default]
+    Html,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Content {
+    format: ContentFormat,
+    source: String,
+    rendered: String,
+}
+
+impl Content {
+    pub fn try_new<S: AsRef<str>>(
+        format: ContentFormat,
+        source: S,
+    ) -> Result<Self, TryNewContentError> {
+        let source = source.as_ref();
+        let unsanitized = render(format, source);
+        let rendered = sanitize(&unsanitized);
+        ensure!(rendered == normalize(&unsanitized), UnsafeSnafu);
+
+        Ok(Self {
+            format,
+            source: source.into(),
+            rendered,
+        })
+    }
+
+    pub fn sanitized<S: AsRef<str>>(format: ContentFormat, source: S) -> Self {
+        let source = source.as_ref();
+        let rendered = sanitize(&render(format, source));
+
+        Self {
+            format,
+            source: source.into(),
+            rendered,
+        }
+    }
+
+    pub fn format(&self) -> ContentFormat {
+        self.format
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    pub fn rendered(&self) -> &str {
+        &self.rendered
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.source.is_empty()
+    }
+
+    // Text nodes of the rendered HTML as a browser parses them, with entities decoded.
+    pub fn text_nodes(&self) -> Vec<String> {
+        let mut nodes = Vec::new();
+        let mut node = String::new();
+        let mut tag: Option<Option<char>> = None;
+
+        for c in self.rendered.chars() {
+            match (tag, c) {
+                (None, '<') => {
+                    if !node.is_empty() {
+                        nodes.push(decode_entities(&node));
+                        node.clear();
+                    }
+                    tag = Some(None);
+                }
+                (None, _) => node.push(c),
+                (Some(None), '>') => tag = None,
+                (Some(None), '"' | '\'') => tag = Some(Some(c)),
+                (Some(Some(quote)), _) if c == quote => tag = Some(None),
+                (Some(_), _) => {}
+            }
+        }
+
+        if !node.is_empty() {
+            nodes.push(decode_entities(&node));
+        }
+
+        nodes
+    }
+
+    pub fn text(&self) -> String {
+        self.text_nodes()
+            .iter()
+            .flat_map(|node| node.split_whitespace())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+impl From<&str> for Content {
+    fn from(value: &str) -> Self {
+        Self::sanitized(ContentFormat::Plain, value)
+    }
+}
+
+impl From<String> for Content {
+    fn from(value: String) -> Self {
+        Self::sanitized(ContentFormat::Plain, value)
+    }
+}
+
+impl Serialize for Content {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.rendered)
+    }
+}
+
+fn render(format: ContentFormat, source: &str) -> String {
+    match format {
+        ContentFormat::Plain => escape(source),
+        ContentFormat::Markdown => {
+            let mut res = String::with_capacity(source.len() * 3 / 2);
+            let parser = Parser::new_ext(
+                source,
+                Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TABLES,
+            );
+            html::push_html(&mut res, parser);
+            res.trim_end().to_owned()
+        }
+        ContentFormat::Html => source.to_owned(),
+    }
+}
+
+fn escape(source: &str) -> String {
+    let mut res = String::with_capacity(source.len());
+
+    for c in source.chars() {
+        match c {
+            '&' => res.push_str("&amp;"),
+            '<' => res.push_str("&lt;"),
+            '>' => res.push_str("&gt;"),
+            '"' => res.push_str("&quot;"),
+            '\'' => res.push_str("&#39;"),
+            _ => res.push(c),
+        }
+    }
+
+    res
+}
+
+fn decode_entities(text: &str) -> String {
+    let mut res = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        res.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let decoded = rest.find(';').and_then(|end| {
+            let c = match &rest[1..end] {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some('\u{a0}'),
+                entity => entity
+                    .strip_prefix("#x")
+                    .or_else(|| entity.strip_prefix("#X"))
+                    .map(|hex| u32::from_str_radix(hex, 16))
+                    .or_else(|| entity.strip_prefix('#').map(str::parse))
+                    .and_then(Result::ok)
+                    .and_then(char::from_u32),
+            }?;
+            Some((c, end))
+        });
+
+        match decoded {
+            Some((c, end)) => {
+                res.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                res.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    res.push_str(rest);
+    res
+}
+
+fn sanitize(html: &str) -> String {
+    let tag_attributes = HashMap::from([
+        ("a", HashSet::from(["href", "title"])),
+        (
+            "img",
+            HashSet::from(["src", "alt", "title", "width", "height"]),
+        ),
+    ]);
+
+    Builder::empty()
+        .tags(ALLOWED_TAGS.iter().copied().collect())
+        .tag_attributes(tag_attributes)
+        .generic_attributes(HashSet::new())
+        .url_schemes(ALLOWED_URL_SCHEMES.iter().copied().collect())
+        .link_rel(None)
+        .clean(html)
+        .to_string()
+}
+
+// Serializes HTML the same way as `sanitize` but keeps everything a browser
+// might act on, so comparing both results reveals whether anything was dropped.
+fn normalize(html: &str) -> String {
+    Builder::empty()
+        .tags(KNOWN_TAGS.iter().copied().collect())
+        .clean_content_tags(HashSet::new())
+        .tag_attributes(HashMap::new())
+        .generic_attributes(KNOWN_ATTRIBUTES.iter().copied().collect())
+        .generic_attribute_prefixes(KNOWN_ATTRIBUTE_PREFIXES.iter().copied().collect())
+        .url_schemes(KNOWN_URL_SCHEMES.iter().copied().collect())
+        .link_rel(None)
+        .clean(html)
+        .to_string()
+}
+
+#[derive(Debug, Clone, Snafu, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TryNewContentError {
+    #[snafu(display("Content contains markup which is not allowed"))]
+    Unsafe,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn content_try_new_plain() {
+        let content = Content::try_new(ContentFormat::Plain, "a <b> & 'c'").unwrap();
+        assert_eq!(content.rendered(), "a &lt;b&gt; &amp; 'c'");
+        assert_eq!(content.source(), "a <b> & 'c'");
+    }
+
+    #[test]
+    fn content_try_new_markdown() {
+        let content = Content::try_new(ContentFormat::Markdown, "Run `ls -l` **now**").unwrap();
+        assert_eq!(
+            content.rendered(),
+            "<p>Run <code>ls -l</code> <strong>now</strong></p>"
+        );
+    }
+
+    #[test]
+    fn content_try_new_html() {
+        let source = r#"Search "error" in <code>/var/log/syslog</code> &amp; <code>a > b</code>"#;
+        let content = Content::try_new(ContentFormat::Html, source).unwrap();
+        assert_eq!(
+            content.rendered(),
+            r#"Search "error" in <code>/var/log/syslog</code> &amp; <code>a &gt; b</code>"#
+        );
+    }
+
+    #[test]
+    fn content_try_new_media() {
+        let content = Content::try_new(ContentFormat::Markdown, "![output](media:3)").unwrap();
+        assert_eq!(
+            content.rendered(),
+            r#"<p><img src="media:3" alt="output"></p>"#
+        );
+    }
+
+    #[test]
+    fn content_try_new_unsafe() {
+        let sources = [
+            "<script>alert(1)</script>",
+            r#"<img src="x.png" onerror="alert(1)">"#,
+            r#"<a href="javascript:alert(1)">link</a>"#,
+            r#"<iframe src="https://example.com"></iframe>"#,
+            r#"<code style="color: red">ls</code>"#,
+        ];
+
+        for source in sources {
+            assert!(matches!(
+                Content::try_new(ContentFormat::Html, source),
+                Err(TryNewContentError::Unsafe),
+            ));
+        }
+
+        assert!(matches!(
+            Content::try_new(ContentFormat::Markdown, "text <script>alert(1)</script>"),
+            Err(TryNewContentError::Unsafe),
+        ));
+    }
+
+    #[test]
+    fn content_sanitized() {
+        let content = Content::sanitized(
+            ContentFormat::Html,
+            r#"<img src="x.png" onerror="alert(1)"><script>alert(1)</script>"#,
+        );
+        assert_eq!(content.rendered(), r#"<img src="x.png">"#);
+    }
+
+    #[test]
+    fn content_text() {
+        let content = Content::sanitized(
+            ContentFormat::Html,
+            r#"<p>Fish &amp; <a href="a___b" title="x > y">chips</a></p>
+<p>&#60;tag&#x3e;</p>"#,
+        );
+        assert_eq!(content.text_nodes(), ["Fish & ", "chips", "\n", "<tag>"]);
+        assert_eq!(content.text(), "Fish & chips <tag>");
+
+        let content = Content::sanitized(ContentFormat::Markdown, "Use `ls` to *list* files");
+        assert_eq!(content.text(), "Use ls to list files");
+    }
+}
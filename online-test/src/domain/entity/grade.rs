@@ -0,0 +1,119 @@
+use crate::domain::entity::score::Score;
+
+// Letter grade of a finished attempt and whether it passes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grade {
+    letter: String,
+    passed: bool,
+}
+
+impl Grade {
+    pub fn new(letter: impl Into<String>, passed: bool) -> Self {
+        Self {
+            letter: letter.into(),
+            passed,
+        }
+    }
+
+    pub fn letter(&self) -> &str {
+        &self.letter
+    }
+
+    pub fn passed(&self) -> bool {
+        self.passed
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GradeBand {
+    pub letter: String,
+    pub min: f32,
+}
+
+impl GradeBand {
+    pub fn new(letter: impl Into<String>, min: f32) -> Self {
+        Self {
+            letter: letter.into(),
+            min,
+        }
+    }
+}
+
+// Bands are ordered from the highest minimum score down. Scores below every band get the
+// letter of the lowest one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GradeScale {
+    pub bands: Vec<GradeBand>,
+    pub pass: f32,
+}
+
+impl GradeScale {
+    pub fn standard() -> Self {
+        Self {
+            bands: vec![
+                GradeBand::new("A", 90.0),
+                GradeBand::new("B", 80.0),
+                GradeBand::new("C", 70.0),
+                GradeBand::new("D", 60.0),
+                GradeBand::new("F", 0.0),
+            ],
+            pass: 60.0,
+        }
+    }
+
+    pub fn grade(&self, score: Score) -> Grade {
+        let score = score.inner();
+        let letter = self
+            .bands
+            .iter()
+            .find(|b| score >= b.min)
+            .or(self.bands.last())
+            .map_or("", |b| &b.letter);
+
+        Grade::new(letter, score >= self.pass)
+    }
+}
+
+impl Default for GradeScale {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grade_scale_standard() {
+        let scale = GradeScale::standard();
+        let grade = |score| scale.grade(Score::try_new(score).unwrap());
+
+        assert_eq!(grade(100.0), Grade::new("A", true));
+        assert_eq!(grade(90.0), Grade::new("A", true));
+        assert_eq!(grade(89.9), Grade::new("B", true));
+        assert_eq!(grade(60.0), Grade::new("D", true));
+        assert_eq!(grade(59.9), Grade::new("F", false));
+        assert_eq!(grade(0.0), Grade::new("F", false));
+    }
+
+    #[test]
+    fn grade_scale_custom() {
+        let scale = GradeScale {
+            bands: vec![
+                GradeBand::new("Distinction", 85.0),
+                GradeBand::new("Merit", 50.0),
+            ],
+            pass: 50.0,
+        };
+
+        assert_eq!(
+            scale.grade(Score::try_new(90.0).unwrap()),
+            Grade::new("Distinction", true)
+        );
+        assert_eq!(
+            scale.grade(Score::try_new(20.0).unwrap()),
+            Grade::new("Merit", false)
+        );
+    }
+}
@@ -1,6 +1,11 @@
+pub mod ability;
 pub mod answer;
+pub mod certificate;
+pub mod content;
+pub mod grade;
 pub mod id;
 pub mod question;
+pub mod review;
 pub mod score;
 pub mod test;
 pub mod user;
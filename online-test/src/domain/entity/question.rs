@@ -2,18 +2,43 @@ use serde::Serialize;
 use snafu::prelude::*;
 
 use crate::domain::entity::answer::{
-    Answer, CompletionAnswer, MultipleSelectionAnswer, SingleSelectionAnswer, StandardSource,
-    SubmissionSource,
+    Answer, ClozeAnswer, CompletionAnswer, EssayAnswer, MatchingAnswer, MultipleSelectionAnswer,
+    OrderingAnswer, SingleSelectionAnswer, StandardSource, SubmissionSource, TrueFalseAnswer,
 };
+use crate::domain::entity::content::Content;
 use crate::domain::entity::id::Id;
 
 #[enum_dispatch::enum_dispatch]
 pub trait AbstractQuestion {
     fn id(&self) -> Id;
 
-    fn content(&self) -> &str;
+    fn revision(&self) -> Id;
+
+    fn kind(&self) -> QuestionKind;
+
+    fn content(&self) -> &Content;
+
+    // Shown to the student only once the test has been submitted.
+    fn explanation(&self) -> Option<&Content>;
+
+    // Weight of the question within a test, or `None` to use the default of its kind.
+    fn points(&self) -> Option<u32>;
 
     fn check(&self, submission: &Answer<SubmissionSource>) -> bool;
+
+    // Share of the question's credit within [0, 1] earned by the submission.
+    fn credit(&self, submission: &Answer<SubmissionSource>) -> f32 {
+        if self.check(submission) {
+            1.0
+        } else {
+            0.0
+        }
+    }
+
+    // Whether submissions are left to a teacher instead of being graded automatically.
+    fn manual(&self) -> bool {
+        false
+    }
 }
 
 #[enum_dispatch::enum_dispatch(AbstractQuestion)]
@@ -23,22 +48,114 @@ pub enum Question {
     SingleSelection(SingleSelectionQuestion),
     MultipleSelection(MultipleSelectionQuestion),
     Completion(CompletionQuestion),
+    TrueFalse(TrueFalseQuestion),
+    Ordering(OrderingQuestion),
+    Matching(MatchingQuestion),
+    Essay(EssayQuestion),
+    Cloze(ClozeQuestion),
+}
+
+impl Question {
+    // Options of selection questions or items of ordering and matching questions.
+    pub fn choices(&self) -> &[Content] {
+        match self {
+            Self::SingleSelection(q) => q.options(),
+            Self::MultipleSelection(q) => q.options(),
+            Self::Ordering(q) => q.items(),
+            Self::Matching(q) => q.items(),
+            Self::Completion(_) | Self::TrueFalse(_) | Self::Essay(_) | Self::Cloze(_) => &[],
+        }
+    }
+
+    pub fn answer(&self) -> Answer<StandardSource> {
+        match self {
+            Self::SingleSelection(q) => q.answer().clone().into(),
+            Self::MultipleSelection(q) => q.answer().clone().into(),
+            Self::Completion(q) => q.answer().clone().into(),
+            Self::TrueFalse(q) => q.answer().clone().into(),
+            Self::Ordering(q) => q.answer().clone().into(),
+            Self::Matching(q) => q.answer().clone().into(),
+            Self::Essay(q) => q.answer().clone().into(),
+            Self::Cloze(q) => q.answer().clone().into(),
+        }
+    }
+
+    pub fn with_explanation(self, explanation: Option<Content>) -> Self {
+        match self {
+            Self::SingleSelection(q) => q.with_explanation(explanation).into(),
+            Self::MultipleSelection(q) => q.with_explanation(explanation).into(),
+            Self::Completion(q) => q.with_explanation(explanation).into(),
+            Self::TrueFalse(q) => q.with_explanation(explanation).into(),
+            Self::Ordering(q) => q.with_explanation(explanation).into(),
+            Self::Matching(q) => q.with_explanation(explanation).into(),
+            Self::Essay(q) => q.with_explanation(explanation).into(),
+            Self::Cloze(q) => q.with_explanation(explanation).into(),
+        }
+    }
+
+    pub fn with_points(self, points: Option<u32>) -> Self {
+        match self {
+            Self::SingleSelection(q) => q.with_points(points).into(),
+            Self::MultipleSelection(q) => q.with_points(points).into(),
+            Self::Completion(q) => q.with_points(points).into(),
+            Self::TrueFalse(q) => q.with_points(points).into(),
+            Self::Ordering(q) => q.with_points(points).into(),
+            Self::Matching(q) => q.with_points(points).into(),
+            Self::Essay(q) => q.with_points(points).into(),
+            Self::Cloze(q) => q.with_points(points).into(),
+        }
+    }
+
+    // Items of ordering questions are authored in any order, often the correct one, so they are
+    // served shuffled. Other questions are served as they are.
+    pub fn shuffled(self, rng: &mut fastrand::Rng) -> Self {
+        match self {
+            Self::Ordering(q) => q.shuffled(rng).into(),
+            q => q,
+        }
+    }
+
+    // Targets that the items of matching questions are matched to.
+    pub fn targets(&self) -> &[Content] {
+        match self {
+            Self::Matching(q) => q.targets(),
+            _ => &[],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum QuestionKind {
+    SingleSelection,
+    MultipleSelection,
+    Completion,
+    TrueFalse,
+    Ordering,
+    Matching,
+    Essay,
+    Cloze,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct SingleSelectionQuestion {
     id: Id,
-    content: String,
-    options: Vec<String>,
+    #[serde(skip)]
+    revision: Id,
+    content: Content,
+    options: Vec<Content>,
     #[serde(skip)]
     answer: SingleSelectionAnswer<StandardSource>,
+    #[serde(skip)]
+    explanation: Option<Content>,
+    #[serde(skip)]
+    points: Option<u32>,
 }
 
 impl SingleSelectionQuestion {
     pub fn try_new(
         id: Id,
-        content: String,
-        options: Vec<String>,
+        content: Content,
+        options: Vec<Content>,
         answer: SingleSelectionAnswer<StandardSource>,
     ) -> Result<Self, TryNewQuestionError> {
         ensure!(!content.is_empty(), ContentEmptySnafu);
@@ -47,15 +164,37 @@ impl SingleSelectionQuestion {
 
         Ok(Self {
             id,
+            revision: id,
             content,
             options,
             answer,
+            explanation: None,
+            points: None,
         })
     }
 
-    pub fn options(&self) -> &Vec<String> {
+    pub fn options(&self) -> &Vec<Content> {
         &self.options
     }
+
+    pub fn with_revision(mut self, revision: Id) -> Self {
+        self.revision = revision;
+        self
+    }
+
+    pub fn with_explanation(mut self, explanation: Option<Content>) -> Self {
+        self.explanation = explanation;
+        self
+    }
+
+    pub fn with_points(mut self, points: Option<u32>) -> Self {
+        self.points = points;
+        self
+    }
+
+    pub fn answer(&self) -> &SingleSelectionAnswer<StandardSource> {
+        &self.answer
+    }
 }
 
 impl AbstractQuestion for SingleSelectionQuestion {
@@ -63,10 +202,26 @@ impl AbstractQuestion for SingleSelectionQuestion {
         self.id
     }
 
-    fn content(&self) -> &str {
+    fn revision(&self) -> Id {
+        self.revision
+    }
+
+    fn kind(&self) -> QuestionKind {
+        QuestionKind::SingleSelection
+    }
+
+    fn content(&self) -> &Content {
         &self.content
     }
 
+    fn explanation(&self) -> Option<&Content> {
+        self.explanation.as_ref()
+    }
+
+    fn points(&self) -> Option<u32> {
+        self.points
+    }
+
     fn check(&self, submission: &Answer<SubmissionSource>) -> bool {
         self.answer.check(submission)
     }
@@ -75,17 +230,23 @@ impl AbstractQuestion for SingleSelectionQuestion {
 #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct MultipleSelectionQuestion {
     id: Id,
-    content: String,
-    options: Vec<String>,
+    #[serde(skip)]
+    revision: Id,
+    content: Content,
+    options: Vec<Content>,
     #[serde(skip)]
     answer: MultipleSelectionAnswer<StandardSource>,
+    #[serde(skip)]
+    explanation: Option<Content>,
+    #[serde(skip)]
+    points: Option<u32>,
 }
 
 impl MultipleSelectionQuestion {
     pub fn try_new(
         id: Id,
-        content: String,
-        options: Vec<String>,
+        content: Content,
+        options: Vec<Content>,
         answer: MultipleSelectionAnswer<StandardSource>,
     ) -> Result<Self, TryNewQuestionError> {
         ensure!(!content.is_empty(), ContentEmptySnafu);
@@ -94,15 +255,37 @@ impl MultipleSelectionQuestion {
 
         Ok(Self {
             id,
+            revision: id,
             content,
             options,
             answer,
+            explanation: None,
+            points: None,
         })
     }
 
-    pub fn options(&self) -> &Vec<String> {
+    pub fn options(&self) -> &Vec<Content> {
         &self.options
     }
+
+    pub fn with_revision(mut self, revision: Id) -> Self {
+        self.revision = revision;
+        self
+    }
+
+    pub fn with_explanation(mut self, explanation: Option<Content>) -> Self {
+        self.explanation = explanation;
+        self
+    }
+
+    pub fn with_points(mut self, points: Option<u32>) -> Self {
+        self.points = points;
+        self
+    }
+
+    pub fn answer(&self) -> &MultipleSelectionAnswer<StandardSource> {
+        &self.answer
+    }
 }
 
 impl AbstractQuestion for MultipleSelectionQuestion {
@@ -110,10 +293,26 @@ impl AbstractQuestion for MultipleSelectionQuestion {
         self.id
     }
 
-    fn content(&self) -> &str {
+    fn revision(&self) -> Id {
+        self.revision
+    }
+
+    fn kind(&self) -> QuestionKind {
+        QuestionKind::MultipleSelection
+    }
+
+    fn content(&self) -> &Content {
         &self.content
     }
 
+    fn explanation(&self) -> Option<&Content> {
+        self.explanation.as_ref()
+    }
+
+    fn points(&self) -> Option<u32> {
+        self.points
+    }
+
     fn check(&self, submission: &Answer<SubmissionSource>) -> bool {
         self.answer.check(submission)
     }
@@ -122,25 +321,53 @@ impl AbstractQuestion for MultipleSelectionQuestion {
 #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct CompletionQuestion {
     id: Id,
-    content: String,
+    #[serde(skip)]
+    revision: Id,
+    content: Content,
     #[serde(skip)]
     answer: CompletionAnswer<StandardSource>,
+    #[serde(skip)]
+    explanation: Option<Content>,
+    #[serde(skip)]
+    points: Option<u32>,
 }
 
 impl CompletionQuestion {
     pub fn try_new(
         id: Id,
-        content: String,
+        content: Content,
         answer: CompletionAnswer<StandardSource>,
     ) -> Result<Self, TryNewQuestionError> {
         ensure!(!content.is_empty(), ContentEmptySnafu);
 
         Ok(Self {
             id,
+            revision: id,
             content,
             answer,
+            explanation: None,
+            points: None,
         })
     }
+
+    pub fn with_revision(mut self, revision: Id) -> Self {
+        self.revision = revision;
+        self
+    }
+
+    pub fn with_explanation(mut self, explanation: Option<Content>) -> Self {
+        self.explanation = explanation;
+        self
+    }
+
+    pub fn with_points(mut self, points: Option<u32>) -> Self {
+        self.points = points;
+        self
+    }
+
+    pub fn answer(&self) -> &CompletionAnswer<StandardSource> {
+        &self.answer
+    }
 }
 
 impl AbstractQuestion for CompletionQuestion {
@@ -148,13 +375,544 @@ impl AbstractQuestion for CompletionQuestion {
         self.id
     }
 
-    fn content(&self) -> &str {
+    fn revision(&self) -> Id {
+        self.revision
+    }
+
+    fn kind(&self) -> QuestionKind {
+        QuestionKind::Completion
+    }
+
+    fn content(&self) -> &Content {
+        &self.content
+    }
+
+    fn explanation(&self) -> Option<&Content> {
+        self.explanation.as_ref()
+    }
+
+    fn points(&self) -> Option<u32> {
+        self.points
+    }
+
+    fn check(&self, submission: &Answer<SubmissionSource>) -> bool {
+        self.answer.check(submission)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TrueFalseQuestion {
+    id: Id,
+    #[serde(skip)]
+    revision: Id,
+    content: Content,
+    #[serde(skip)]
+    answer: TrueFalseAnswer<StandardSource>,
+    #[serde(skip)]
+    explanation: Option<Content>,
+    #[serde(skip)]
+    points: Option<u32>,
+}
+
+impl TrueFalseQuestion {
+    pub fn try_new(
+        id: Id,
+        content: Content,
+        answer: TrueFalseAnswer<StandardSource>,
+    ) -> Result<Self, TryNewQuestionError> {
+        ensure!(!content.is_empty(), ContentEmptySnafu);
+
+        Ok(Self {
+            id,
+            revision: id,
+            content,
+            answer,
+            explanation: None,
+            points: None,
+        })
+    }
+
+    pub fn with_revision(mut self, revision: Id) -> Self {
+        self.revision = revision;
+        self
+    }
+
+    pub fn with_explanation(mut self, explanation: Option<Content>) -> Self {
+        self.explanation = explanation;
+        self
+    }
+
+    pub fn with_points(mut self, points: Option<u32>) -> Self {
+        self.points = points;
+        self
+    }
+
+    pub fn answer(&self) -> &TrueFalseAnswer<StandardSource> {
+        &self.answer
+    }
+}
+
+impl AbstractQuestion for TrueFalseQuestion {
+    fn id(&self) -> Id {
+        self.id
+    }
+
+    fn revision(&self) -> Id {
+        self.revision
+    }
+
+    fn kind(&self) -> QuestionKind {
+        QuestionKind::TrueFalse
+    }
+
+    fn content(&self) -> &Content {
+        &self.content
+    }
+
+    fn explanation(&self) -> Option<&Content> {
+        self.explanation.as_ref()
+    }
+
+    fn points(&self) -> Option<u32> {
+        self.points
+    }
+
+    fn check(&self, submission: &Answer<SubmissionSource>) -> bool {
+        self.answer.check(submission)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct OrderingQuestion {
+    id: Id,
+    #[serde(skip)]
+    revision: Id,
+    content: Content,
+    items: Vec<Content>,
+    #[serde(skip)]
+    answer: OrderingAnswer<StandardSource>,
+    #[serde(skip)]
+    explanation: Option<Content>,
+    #[serde(skip)]
+    points: Option<u32>,
+}
+
+impl OrderingQuestion {
+    pub fn try_new(
+        id: Id,
+        content: Content,
+        items: Vec<Content>,
+        answer: OrderingAnswer<StandardSource>,
+    ) -> Result<Self, TryNewQuestionError> {
+        ensure!(!content.is_empty(), ContentEmptySnafu);
+        ensure!(items.iter().all(|i| !i.is_empty()), OptionEmptySnafu);
+        ensure!(items.len() == answer.value().len(), ItemMismatchedSnafu);
+
+        Ok(Self {
+            id,
+            revision: id,
+            content,
+            items,
+            answer,
+            explanation: None,
+            points: None,
+        })
+    }
+
+    pub fn items(&self) -> &Vec<Content> {
+        &self.items
+    }
+
+    pub fn with_revision(mut self, revision: Id) -> Self {
+        self.revision = revision;
+        self
+    }
+
+    pub fn with_explanation(mut self, explanation: Option<Content>) -> Self {
+        self.explanation = explanation;
+        self
+    }
+
+    pub fn with_points(mut self, points: Option<u32>) -> Self {
+        self.points = points;
+        self
+    }
+
+    pub fn answer(&self) -> &OrderingAnswer<StandardSource> {
+        &self.answer
+    }
+
+    // Reorders the items, never into the correct order, and renumbers the answer to match.
+    pub fn shuffled(mut self, rng: &mut fastrand::Rng) -> Self {
+        let correct: Vec<usize> = self.answer.value().iter().map(|&i| i as usize).collect();
+        let mut order: Vec<usize> = (0..self.items.len()).collect();
+        loop {
+            rng.shuffle(&mut order);
+            if order != correct {
+                break;
+            }
+        }
+
+        let answer = correct
+            .iter()
+            .map(|&item| order.iter().position(|&i| i == item).unwrap_or_default() as u32)
+            .collect();
+        self.items = order.iter().map(|&i| self.items[i].clone()).collect();
+        self.answer = OrderingAnswer::<StandardSource>::try_new(answer)
+            .unwrap_or_else(|_| unreachable!("Shuffled answer should be a permutation"));
+        self
+    }
+}
+
+impl AbstractQuestion for OrderingQuestion {
+    fn id(&self) -> Id {
+        self.id
+    }
+
+    fn revision(&self) -> Id {
+        self.revision
+    }
+
+    fn kind(&self) -> QuestionKind {
+        QuestionKind::Ordering
+    }
+
+    fn content(&self) -> &Content {
         &self.content
     }
 
+    fn explanation(&self) -> Option<&Content> {
+        self.explanation.as_ref()
+    }
+
+    fn points(&self) -> Option<u32> {
+        self.points
+    }
+
     fn check(&self, submission: &Answer<SubmissionSource>) -> bool {
         self.answer.check(submission)
     }
+
+    fn credit(&self, submission: &Answer<SubmissionSource>) -> f32 {
+        self.answer.credit(submission)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MatchingQuestion {
+    id: Id,
+    #[serde(skip)]
+    revision: Id,
+    content: Content,
+    items: Vec<Content>,
+    targets: Vec<Content>,
+    #[serde(skip)]
+    answer: MatchingAnswer<StandardSource>,
+    #[serde(skip)]
+    explanation: Option<Content>,
+    #[serde(skip)]
+    points: Option<u32>,
+}
+
+impl MatchingQuestion {
+    pub fn try_new(
+        id: Id,
+        content: Content,
+        items: Vec<Content>,
+        targets: Vec<Content>,
+        answer: MatchingAnswer<StandardSource>,
+    ) -> Result<Self, TryNewQuestionError> {
+        ensure!(!content.is_empty(), ContentEmptySnafu);
+        ensure!(items.iter().all(|i| !i.is_empty()), OptionEmptySnafu);
+        ensure!(targets.iter().all(|t| !t.is_empty()), OptionEmptySnafu);
+        ensure!(items.len() == answer.value().len(), ItemMismatchedSnafu);
+        ensure!(
+            answer
+                .targets()
+                .iter()
+                .all(|t| (*t as usize) < targets.len()),
+            TargetMismatchedSnafu
+        );
+
+        Ok(Self {
+            id,
+            revision: id,
+            content,
+            items,
+            targets,
+            answer,
+            explanation: None,
+            points: None,
+        })
+    }
+
+    pub fn items(&self) -> &Vec<Content> {
+        &self.items
+    }
+
+    pub fn targets(&self) -> &Vec<Content> {
+        &self.targets
+    }
+
+    // Submissions earn nothing unless they have a choice for every item, each either one of the
+    // targets or left unmatched.
+    fn fits(&self, submission: &Answer<SubmissionSource>) -> bool {
+        let Answer::Matching(submission) = submission else {
+            return false;
+        };
+
+        submission.value().len() == self.items.len()
+            && submission
+                .value()
+                .iter()
+                .flatten()
+                .all(|&t| (t as usize) < self.targets.len())
+    }
+
+    pub fn with_revision(mut self, revision: Id) -> Self {
+        self.revision = revision;
+        self
+    }
+
+    pub fn with_explanation(mut self, explanation: Option<Content>) -> Self {
+        self.explanation = explanation;
+        self
+    }
+
+    pub fn with_points(mut self, points: Option<u32>) -> Self {
+        self.points = points;
+        self
+    }
+
+    pub fn answer(&self) -> &MatchingAnswer<StandardSource> {
+        &self.answer
+    }
+}
+
+impl AbstractQuestion for MatchingQuestion {
+    fn id(&self) -> Id {
+        self.id
+    }
+
+    fn revision(&self) -> Id {
+        self.revision
+    }
+
+    fn kind(&self) -> QuestionKind {
+        QuestionKind::Matching
+    }
+
+    fn content(&self) -> &Content {
+        &self.content
+    }
+
+    fn explanation(&self) -> Option<&Content> {
+        self.explanation.as_ref()
+    }
+
+    fn points(&self) -> Option<u32> {
+        self.points
+    }
+
+    fn check(&self, submission: &Answer<SubmissionSource>) -> bool {
+        self.fits(submission) && self.answer.check(submission)
+    }
+
+    fn credit(&self, submission: &Answer<SubmissionSource>) -> f32 {
+        if self.fits(submission) {
+            self.answer.credit(submission)
+        } else {
+            0.0
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct EssayQuestion {
+    id: Id,
+    #[serde(skip)]
+    revision: Id,
+    content: Content,
+    #[serde(skip)]
+    answer: EssayAnswer<StandardSource>,
+    #[serde(skip)]
+    explanation: Option<Content>,
+    #[serde(skip)]
+    points: Option<u32>,
+}
+
+impl EssayQuestion {
+    pub fn try_new(
+        id: Id,
+        content: Content,
+        answer: EssayAnswer<StandardSource>,
+    ) -> Result<Self, TryNewQuestionError> {
+        ensure!(!content.is_empty(), ContentEmptySnafu);
+
+        Ok(Self {
+            id,
+            revision: id,
+            content,
+            answer,
+            explanation: None,
+            points: None,
+        })
+    }
+
+    pub fn with_revision(mut self, revision: Id) -> Self {
+        self.revision = revision;
+        self
+    }
+
+    pub fn with_explanation(mut self, explanation: Option<Content>) -> Self {
+        self.explanation = explanation;
+        self
+    }
+
+    pub fn with_points(mut self, points: Option<u32>) -> Self {
+        self.points = points;
+        self
+    }
+
+    pub fn answer(&self) -> &EssayAnswer<StandardSource> {
+        &self.answer
+    }
+}
+
+impl AbstractQuestion for EssayQuestion {
+    fn id(&self) -> Id {
+        self.id
+    }
+
+    fn revision(&self) -> Id {
+        self.revision
+    }
+
+    fn kind(&self) -> QuestionKind {
+        QuestionKind::Essay
+    }
+
+    fn content(&self) -> &Content {
+        &self.content
+    }
+
+    fn explanation(&self) -> Option<&Content> {
+        self.explanation.as_ref()
+    }
+
+    fn points(&self) -> Option<u32> {
+        self.points
+    }
+
+    fn check(&self, submission: &Answer<SubmissionSource>) -> bool {
+        self.answer.check(submission)
+    }
+
+    fn manual(&self) -> bool {
+        true
+    }
+}
+
+// Blanks are written inline in the content as runs of at least three underscores.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ClozeQuestion {
+    id: Id,
+    #[serde(skip)]
+    revision: Id,
+    content: Content,
+    #[serde(skip)]
+    answer: ClozeAnswer<StandardSource>,
+    #[serde(skip)]
+    explanation: Option<Content>,
+    #[serde(skip)]
+    points: Option<u32>,
+}
+
+impl ClozeQuestion {
+    pub fn try_new(
+        id: Id,
+        content: Content,
+        answer: ClozeAnswer<StandardSource>,
+    ) -> Result<Self, TryNewQuestionError> {
+        ensure!(!content.is_empty(), ContentEmptySnafu);
+        ensure!(
+            Self::blanks(&content) == answer.value().len(),
+            BlankMismatchedSnafu
+        );
+
+        Ok(Self {
+            id,
+            revision: id,
+            content,
+            answer,
+            explanation: None,
+            points: None,
+        })
+    }
+
+    // Runs of three or more underscores in the text, as the client turns them into inputs. Those
+    // in markup, such as in the URL of a link, are left alone.
+    pub fn blanks(content: &Content) -> usize {
+        content
+            .text_nodes()
+            .iter()
+            .flat_map(|node| node.split(|c| c != '_'))
+            .filter(|run| run.len() >= 3)
+            .count()
+    }
+
+    pub fn with_revision(mut self, revision: Id) -> Self {
+        self.revision = revision;
+        self
+    }
+
+    pub fn with_explanation(mut self, explanation: Option<Content>) -> Self {
+        self.explanation = explanation;
+        self
+    }
+
+    pub fn with_points(mut self, points: Option<u32>) -> Self {
+        self.points = points;
+        self
+    }
+
+    pub fn answer(&self) -> &ClozeAnswer<StandardSource> {
+        &self.answer
+    }
+}
+
+impl AbstractQuestion for ClozeQuestion {
+    fn id(&self) -> Id {
+        self.id
+    }
+
+    fn revision(&self) -> Id {
+        self.revision
+    }
+
+    fn kind(&self) -> QuestionKind {
+        QuestionKind::Cloze
+    }
+
+    fn content(&self) -> &Content {
+        &self.content
+    }
+
+    fn explanation(&self) -> Option<&Content> {
+        self.explanation.as_ref()
+    }
+
+    fn points(&self) -> Option<u32> {
+        self.points
+    }
+
+    fn check(&self, submission: &Answer<SubmissionSource>) -> bool {
+        self.answer.check(submission)
+    }
+
+    fn credit(&self, submission: &Answer<SubmissionSource>) -> f32 {
+        self.answer.credit(submission)
+    }
 }
 
 #[derive(Debug, Clone, Snafu, PartialEq, Eq)]
@@ -166,10 +924,18 @@ pub enum TryNewQuestionError {
     OptionEmpty,
     #[snafu(display("Question must have four options"))]
     OptionInsufficient,
+    #[snafu(display("Question must have as many items as its answer orders"))]
+    ItemMismatched,
+    #[snafu(display("Question must match every item to one of its targets"))]
+    TargetMismatched,
+    #[snafu(display("Question must have as many blanks as its answer fills"))]
+    BlankMismatched,
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::domain::entity::content::ContentFormat;
+
     use super::*;
 
     #[test]
@@ -184,9 +950,7 @@ mod tests {
                     "option-2".into(),
                     "option-3".into(),
                 ],
-                SingleSelectionAnswer::<StandardSource>::try_new(0)
-                    .unwrap()
-                    .into()
+                SingleSelectionAnswer::<StandardSource>::try_new(0).unwrap()
             ),
             Err(TryNewQuestionError::ContentEmpty),
         ));
@@ -199,9 +963,7 @@ mod tests {
                 0.into(),
                 "content".into(),
                 vec!["option-0".into()],
-                MultipleSelectionAnswer::<StandardSource>::try_new(vec![0])
-                    .unwrap()
-                    .into()
+                MultipleSelectionAnswer::<StandardSource>::try_new(vec![0]).unwrap()
             ),
             Err(TryNewQuestionError::OptionInsufficient),
         ));
@@ -219,9 +981,7 @@ mod tests {
                     "option-2".into(),
                     "".into(),
                 ],
-                SingleSelectionAnswer::<StandardSource>::try_new(0)
-                    .unwrap()
-                    .into()
+                SingleSelectionAnswer::<StandardSource>::try_new(0).unwrap()
             ),
             Err(TryNewQuestionError::OptionEmpty),
         ));
@@ -238,9 +998,7 @@ mod tests {
                 "option-2".into(),
                 "option-3".into(),
             ],
-            SingleSelectionAnswer::<StandardSource>::try_new(0)
-                .unwrap()
-                .into(),
+            SingleSelectionAnswer::<StandardSource>::try_new(0).unwrap(),
         )
         .unwrap()
         .into();
@@ -252,4 +1010,180 @@ mod tests {
         let mismatched = CompletionAnswer::<SubmissionSource>::try_new("").unwrap();
         assert!(!question.check(&mismatched.into()));
     }
+
+    #[test]
+    fn question_try_new_target_mismatched() {
+        assert!(matches!(
+            MatchingQuestion::try_new(
+                0.into(),
+                "content".into(),
+                vec!["item-0".into(), "item-1".into()],
+                vec!["target-0".into(), "target-1".into()],
+                MatchingAnswer::<StandardSource>::try_new(vec![0, 2]).unwrap(),
+            ),
+            Err(TryNewQuestionError::TargetMismatched),
+        ));
+        assert!(matches!(
+            MatchingQuestion::try_new(
+                0.into(),
+                "content".into(),
+                vec!["item-0".into(), "item-1".into(), "item-2".into()],
+                vec!["target-0".into(), "target-1".into()],
+                MatchingAnswer::<StandardSource>::try_new(vec![0, 1]).unwrap(),
+            ),
+            Err(TryNewQuestionError::ItemMismatched),
+        ));
+    }
+
+    #[test]
+    fn question_try_new_blank_mismatched() {
+        let answer =
+            ClozeAnswer::<StandardSource>::try_new(vec![vec!["ls".into()], vec!["cd".into()]])
+                .unwrap();
+
+        assert!(matches!(
+            ClozeQuestion::try_new(0.into(), "Use ___ to list files".into(), answer.clone()),
+            Err(TryNewQuestionError::BlankMismatched),
+        ));
+        assert!(ClozeQuestion::try_new(
+            0.into(),
+            "Use ___ to list files and _____ to change directory".into(),
+            answer,
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn question_cloze_blanks() {
+        let blanks =
+            |source: &str| ClozeQuestion::blanks(&Content::sanitized(ContentFormat::Html, source));
+
+        assert_eq!(blanks("Use ___ to list files"), 1);
+        assert_eq!(
+            blanks(r#"See <a href="a___b" title="___">___</a> and ___"#),
+            2
+        );
+        assert_eq!(blanks("Split __<b>_</b>__ runs"), 0);
+        assert_eq!(blanks("Entities &#95;&#95;&#95; count too"), 1);
+    }
+
+    #[test]
+    fn question_shuffled() {
+        let items: Vec<Content> = vec!["item-0".into(), "item-1".into(), "item-2".into()];
+        let question: Question = OrderingQuestion::try_new(
+            0.into(),
+            "content".into(),
+            items.clone(),
+            OrderingAnswer::<StandardSource>::try_new(vec![0, 1, 2]).unwrap(),
+        )
+        .unwrap()
+        .into();
+
+        for seed in 0..20 {
+            let shuffled = question
+                .clone()
+                .shuffled(&mut fastrand::Rng::with_seed(seed));
+
+            // Submitting the items as served never scores, while the renumbered answer does.
+            let unchanged = OrderingAnswer::<SubmissionSource>::try_new(vec![0, 1, 2]).unwrap();
+            assert!(!shuffled.check(&unchanged.into()));
+            let Answer::Ordering(answer) = shuffled.answer() else {
+                unreachable!()
+            };
+            let ordered: Vec<Content> = answer
+                .value()
+                .iter()
+                .map(|&i| shuffled.choices()[i as usize].clone())
+                .collect();
+            assert_eq!(ordered, items);
+        }
+    }
+
+    #[test]
+    fn question_matching_fits() {
+        let question: Question = MatchingQuestion::try_new(
+            0.into(),
+            "content".into(),
+            vec!["item-0".into(), "item-1".into()],
+            vec!["target-0".into(), "target-1".into()],
+            MatchingAnswer::<StandardSource>::try_new(vec![1, 0]).unwrap(),
+        )
+        .unwrap()
+        .into();
+        let submit = |value: Vec<Option<u32>>| -> Answer<SubmissionSource> {
+            MatchingAnswer::<SubmissionSource>::try_new(value)
+                .unwrap()
+                .into()
+        };
+
+        assert!(question.check(&submit(vec![Some(1), Some(0)])));
+        assert_eq!(question.credit(&submit(vec![Some(1), None])), 0.5);
+
+        // Choices for items or targets the question does not have are not credited.
+        assert_eq!(question.credit(&submit(vec![Some(1)])), 0.0);
+        assert_eq!(question.credit(&submit(vec![Some(1), Some(0), None])), 0.0);
+        assert_eq!(question.credit(&submit(vec![Some(1), Some(2)])), 0.0);
+        assert!(!question.check(&submit(vec![Some(1), Some(2)])));
+    }
+
+    #[test]
+    fn question_credit() {
+        let question: Question = OrderingQuestion::try_new(
+            0.into(),
+            "content".into(),
+            vec!["item-0".into(), "item-1".into(), "item-2".into()],
+            OrderingAnswer::<StandardSource>::try_new(vec![1, 2, 0]).unwrap(),
+        )
+        .unwrap()
+        .into();
+
+        let swapped = OrderingAnswer::<SubmissionSource>::try_new(vec![2, 1, 0]).unwrap();
+        assert!(!question.check(&swapped.clone().into()));
+        assert_eq!(question.credit(&swapped.into()), 2.0 / 3.0);
+
+        let question: Question = TrueFalseQuestion::try_new(
+            0.into(),
+            "content".into(),
+            TrueFalseAnswer::try_new(true).unwrap(),
+        )
+        .unwrap()
+        .into();
+        assert_eq!(
+            question.credit(
+                &TrueFalseAnswer::<SubmissionSource>::try_new(true)
+                    .unwrap()
+                    .into()
+            ),
+            1.0
+        );
+        assert_eq!(
+            question.credit(
+                &TrueFalseAnswer::<SubmissionSource>::try_new(false)
+                    .unwrap()
+                    .into()
+            ),
+            0.0
+        );
+    }
+
+    #[test]
+    fn question_serialize_hides_explanation() {
+        let question: Question = TrueFalseQuestion::try_new(
+            0.into(),
+            "content".into(),
+            TrueFalseAnswer::try_new(true).unwrap(),
+        )
+        .unwrap()
+        .into();
+        let question = question.with_explanation(Some("explanation".into()));
+
+        assert_eq!(
+            question.explanation().map(Content::source),
+            Some("explanation")
+        );
+        assert_eq!(
+            serde_json::to_value(&question).unwrap(),
+            serde_json::json!({ "type": "TrueFalse", "id": 0, "content": "content" })
+        );
+    }
 }
@@ -0,0 +1,105 @@
+use chrono::{DateTime, TimeDelta, Utc};
+
+use crate::domain::entity::id::Id;
+
+const INITIAL_EASE: f32 = 2.5;
+const MINIMUM_EASE: f32 = 1.3;
+
+// SM-2 schedule of one question for one user. Answers are only right or wrong, so they are
+// rated 4 and 1 on the 0 to 5 quality scale of SM-2.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReviewCard {
+    pub question: Id,
+    pub repetitions: u32,
+    pub interval_days: u32,
+    pub ease: f32,
+    pub due: DateTime<Utc>,
+    pub attempts: u32,
+    pub correct: u32,
+}
+
+impl ReviewCard {
+    pub fn new(question: Id, now: DateTime<Utc>) -> Self {
+        Self {
+            question,
+            repetitions: 0,
+            interval_days: 0,
+            ease: INITIAL_EASE,
+            due: now,
+            attempts: 0,
+            correct: 0,
+        }
+    }
+
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        self.due <= now
+    }
+
+    // Questions always answered correctly are tracked but never come up for review.
+    pub fn missed(&self) -> bool {
+        self.correct < self.attempts
+    }
+
+    pub fn review(&mut self, correct: bool, now: DateTime<Utc>) {
+        let quality: f32 = if correct { 4.0 } else { 1.0 };
+
+        if correct {
+            self.repetitions += 1;
+            self.interval_days = match self.repetitions {
+                1 => 1,
+                2 => 6,
+                _ => (self.interval_days as f32 * self.ease).round() as u32,
+            };
+        } else {
+            self.repetitions = 0;
+            self.interval_days = 1;
+        }
+
+        let lapse = 5.0 - quality;
+        self.ease = (self.ease + 0.1 - lapse * (0.08 + lapse * 0.02)).max(MINIMUM_EASE);
+        self.due = now + TimeDelta::days(self.interval_days.into());
+        self.attempts += 1;
+        self.correct += u32::from(correct);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn review_card_schedule() {
+        let now = Utc::now();
+        let mut card = ReviewCard::new(0.into(), now);
+        assert!(card.is_due(now));
+
+        assert!(!card.missed());
+        card.review(false, now);
+        assert!(card.missed());
+        assert_eq!(card.interval_days, 1);
+        assert!((card.ease - 1.96).abs() < 1e-5);
+        assert!(!card.is_due(now));
+        assert!(card.is_due(now + TimeDelta::days(1)));
+
+        card.review(true, now);
+        assert_eq!(card.interval_days, 1);
+        card.review(true, now);
+        assert_eq!(card.interval_days, 6);
+        card.review(true, now);
+        assert_eq!(card.interval_days, 12);
+        assert_eq!(card.due, now + TimeDelta::days(12));
+        assert_eq!((card.attempts, card.correct), (4, 3));
+    }
+
+    #[test]
+    fn review_card_minimum_ease() {
+        let now = Utc::now();
+        let mut card = ReviewCard::new(0.into(), now);
+
+        for _ in 0..5 {
+            card.review(false, now);
+        }
+        assert_eq!(card.ease, MINIMUM_EASE);
+        assert_eq!(card.repetitions, 0);
+    }
+}
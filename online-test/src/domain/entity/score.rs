@@ -21,6 +21,57 @@ impl From<Score> for f32 {
     }
 }
 
+// Points earned in an attempt out of the total points of its questions, along with the
+// negative marks deducted for wrong answers.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Points {
+    earned: f32,
+    penalty: f32,
+    total: u32,
+}
+
+impl Points {
+    pub fn new(earned: f32, total: u32) -> Self {
+        Self {
+            earned,
+            penalty: 0.0,
+            total,
+        }
+    }
+
+    pub fn with_penalty(mut self, penalty: f32) -> Self {
+        self.penalty = penalty;
+        self
+    }
+
+    pub fn earned(&self) -> f32 {
+        self.earned
+    }
+
+    pub fn penalty(&self) -> f32 {
+        self.penalty
+    }
+
+    pub fn total(&self) -> u32 {
+        self.total
+    }
+
+    // Negative marks never take an attempt below zero.
+    pub fn net(&self) -> f32 {
+        (self.earned - self.penalty).max(0.0)
+    }
+
+    // An attempt without any points to earn counts as full marks.
+    pub fn score(&self) -> Score {
+        if self.total == 0 {
+            return Score(100f32);
+        }
+
+        Score::try_new((100f32 * self.net() / self.total as f32).clamp(0f32, 100f32))
+            .unwrap_or_else(|_| unreachable!("Clamped `f32` should be converted to `Score`"))
+    }
+}
+
 #[derive(Debug, Clone, Snafu, PartialEq, Eq)]
 #[non_exhaustive]
 pub enum TryNewScoreError {
@@ -46,4 +97,21 @@ mod tests {
         let large = Score::try_new(101f32);
         assert!(matches!(large, Err(TryNewScoreError::Invalid)));
     }
+
+    #[test]
+    fn points_score() {
+        assert_eq!(Points::new(3.5, 7).score(), Score(50f32));
+        assert_eq!(Points::new(0.0, 0).score(), Score(100f32));
+    }
+
+    #[test]
+    fn points_score_penalty() {
+        let points = Points::new(3.0, 4).with_penalty(1.0);
+        assert_eq!(points.net(), 2.0);
+        assert_eq!(points.score(), Score(50f32));
+
+        let points = Points::new(1.0, 4).with_penalty(2.5);
+        assert_eq!(points.net(), 0.0);
+        assert_eq!(points.score(), Score(0f32));
+    }
 }
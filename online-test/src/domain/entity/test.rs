@@ -3,43 +3,245 @@ use std::collections::HashMap;
 use serde::Serialize;
 
 use crate::domain::entity::answer::{Answer, SubmissionSource};
+use crate::domain::entity::content::Content;
+use crate::domain::entity::grade::{Grade, GradeScale};
 use crate::domain::entity::id::Id;
-use crate::domain::entity::question::{AbstractQuestion, Question};
-use crate::domain::entity::score::Score;
+use crate::domain::entity::question::{AbstractQuestion, Question, QuestionKind};
+use crate::domain::entity::score::{Points, Score};
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct Test {
     id: Id,
     questions: Vec<Question>,
+    #[serde(skip)]
+    blueprint: Blueprint,
 }
 
 impl Test {
     pub fn new(id: Id, questions: Vec<Question>) -> Self {
-        Self { id, questions }
+        Self {
+            id,
+            questions,
+            blueprint: Blueprint::default(),
+        }
+    }
+
+    pub fn with_blueprint(mut self, blueprint: Blueprint) -> Self {
+        self.blueprint = blueprint;
+        self
     }
 
     pub fn id(&self) -> Id {
         self.id
     }
 
+    pub fn questions(&self) -> &[Question] {
+        &self.questions
+    }
+
     pub fn grade(&self, submission: &Submission) -> Score {
-        if self.questions.is_empty() {
-            return Score::try_new(100f32)
-                .unwrap_or_else(|_| unreachable!("`100f32` should be converted to `Score`"));
-        }
+        Self::score(&self.grade_items(submission))
+    }
 
-        let correct = self
-            .questions
+    // Manually graded items are left pending with no credit, keeping the submitted text for
+    // the grader. Wrong answers lose the negative marks of their kind in proportion to the
+    // credit they miss, while unanswered ones and answers of another kind lose nothing.
+    pub fn grade_items(&self, submission: &Submission) -> Vec<ItemResult> {
+        self.questions
             .iter()
-            .filter_map(|q| submission.answers.get(&q.id()).map(|a| (q, a)))
-            .filter(|(q, a)| q.check(a))
-            .count();
+            .map(|q| {
+                let answer = submission.answers.get(&q.id()).filter(|a| !a.is_blank());
+                let response = match answer {
+                    Some(Answer::Essay(a)) if q.manual() => Some(a.value().to_owned()),
+                    _ => None,
+                };
+
+                let correct = answer.is_some_and(|a| q.check(a));
+                let credit = answer.map_or(0.0, |a| q.credit(a));
+                let points = self.blueprint.points(q);
+                let penalty = match answer {
+                    Some(a) if a.kind() == q.kind() && !q.manual() => {
+                        self.blueprint.penalty.rate(q.kind()) * points as f32 * (1.0 - credit)
+                    }
+                    _ => 0.0,
+                };
+
+                ItemResult {
+                    question: q.id(),
+                    revision: q.revision(),
+                    correct,
+                    credit,
+                    points,
+                    penalty,
+                    pending: q.manual(),
+                    response,
+                }
+            })
+            .collect()
+    }
+
+    // Pairs graded items with the explanations of their questions, which students only see
+    // after submitting.
+    pub fn feedback(&self, items: &[ItemResult]) -> Vec<ItemFeedback> {
+        self.questions
+            .iter()
+            .zip(items)
+            .map(|(q, i)| ItemFeedback {
+                question: q.id(),
+                correct: i.correct,
+                pending: i.pending,
+                penalty: i.penalty,
+                explanation: q.explanation().cloned(),
+            })
+            .collect()
+    }
+
+    // Attempts with items still pending have no grade yet.
+    pub fn evaluate(&self, items: &[ItemResult]) -> Option<Grade> {
+        (!items.iter().any(|i| i.pending)).then(|| self.blueprint.scale.grade(Self::score(items)))
+    }
+
+    pub fn score(items: &[ItemResult]) -> Score {
+        Self::points(items).score()
+    }
+
+    pub fn points(items: &[ItemResult]) -> Points {
+        let earned = items.iter().map(|i| i.credit * i.points as f32).sum();
+        let total = items.iter().map(|i| i.points).sum();
+        let penalty = items.iter().map(|i| i.penalty).sum();
+
+        Points::new(earned, total).with_penalty(penalty)
+    }
+}
+
+// How a test is scored: default points by kind for questions without a weight of their own,
+// negative marks for wrong answers and the grade bands of the final score.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Blueprint {
+    pub weights: Weights,
+    pub penalty: Penalty,
+    pub scale: GradeScale,
+}
+
+impl Blueprint {
+    pub fn points(&self, question: &Question) -> u32 {
+        question
+            .points()
+            .unwrap_or(self.weights.points(question.kind()))
+    }
+}
+
+// Every question is worth one point and wrong answers lose nothing.
+impl Default for Blueprint {
+    fn default() -> Self {
+        Self {
+            weights: Weights::UNIFORM,
+            penalty: Penalty::NONE,
+            scale: GradeScale::standard(),
+        }
+    }
+}
+
+// Points a question is worth by kind, unless it has a weight of its own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Weights {
+    pub single_selection: u32,
+    pub multiple_selection: u32,
+    pub completion: u32,
+    pub true_false: u32,
+    pub ordering: u32,
+    pub matching: u32,
+    pub essay: u32,
+    pub cloze: u32,
+}
+
+impl Weights {
+    pub const UNIFORM: Self = Self {
+        single_selection: 1,
+        multiple_selection: 1,
+        completion: 1,
+        true_false: 1,
+        ordering: 1,
+        matching: 1,
+        essay: 1,
+        cloze: 1,
+    };
 
-        Score::try_new(100f32 * correct as f32 / self.questions.len() as f32)
-            .unwrap_or_else(|_| unreachable!("`f32` variable should be converted to `Score`"))
+    pub fn points(&self, kind: QuestionKind) -> u32 {
+        match kind {
+            QuestionKind::SingleSelection => self.single_selection,
+            QuestionKind::MultipleSelection => self.multiple_selection,
+            QuestionKind::Completion => self.completion,
+            QuestionKind::TrueFalse => self.true_false,
+            QuestionKind::Ordering => self.ordering,
+            QuestionKind::Matching => self.matching,
+            QuestionKind::Essay => self.essay,
+            QuestionKind::Cloze => self.cloze,
+        }
     }
 }
 
+// Share of a question's points deducted for a wrong answer, by kind.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Penalty {
+    pub single_selection: f32,
+    pub multiple_selection: f32,
+    pub completion: f32,
+    pub true_false: f32,
+    pub ordering: f32,
+    pub matching: f32,
+    pub essay: f32,
+    pub cloze: f32,
+}
+
+impl Penalty {
+    pub const NONE: Self = Self {
+        single_selection: 0.0,
+        multiple_selection: 0.0,
+        completion: 0.0,
+        true_false: 0.0,
+        ordering: 0.0,
+        matching: 0.0,
+        essay: 0.0,
+        cloze: 0.0,
+    };
+
+    pub fn rate(&self, kind: QuestionKind) -> f32 {
+        match kind {
+            QuestionKind::SingleSelection => self.single_selection,
+            QuestionKind::MultipleSelection => self.multiple_selection,
+            QuestionKind::Completion => self.completion,
+            QuestionKind::TrueFalse => self.true_false,
+            QuestionKind::Ordering => self.ordering,
+            QuestionKind::Matching => self.matching,
+            QuestionKind::Essay => self.essay,
+            QuestionKind::Cloze => self.cloze,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ItemResult {
+    pub question: Id,
+    pub revision: Id,
+    pub correct: bool,
+    pub credit: f32,
+    pub points: u32,
+    // Points deducted for a wrong answer.
+    pub penalty: f32,
+    pub pending: bool,
+    pub response: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ItemFeedback {
+    pub question: Id,
+    pub correct: bool,
+    pub pending: bool,
+    pub penalty: f32,
+    pub explanation: Option<Content>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Submission {
     answers: HashMap<Id, Answer<SubmissionSource>>,
@@ -55,9 +257,13 @@ impl Submission {
 mod tests {
     use crate::domain::entity::{
         answer::{
-            CompletionAnswer, MultipleSelectionAnswer, SingleSelectionAnswer, StandardSource,
+            CompletionAnswer, EssayAnswer, MultipleSelectionAnswer, OrderingAnswer,
+            SingleSelectionAnswer, StandardSource, TrueFalseAnswer,
+        },
+        question::{
+            CompletionQuestion, EssayQuestion, MultipleSelectionQuestion, OrderingQuestion,
+            SingleSelectionQuestion, TrueFalseQuestion,
         },
-        question::{CompletionQuestion, MultipleSelectionQuestion, SingleSelectionQuestion},
     };
 
     use super::*;
@@ -138,5 +344,411 @@ mod tests {
         );
 
         assert_eq!(test.grade(&submission), Score::try_new(75f32).unwrap());
+        assert_eq!(
+            test.grade_items(&submission)
+                .into_iter()
+                .map(|i| i.correct)
+                .collect::<Vec<_>>(),
+            vec![true, false, true, true],
+        );
+    }
+
+    #[test]
+    fn test_grade_partial_credit() {
+        let test = Test::new(
+            0.into(),
+            vec![
+                TrueFalseQuestion::try_new(
+                    0.into(),
+                    "0. True-false".into(),
+                    TrueFalseAnswer::<StandardSource>::try_new(true).unwrap(),
+                )
+                .unwrap()
+                .into(),
+                OrderingQuestion::try_new(
+                    1.into(),
+                    "1. Ordering".into(),
+                    vec!["item a".into(), "item b".into(), "item c".into()],
+                    OrderingAnswer::<StandardSource>::try_new(vec![0, 1, 2]).unwrap(),
+                )
+                .unwrap()
+                .into(),
+            ],
+        );
+
+        let submission = Submission::new(
+            vec![
+                TrueFalseAnswer::<SubmissionSource>::try_new(true)
+                    .unwrap()
+                    .into(),
+                OrderingAnswer::<SubmissionSource>::try_new(vec![0, 2, 1])
+                    .unwrap()
+                    .into(),
+            ]
+            .into_iter()
+            .enumerate()
+            .map(|(i, a)| (i.into(), a))
+            .collect(),
+        );
+
+        let items = test.grade_items(&submission);
+        assert_eq!(
+            items.iter().map(|i| i.correct).collect::<Vec<_>>(),
+            vec![true, false],
+        );
+        assert_eq!(
+            items.iter().map(|i| i.credit).collect::<Vec<_>>(),
+            vec![1.0, 2.0 / 3.0],
+        );
+        assert_eq!(
+            test.grade(&submission),
+            Score::try_new(100f32 * (1.0 + 2.0 / 3.0) / 2.0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_grade_weighted() {
+        let blueprint = Blueprint {
+            weights: Weights {
+                completion: 3,
+                ..Weights::UNIFORM
+            },
+            ..Blueprint::default()
+        };
+        let test = Test::new(
+            0.into(),
+            vec![
+                TrueFalseQuestion::try_new(
+                    0.into(),
+                    "0. True-false".into(),
+                    TrueFalseAnswer::<StandardSource>::try_new(true).unwrap(),
+                )
+                .unwrap()
+                .into(),
+                CompletionQuestion::try_new(
+                    1.into(),
+                    "1. Completion".into(),
+                    CompletionAnswer::<StandardSource>::try_new("answer").unwrap(),
+                )
+                .unwrap()
+                .into(),
+                TrueFalseQuestion::try_new(
+                    2.into(),
+                    "2. True-false".into(),
+                    TrueFalseAnswer::<StandardSource>::try_new(false).unwrap(),
+                )
+                .unwrap()
+                .with_points(Some(4))
+                .into(),
+            ],
+        )
+        .with_blueprint(blueprint);
+
+        let submission = Submission::new(
+            vec![
+                TrueFalseAnswer::<SubmissionSource>::try_new(false)
+                    .unwrap()
+                    .into(),
+                CompletionAnswer::<SubmissionSource>::try_new("answer")
+                    .unwrap()
+                    .into(),
+                TrueFalseAnswer::<SubmissionSource>::try_new(false)
+                    .unwrap()
+                    .into(),
+            ]
+            .into_iter()
+            .enumerate()
+            .map(|(i, a)| (i.into(), a))
+            .collect(),
+        );
+
+        let items = test.grade_items(&submission);
+        assert_eq!(
+            items.iter().map(|i| i.points).collect::<Vec<_>>(),
+            vec![1, 3, 4],
+        );
+        assert_eq!(Test::points(&items), Points::new(7.0, 8));
+        assert_eq!(test.grade(&submission), Score::try_new(87.5).unwrap());
+        assert_eq!(test.evaluate(&items), Some(Grade::new("B", true)));
+    }
+
+    #[test]
+    fn test_grade_penalty() {
+        let blueprint = Blueprint {
+            penalty: Penalty {
+                single_selection: 0.25,
+                true_false: 1.0,
+                ..Penalty::NONE
+            },
+            ..Blueprint::default()
+        };
+        let test = Test::new(
+            0.into(),
+            vec![
+                SingleSelectionQuestion::try_new(
+                    0.into(),
+                    "0. Single-selection".into(),
+                    vec![
+                        "option a".into(),
+                        "option b".into(),
+                        "option c".into(),
+                        "option d".into(),
+                    ],
+                    SingleSelectionAnswer::<StandardSource>::try_new(0).unwrap(),
+                )
+                .unwrap()
+                .with_points(Some(4))
+                .into(),
+                TrueFalseQuestion::try_new(
+                    1.into(),
+                    "1. True-false".into(),
+                    TrueFalseAnswer::<StandardSource>::try_new(true).unwrap(),
+                )
+                .unwrap()
+                .into(),
+                TrueFalseQuestion::try_new(
+                    2.into(),
+                    "2. True-false".into(),
+                    TrueFalseAnswer::<StandardSource>::try_new(true).unwrap(),
+                )
+                .unwrap()
+                .into(),
+                TrueFalseQuestion::try_new(
+                    3.into(),
+                    "3. True-false".into(),
+                    TrueFalseAnswer::<StandardSource>::try_new(true).unwrap(),
+                )
+                .unwrap()
+                .into(),
+            ],
+        )
+        .with_blueprint(blueprint);
+
+        // The last question is left unanswered.
+        let submission = Submission::new(
+            vec![
+                SingleSelectionAnswer::<SubmissionSource>::try_new(1)
+                    .unwrap()
+                    .into(),
+                TrueFalseAnswer::<SubmissionSource>::try_new(true)
+                    .unwrap()
+                    .into(),
+                TrueFalseAnswer::<SubmissionSource>::try_new(false)
+                    .unwrap()
+                    .into(),
+            ]
+            .into_iter()
+            .enumerate()
+            .map(|(i, a)| (i.into(), a))
+            .collect(),
+        );
+
+        let items = test.grade_items(&submission);
+        assert_eq!(
+            items.iter().map(|i| i.penalty).collect::<Vec<_>>(),
+            vec![1.0, 0.0, 1.0, 0.0],
+        );
+        assert_eq!(Test::points(&items), Points::new(1.0, 7).with_penalty(2.0));
+        assert_eq!(test.grade(&submission), Score::try_new(0f32).unwrap());
+    }
+
+    #[test]
+    fn test_grade_penalty_partial() {
+        let blueprint = Blueprint {
+            penalty: Penalty {
+                ordering: 0.6,
+                ..Penalty::NONE
+            },
+            ..Blueprint::default()
+        };
+        let ordering = |id: usize| -> Question {
+            OrderingQuestion::try_new(
+                id.into(),
+                "Ordering".into(),
+                vec!["item a".into(), "item b".into(), "item c".into()],
+                OrderingAnswer::<StandardSource>::try_new(vec![0, 1, 2]).unwrap(),
+            )
+            .unwrap()
+            .into()
+        };
+        let test = Test::new(0.into(), vec![ordering(0), ordering(1), ordering(2)])
+            .with_blueprint(blueprint);
+
+        // Partly correct answers lose only the share of the credit they miss, and answers of
+        // another kind are no guesses at the question.
+        let submission = Submission::new(
+            vec![
+                OrderingAnswer::<SubmissionSource>::try_new(vec![0, 1, 2])
+                    .unwrap()
+                    .into(),
+                OrderingAnswer::<SubmissionSource>::try_new(vec![0, 2, 1])
+                    .unwrap()
+                    .into(),
+                TrueFalseAnswer::<SubmissionSource>::try_new(true)
+                    .unwrap()
+                    .into(),
+            ]
+            .into_iter()
+            .enumerate()
+            .map(|(i, a)| (i.into(), a))
+            .collect(),
+        );
+
+        let items = test.grade_items(&submission);
+        assert_eq!(
+            items.iter().map(|i| i.penalty).collect::<Vec<_>>(),
+            vec![0.0, 0.6 * (1.0 - 2.0 / 3.0), 0.0],
+        );
+    }
+
+    #[test]
+    fn test_grade_empty_submission() {
+        let blueprint = Blueprint {
+            penalty: Penalty {
+                multiple_selection: 0.5,
+                ..Penalty::NONE
+            },
+            ..Blueprint::default()
+        };
+        let test = Test::new(
+            0.into(),
+            vec![MultipleSelectionQuestion::try_new(
+                0.into(),
+                "0. Multiple-selection".into(),
+                vec![
+                    "option a".into(),
+                    "option b".into(),
+                    "option c".into(),
+                    "option d".into(),
+                ],
+                MultipleSelectionAnswer::<StandardSource>::try_new(vec![2, 3]).unwrap(),
+            )
+            .unwrap()
+            .into()],
+        )
+        .with_blueprint(blueprint);
+
+        // Nothing selected counts as unanswered, like a question left out entirely.
+        let submission = Submission::new(
+            [(
+                0.into(),
+                MultipleSelectionAnswer::<SubmissionSource>::try_new(vec![])
+                    .unwrap()
+                    .into(),
+            )]
+            .into_iter()
+            .collect(),
+        );
+        let items = test.grade_items(&submission);
+        assert!(!items[0].correct);
+        assert_eq!(items[0].penalty, 0.0);
+        assert_eq!(Test::points(&items), Points::new(0.0, 1));
+
+        let items = test.grade_items(&Submission::new(HashMap::new()));
+        assert_eq!(items[0].penalty, 0.0);
+    }
+
+    #[test]
+    fn test_grade_essay_pending() {
+        let test = Test::new(
+            0.into(),
+            vec![
+                TrueFalseQuestion::try_new(
+                    0.into(),
+                    "0. True-false".into(),
+                    TrueFalseAnswer::<StandardSource>::try_new(true).unwrap(),
+                )
+                .unwrap()
+                .into(),
+                EssayQuestion::try_new(
+                    1.into(),
+                    "1. Essay".into(),
+                    EssayAnswer::<StandardSource>::try_new("").unwrap(),
+                )
+                .unwrap()
+                .into(),
+            ],
+        );
+
+        let submission = Submission::new(
+            vec![
+                TrueFalseAnswer::<SubmissionSource>::try_new(true)
+                    .unwrap()
+                    .into(),
+                EssayAnswer::<SubmissionSource>::try_new("essay")
+                    .unwrap()
+                    .into(),
+            ]
+            .into_iter()
+            .enumerate()
+            .map(|(i, a)| (i.into(), a))
+            .collect(),
+        );
+
+        let items = test.grade_items(&submission);
+        assert_eq!(
+            items.iter().map(|i| i.pending).collect::<Vec<_>>(),
+            vec![false, true],
+        );
+        assert_eq!(items[0].response, None);
+        assert_eq!(items[1].response.as_deref(), Some("essay"));
+        assert_eq!(items[1].credit, 0.0);
+        assert_eq!(test.grade(&submission), Score::try_new(50f32).unwrap());
+        assert_eq!(test.evaluate(&items), None);
+    }
+
+    #[test]
+    fn test_feedback() {
+        let test = Test::new(
+            0.into(),
+            vec![
+                TrueFalseQuestion::try_new(
+                    0.into(),
+                    "0. True-false".into(),
+                    TrueFalseAnswer::<StandardSource>::try_new(true).unwrap(),
+                )
+                .unwrap()
+                .with_explanation(Some("Because it is".into()))
+                .into(),
+                CompletionQuestion::try_new(
+                    1.into(),
+                    "1. Completion".into(),
+                    CompletionAnswer::<StandardSource>::try_new("answer").unwrap(),
+                )
+                .unwrap()
+                .into(),
+            ],
+        );
+
+        let submission = Submission::new(
+            vec![TrueFalseAnswer::<SubmissionSource>::try_new(false)
+                .unwrap()
+                .into()]
+            .into_iter()
+            .enumerate()
+            .map(|(i, a)| (i.into(), a))
+            .collect(),
+        );
+
+        let feedback = test.feedback(&test.grade_items(&submission));
+        assert_eq!(
+            feedback,
+            vec![
+                ItemFeedback {
+                    question: 0.into(),
+                    correct: false,
+                    pending: false,
+                    penalty: 0.0,
+                    explanation: Some("Because it is".into()),
+                },
+                ItemFeedback {
+                    question: 1.into(),
+                    correct: false,
+                    pending: false,
+                    penalty: 0.0,
+                    explanation: None,
+                },
+            ]
+        );
     }
 }
@@ -1,4 +1,5 @@
 pub mod application;
 pub mod entity;
 pub mod repository;
+pub mod service;
 pub mod session;
@@ -0,0 +1,42 @@
+use std::error::Error;
+use std::fmt::Debug;
+
+use async_trait::async_trait;
+use snafu::prelude::*;
+
+use crate::domain::entity::user::User;
+
+// Named groups of users, such as classes, used to filter exported scores. A group exists as
+// long as it has members.
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait GroupRepository: Debug + Send + Sync + 'static {
+    // Users already in the group are skipped.
+    async fn add_members(&self, group: &str, users: Vec<User>) -> Result<(), GroupRepositoryError>;
+
+    // Returns how many of the users were members.
+    async fn remove_members(
+        &self,
+        group: &str,
+        users: Vec<User>,
+    ) -> Result<usize, GroupRepositoryError>;
+
+    async fn list_members(&self, group: &str) -> Result<Vec<User>, GroupRepositoryError>;
+
+    // Every group with its member count, ordered by name.
+    async fn list_groups(&self) -> Result<Vec<(String, usize)>, GroupRepositoryError>;
+}
+
+#[derive(Debug, Snafu)]
+#[non_exhaustive]
+#[snafu(visibility(pub(crate)))]
+pub enum GroupRepositoryError {
+    #[snafu(display("Could not find group {group}"))]
+    NotFound { group: String },
+    #[snafu(whatever, display("Unknown error: {message}"))]
+    Unknown {
+        message: String,
+        #[snafu(source(from(Box<dyn Error + Send>, Some)))]
+        source: Option<Box<dyn Error + Send>>,
+    },
+}
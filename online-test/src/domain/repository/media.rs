@@ -0,0 +1,65 @@
+use std::error::Error;
+use std::fmt::Debug;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use snafu::prelude::*;
+
+use crate::domain::entity::id::Id;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait MediaRepository: Debug + Send + Sync + 'static {
+    async fn insert_media(
+        &self,
+        question: Id,
+        file_name: String,
+        data: Vec<u8>,
+    ) -> Result<Id, MediaRepositoryError>;
+
+    async fn list_media(&self, question: Id) -> Result<Vec<Media>, MediaRepositoryError>;
+
+    async fn find_media(&self, id: Id) -> Result<Media, MediaRepositoryError>;
+
+    async fn read_media(&self, id: Id) -> Result<Vec<u8>, MediaRepositoryError>;
+
+    async fn remove_media(&self, id: Id) -> Result<(), MediaRepositoryError>;
+}
+
+#[derive(Debug, Snafu)]
+#[non_exhaustive]
+#[snafu(visibility(pub(crate)))]
+pub enum MediaRepositoryError {
+    #[snafu(display("Could not find media with ID {id}"))]
+    NotFound { id: Id },
+    #[snafu(display("Could not find question with ID {id}"))]
+    QuestionNotFound { id: Id },
+    #[snafu(display("Media must have a non-empty file name"))]
+    FileNameEmpty,
+    #[snafu(whatever, display("Unknown error: {message}"))]
+    Unknown {
+        message: String,
+        #[snafu(source(from(Box<dyn Error + Send>, Some)))]
+        source: Option<Box<dyn Error + Send>>,
+    },
+}
+
+// Content references media by ID through URLs like `media:42`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Media {
+    pub id: Id,
+    pub question: Id,
+    pub file_name: String,
+    pub mime_type: String,
+    pub size: usize,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Media {
+    pub const SCHEME: &'static str = "media";
+
+    // Only images are shown inline; other files are offered as downloads.
+    pub fn inline(&self) -> bool {
+        self.mime_type.starts_with("image/")
+    }
+}
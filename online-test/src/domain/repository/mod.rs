@@ -1,2 +1,6 @@
+pub mod group;
+pub mod media;
+pub mod practice;
 pub mod question;
+pub mod review;
 pub mod score;
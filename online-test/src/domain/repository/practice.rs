@@ -0,0 +1,40 @@
+use std::error::Error;
+use std::fmt::Debug;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use snafu::prelude::*;
+
+use crate::domain::entity::id::Id;
+use crate::domain::entity::user::User;
+
+// Practice attempts are kept apart from `scores`, so they never count towards exam results.
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait PracticeRepository: Debug + Send + Sync + 'static {
+    async fn insert_attempt(
+        &self,
+        user: User,
+        attempt: PracticeAttempt,
+    ) -> Result<(), PracticeRepositoryError>;
+}
+
+#[derive(Debug, Snafu)]
+#[non_exhaustive]
+#[snafu(visibility(pub(crate)))]
+pub enum PracticeRepositoryError {
+    #[snafu(whatever, display("Unknown error: {message}"))]
+    Unknown {
+        message: String,
+        #[snafu(source(from(Box<dyn Error + Send>, Some)))]
+        source: Option<Box<dyn Error + Send>>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PracticeAttempt {
+    pub question: Id,
+    pub revision: Id,
+    pub correct: bool,
+    pub answered_at: DateTime<Utc>,
+}
@@ -2,41 +2,39 @@ use std::error::Error;
 use std::fmt::Debug;
 
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use snafu::prelude::*;
 
 use crate::domain::entity::answer::TryNewAnswerError;
+use crate::domain::entity::content::{ContentFormat, TryNewContentError};
 use crate::domain::entity::id::Id;
-use crate::domain::entity::question::{Question, TryNewQuestionError};
+use crate::domain::entity::question::{Question, QuestionKind, TryNewQuestionError};
 
 #[cfg_attr(test, mockall::automock)]
 #[async_trait]
 pub trait QuestionRepository: Debug + Send + Sync + 'static {
-    async fn insert_single_selection(
+    async fn insert_question(
         &self,
-        content: String,
-        options: Vec<String>,
-        answer: u32,
-    ) -> Result<(), QuestionRepositoryError>;
+        draft: QuestionDraft,
+        author: String,
+    ) -> Result<Id, QuestionRepositoryError>;
 
-    async fn insert_multiple_selection(
+    async fn update_question(
         &self,
-        content: String,
-        options: Vec<String>,
-        answer: Vec<u32>,
+        id: Id,
+        draft: QuestionDraft,
+        author: String,
     ) -> Result<(), QuestionRepositoryError>;
 
-    async fn insert_completion(
-        &self,
-        content: String,
-        answer: String,
-    ) -> Result<(), QuestionRepositoryError>;
+    // Retires every question or none of them, failing if any is missing or already retired.
+    async fn remove_questions(&self, ids: Vec<Id>) -> Result<(), QuestionRepositoryError>;
 
-    async fn remove_question(&self, id: Id) -> Result<(), QuestionRepositoryError>;
+    async fn set_tags(&self, id: Id, tags: Vec<String>) -> Result<(), QuestionRepositoryError>;
 
     async fn list_questions(
         &self,
-        limits: usize,
-    ) -> Result<Vec<Vec<String>>, QuestionRepositoryError>;
+        filter: ListFilter,
+    ) -> Result<Vec<QuestionSummary>, QuestionRepositoryError>;
 
     async fn select_questions(
         &self,
@@ -47,6 +45,23 @@ pub trait QuestionRepository: Debug + Send + Sync + 'static {
         &self,
         id: Vec<Id>,
     ) -> Result<Vec<Question>, QuestionRepositoryError>;
+
+    // Questions still in use among the IDs, in the same order. Retired and missing ones are left
+    // out instead of failing.
+    async fn select_active_questions_by_id(
+        &self,
+        id: Vec<Id>,
+    ) -> Result<Vec<Question>, QuestionRepositoryError>;
+
+    async fn select_questions_by_revision(
+        &self,
+        revision: Vec<Id>,
+    ) -> Result<Vec<Question>, QuestionRepositoryError>;
+
+    async fn list_revisions(
+        &self,
+        id: Id,
+    ) -> Result<Vec<QuestionRevision>, QuestionRepositoryError>;
 }
 
 #[derive(Debug, Snafu)]
@@ -57,6 +72,8 @@ pub enum QuestionRepositoryError {
     AnswerInvalid { source: TryNewAnswerError },
     #[snafu(display("Could not create valid question"))]
     QuestionInvalid { source: TryNewQuestionError },
+    #[snafu(display("Could not create valid content"))]
+    ContentInvalid { source: TryNewContentError },
     #[snafu(display("Could not select {expected} {which}(s) from {total}"))]
     Insufficient {
         which: String,
@@ -65,6 +82,8 @@ pub enum QuestionRepositoryError {
     },
     #[snafu(display("Could not find question with ID {id}"))]
     NotFound { id: Id },
+    #[snafu(display("Could not find question revision with ID {id}"))]
+    RevisionNotFound { id: Id },
     #[snafu(whatever, display("Unknown error: {message}"))]
     Unknown {
         message: String,
@@ -78,4 +97,150 @@ pub struct SelectCount {
     pub single_selection: usize,
     pub multiple_selection: usize,
     pub completion: usize,
+    pub true_false: usize,
+    pub ordering: usize,
+    pub matching: usize,
+    pub essay: usize,
+    pub cloze: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListFilter {
+    pub kind: Option<QuestionKind>,
+    pub tag: Option<String>,
+    pub search: Option<String>,
+    pub limit: usize,
+    pub offset: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuestionSummary {
+    pub question: Question,
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QuestionDraft {
+    SingleSelection {
+        format: ContentFormat,
+        content: String,
+        options: Vec<String>,
+        answer: u32,
+        explanation: Option<String>,
+        points: Option<u32>,
+    },
+    MultipleSelection {
+        format: ContentFormat,
+        content: String,
+        options: Vec<String>,
+        answer: Vec<u32>,
+        explanation: Option<String>,
+        points: Option<u32>,
+    },
+    Completion {
+        format: ContentFormat,
+        content: String,
+        answer: String,
+        explanation: Option<String>,
+        points: Option<u32>,
+    },
+    TrueFalse {
+        format: ContentFormat,
+        content: String,
+        answer: bool,
+        explanation: Option<String>,
+        points: Option<u32>,
+    },
+    Ordering {
+        format: ContentFormat,
+        content: String,
+        items: Vec<String>,
+        answer: Vec<u32>,
+        explanation: Option<String>,
+        points: Option<u32>,
+    },
+    Matching {
+        format: ContentFormat,
+        content: String,
+        items: Vec<String>,
+        targets: Vec<String>,
+        answer: Vec<u32>,
+        explanation: Option<String>,
+        points: Option<u32>,
+    },
+    Essay {
+        format: ContentFormat,
+        content: String,
+        answer: String,
+        explanation: Option<String>,
+        points: Option<u32>,
+    },
+    Cloze {
+        format: ContentFormat,
+        content: String,
+        answer: Vec<Vec<String>>,
+        explanation: Option<String>,
+        points: Option<u32>,
+    },
+}
+
+impl QuestionDraft {
+    pub fn kind(&self) -> QuestionKind {
+        match self {
+            Self::SingleSelection { .. } => QuestionKind::SingleSelection,
+            Self::MultipleSelection { .. } => QuestionKind::MultipleSelection,
+            Self::Completion { .. } => QuestionKind::Completion,
+            Self::TrueFalse { .. } => QuestionKind::TrueFalse,
+            Self::Ordering { .. } => QuestionKind::Ordering,
+            Self::Matching { .. } => QuestionKind::Matching,
+            Self::Essay { .. } => QuestionKind::Essay,
+            Self::Cloze { .. } => QuestionKind::Cloze,
+        }
+    }
+
+    pub fn format(&self) -> ContentFormat {
+        match self {
+            Self::SingleSelection { format, .. }
+            | Self::MultipleSelection { format, .. }
+            | Self::Completion { format, .. }
+            | Self::TrueFalse { format, .. }
+            | Self::Ordering { format, .. }
+            | Self::Matching { format, .. }
+            | Self::Essay { format, .. }
+            | Self::Cloze { format, .. } => *format,
+        }
+    }
+
+    pub fn explanation(&self) -> Option<&str> {
+        match self {
+            Self::SingleSelection { explanation, .. }
+            | Self::MultipleSelection { explanation, .. }
+            | Self::Completion { explanation, .. }
+            | Self::TrueFalse { explanation, .. }
+            | Self::Ordering { explanation, .. }
+            | Self::Matching { explanation, .. }
+            | Self::Essay { explanation, .. }
+            | Self::Cloze { explanation, .. } => explanation.as_deref(),
+        }
+    }
+
+    pub fn points(&self) -> Option<u32> {
+        match self {
+            Self::SingleSelection { points, .. }
+            | Self::MultipleSelection { points, .. }
+            | Self::Completion { points, .. }
+            | Self::TrueFalse { points, .. }
+            | Self::Ordering { points, .. }
+            | Self::Matching { points, .. }
+            | Self::Essay { points, .. }
+            | Self::Cloze { points, .. } => *points,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuestionRevision {
+    pub question: Question,
+    pub author: String,
+    pub created_at: DateTime<Utc>,
 }
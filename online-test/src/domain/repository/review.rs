@@ -0,0 +1,51 @@
+use std::error::Error;
+use std::fmt::Debug;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use snafu::prelude::*;
+
+use crate::domain::entity::id::Id;
+use crate::domain::entity::review::ReviewCard;
+use crate::domain::entity::user::User;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait ReviewRepository: Debug + Send + Sync + 'static {
+    // Cards of the user among the questions, in no particular order.
+    async fn find_cards(
+        &self,
+        user: &User,
+        questions: Vec<Id>,
+    ) -> Result<Vec<ReviewCard>, ReviewRepositoryError>;
+
+    // Inserts new cards and replaces existing ones.
+    async fn save_cards(
+        &self,
+        user: User,
+        cards: Vec<ReviewCard>,
+    ) -> Result<(), ReviewRepositoryError>;
+
+    // Drops the cards of the user whose questions are retired or missing.
+    async fn remove_stale_cards(&self, user: &User) -> Result<(), ReviewRepositoryError>;
+
+    // Questions missed at least once and due for review at `now`, most overdue first. Retired
+    // questions are left out.
+    async fn list_due(
+        &self,
+        user: &User,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<Id>, ReviewRepositoryError>;
+}
+
+#[derive(Debug, Snafu)]
+#[non_exhaustive]
+#[snafu(visibility(pub(crate)))]
+pub enum ReviewRepositoryError {
+    #[snafu(whatever, display("Unknown error: {message}"))]
+    Unknown {
+        message: String,
+        #[snafu(source(from(Box<dyn Error + Send>, Some)))]
+        source: Option<Box<dyn Error + Send>>,
+    },
+}
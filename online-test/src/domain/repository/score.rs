@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::error::Error;
 use std::fmt::Debug;
 
@@ -6,25 +7,78 @@ use chrono::{DateTime, Utc};
 use snafu::prelude::*;
 use tokio::time::Duration;
 
-use crate::domain::entity::score::Score;
+use crate::domain::entity::certificate::{Certificate, VerificationCode};
+use crate::domain::entity::grade::Grade;
+use crate::domain::entity::id::Id;
+use crate::domain::entity::score::{Points, Score};
+use crate::domain::entity::test::ItemResult;
 use crate::domain::entity::user::User;
 
 #[cfg_attr(test, mockall::automock)]
 #[async_trait]
 pub trait ScoreRepository: Debug + Send + Sync + 'static {
+    #[allow(clippy::too_many_arguments)]
     async fn insert(
         &self,
         user: User,
-        score: Score,
+        kind: AttemptKind,
+        points: Points,
+        grade: Option<Grade>,
         end_time: DateTime<Utc>,
         duration: Duration,
+        items: Vec<ItemResult>,
     ) -> Result<(), ScoreRepositoryError>;
 
     async fn query_all_sorted(&self, user: &User) -> Result<Vec<Record>, ScoreRepositoryError>;
 
+    // Attempts still pending are left out, as their scores may yet change, and so are adaptive
+    // attempts.
     async fn query_best(&self, user: &User) -> Result<Record, ScoreRepositoryError>;
 
     async fn query_latest(&self, user: &User) -> Result<Record, ScoreRepositoryError>;
+
+    async fn list_pending(&self) -> Result<Vec<PendingItem>, ScoreRepositoryError>;
+
+    // Grades a pending item and returns the ID of the score it belongs to.
+    async fn grade_item(&self, id: Id, grade: ItemGrade) -> Result<Id, ScoreRepositoryError>;
+
+    async fn query_items(&self, score: Id) -> Result<Vec<ItemResult>, ScoreRepositoryError>;
+
+    // Replaces a pending score with its final points and grade.
+    async fn finalize(
+        &self,
+        id: Id,
+        points: Points,
+        grade: Grade,
+    ) -> Result<(), ScoreRepositoryError>;
+
+    // Attempts matching the filter along with their users, oldest first.
+    async fn list_records(
+        &self,
+        filter: RecordFilter,
+    ) -> Result<Vec<(User, Record)>, ScoreRepositoryError>;
+
+    // Grades of every finished test attempt, oldest first.
+    async fn list_grades(&self) -> Result<Vec<GradeRecord>, ScoreRepositoryError>;
+
+    // Issues `code` for the best passed test attempt of the user, unless that attempt already
+    // has a certificate, which is returned instead.
+    async fn issue_certificate(
+        &self,
+        user: &User,
+        code: VerificationCode,
+    ) -> Result<Certificate, ScoreRepositoryError>;
+
+    async fn find_certificate(
+        &self,
+        code: &VerificationCode,
+    ) -> Result<Certificate, ScoreRepositoryError>;
+
+    // Counts graded attempts at every revision of the questions, keyed by question ID.
+    async fn query_item_statistics(
+        &self,
+        questions: Vec<Id>,
+    ) -> Result<HashMap<Id, ItemStatistics>, ScoreRepositoryError>;
 }
 
 #[derive(Debug, Snafu)]
@@ -33,6 +87,12 @@ pub trait ScoreRepository: Debug + Send + Sync + 'static {
 pub enum ScoreRepositoryError {
     #[snafu(display("Could not find username {}", user.inner()))]
     NotFound { user: User },
+    #[snafu(display("Could not find pending item with ID {id}"))]
+    PendingItemNotFound { id: Id },
+    #[snafu(display("User {} has not passed any test", user.inner()))]
+    NotPassed { user: User },
+    #[snafu(display("Could not find certificate {code}"))]
+    CertificateNotFound { code: VerificationCode },
     #[snafu(whatever, display("Unknown error: {message}"))]
     Unknown {
         message: String,
@@ -41,9 +101,61 @@ pub enum ScoreRepositoryError {
     },
 }
 
+// Adaptive attempts are kept for their feedback, but never count towards grades or
+// certificates, as each one draws its own questions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttemptKind {
+    Test,
+    Adaptive,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct Record {
     pub score: Score,
+    pub points: Points,
+    // `None` until every item of the attempt is graded.
+    pub grade: Option<Grade>,
     pub end_time: DateTime<Utc>,
     pub duration: Duration,
+    pub pending: bool,
+}
+
+// Attempts ending within [since, until) by any of `users`. `None` leaves a bound open.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RecordFilter {
+    pub users: Option<Vec<User>>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GradeRecord {
+    pub user: User,
+    pub grade: Grade,
+    pub end_time: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PendingItem {
+    pub id: Id,
+    pub score: Id,
+    pub revision: Id,
+    pub points: u32,
+    pub user: User,
+    pub response: String,
+    pub end_time: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ItemGrade {
+    pub credit: f32,
+    pub comment: Option<String>,
+    pub grader: String,
+    pub graded_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ItemStatistics {
+    pub attempts: usize,
+    pub correct: usize,
 }
@@ -0,0 +1,195 @@
+use pdf_writer::{Content, Finish, Name, Pdf, Rect, Ref, Str, TextStr};
+
+use crate::domain::entity::certificate::Certificate;
+
+// A4 in landscape, measured in points.
+const PAGE_WIDTH: f32 = 842.0;
+const PAGE_HEIGHT: f32 = 595.0;
+
+const SANS: Name = Name(b"F1");
+const SANS_BOLD: Name = Name(b"F2");
+const MONO: Name = Name(b"F3");
+
+// Advance widths of the printable ASCII characters in Helvetica, in thousandths of the font size.
+// Usernames, grades and codes are ASCII only, so the bold face is measured with them too.
+const HELVETICA_WIDTHS: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 222, 333, 333, 389, 584, 278, 333, 278, 278, 556, 556, 556,
+    556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556, 1015, 667, 667, 722, 722, 667,
+    611, 778, 722, 278, 500, 667, 556, 833, 722, 778, 667, 778, 722, 667, 611, 722, 667, 944, 667,
+    667, 611, 278, 278, 278, 469, 556, 222, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500,
+    222, 833, 556, 556, 556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584,
+];
+const COURIER_WIDTH: u16 = 600;
+
+// Renders a single page certificate using only the standard PDF fonts, so that nothing has to
+// be embedded.
+pub fn render(certificate: &Certificate) -> Vec<u8> {
+    let catalog_id = Ref::new(1);
+    let page_tree_id = Ref::new(2);
+    let page_id = Ref::new(3);
+    let content_id = Ref::new(4);
+    let info_id = Ref::new(5);
+    let font_ids = [Ref::new(6), Ref::new(7), Ref::new(8)];
+
+    let mut pdf = Pdf::new();
+    pdf.catalog(catalog_id).pages(page_tree_id);
+    pdf.pages(page_tree_id).kids([page_id]).count(1);
+
+    let mut page = pdf.page(page_id);
+    page.media_box(Rect::new(0.0, 0.0, PAGE_WIDTH, PAGE_HEIGHT));
+    page.parent(page_tree_id);
+    page.contents(content_id);
+    page.resources()
+        .fonts()
+        .pair(SANS, font_ids[0])
+        .pair(SANS_BOLD, font_ids[1])
+        .pair(MONO, font_ids[2]);
+    page.finish();
+
+    for (id, font) in font_ids
+        .into_iter()
+        .zip([&b"Helvetica"[..], b"Helvetica-Bold", b"Courier"])
+    {
+        pdf.type1_font(id).base_font(Name(font));
+    }
+
+    let title = format!("Certificate of {}", certificate.user);
+    pdf.document_info(info_id)
+        .title(TextStr(&title))
+        .creator(TextStr("online-test"));
+
+    let mut content = Content::new();
+    content.set_stroke_rgb(0.2, 0.3, 0.5);
+    content.set_line_width(3.0);
+    content.rect(30.0, 30.0, PAGE_WIDTH - 60.0, PAGE_HEIGHT - 60.0);
+    content.stroke();
+    content.set_line_width(1.0);
+    content.rect(40.0, 40.0, PAGE_WIDTH - 80.0, PAGE_HEIGHT - 80.0);
+    content.stroke();
+
+    let points = &certificate.points;
+    let lines = [
+        (
+            SANS_BOLD,
+            34.0,
+            470.0,
+            "Certificate of Completion".to_owned(),
+        ),
+        (SANS, 16.0, 410.0, "This certifies that".to_owned()),
+        (SANS_BOLD, 28.0, 365.0, certificate.user.to_string()),
+        (
+            SANS,
+            16.0,
+            320.0,
+            format!(
+                "has passed the online test with a score of {:.1}%, grade {}.",
+                certificate.score.inner(),
+                certificate.grade.letter()
+            ),
+        ),
+        (
+            SANS,
+            12.0,
+            295.0,
+            format!(
+                "{} of {} points, completed on {}",
+                format_points(points.net()),
+                points.total(),
+                certificate.end_time.format("%Y-%m-%d")
+            ),
+        ),
+        (
+            MONO,
+            14.0,
+            120.0,
+            format!("Verification code: {}", certificate.code),
+        ),
+        (
+            SANS,
+            10.0,
+            100.0,
+            format!(
+                "Verify with `online-test cli verify-certificate {}`",
+                certificate.code
+            ),
+        ),
+    ];
+
+    content.set_fill_rgb(0.1, 0.1, 0.1);
+    for (font, size, y, text) in lines {
+        let x = (PAGE_WIDTH - text_width(font, size, &text)) / 2.0;
+        content.begin_text();
+        content.set_font(font, size);
+        content.next_line(x, y);
+        content.show(Str(text.as_bytes()));
+        content.end_text();
+    }
+
+    pdf.stream(content_id, &content.finish());
+    pdf.finish()
+}
+
+fn text_width(font: Name, size: f32, text: &str) -> f32 {
+    let units: u32 = text
+        .bytes()
+        .map(|c| {
+            if font == MONO {
+                COURIER_WIDTH
+            } else {
+                match c {
+                    b' '..=b'~' => HELVETICA_WIDTHS[(c - b' ') as usize],
+                    _ => HELVETICA_WIDTHS[0],
+                }
+            }
+        })
+        .map(u32::from)
+        .sum();
+
+    units as f32 * size / 1000.0
+}
+
+fn format_points(points: f32) -> String {
+    if points.fract() == 0.0 {
+        format!("{points:.0}")
+    } else {
+        format!("{points:.2}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::DateTime;
+
+    use crate::domain::entity::certificate::VerificationCode;
+    use crate::domain::entity::grade::Grade;
+    use crate::domain::entity::score::{Points, Score};
+    use crate::domain::entity::user::User;
+
+    use super::*;
+
+    #[test]
+    fn certificate_render() {
+        let certificate = Certificate {
+            code: VerificationCode::try_new("7KQF-2M9X-HC4T").unwrap(),
+            user: User::try_new("oo-infty").unwrap(),
+            score: Score::try_new(87.5).unwrap(),
+            points: Points::new(42.0, 48),
+            grade: Grade::new("B", true),
+            end_time: DateTime::from_timestamp_nanos(0),
+        };
+
+        let pdf = render(&certificate);
+        let text = String::from_utf8_lossy(&pdf);
+        assert!(pdf.starts_with(b"%PDF-"));
+        assert!(text.contains("(oo-infty)"));
+        assert!(text.contains("score of 87.5%, grade B."));
+        assert!(text.contains("42 of 48 points, completed on 1970-01-01"));
+        assert!(text.contains("7KQF-2M9X-HC4T"));
+    }
+
+    #[test]
+    fn text_width_by_font() {
+        assert_eq!(text_width(SANS, 10.0, "Hi"), 9.44);
+        assert_eq!(text_width(MONO, 10.0, "Hi"), 12.0);
+    }
+}
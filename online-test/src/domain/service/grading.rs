@@ -0,0 +1,292 @@
+use std::sync::Arc;
+
+use chrono::Utc;
+use snafu::prelude::*;
+
+use crate::domain::entity::grade::GradeScale;
+use crate::domain::entity::id::Id;
+use crate::domain::entity::question::Question;
+use crate::domain::entity::score::Score;
+use crate::domain::entity::test::Test;
+use crate::domain::repository::question::{QuestionRepository, QuestionRepositoryError};
+use crate::domain::repository::score::{
+    ItemGrade, PendingItem, ScoreRepository, ScoreRepositoryError,
+};
+
+#[derive(Debug)]
+pub struct GradingService {
+    question_repository: Arc<dyn QuestionRepository>,
+    score_repository: Arc<dyn ScoreRepository>,
+    scale: GradeScale,
+}
+
+impl GradingService {
+    pub fn new(
+        question_repository: Arc<dyn QuestionRepository>,
+        score_repository: Arc<dyn ScoreRepository>,
+        scale: GradeScale,
+    ) -> Self {
+        Self {
+            question_repository,
+            score_repository,
+            scale,
+        }
+    }
+
+    // Pending items in submission order, along with the question revisions they answer.
+    pub async fn pending(&self) -> Result<Vec<PendingReview>, GradingError> {
+        let items = self
+            .score_repository
+            .list_pending()
+            .await
+            .context(LoadItemsSnafu)?;
+
+        let questions = self
+            .question_repository
+            .select_questions_by_revision(items.iter().map(|i| i.revision).collect())
+            .await
+            .context(LoadQuestionsSnafu)?;
+
+        Ok(items
+            .into_iter()
+            .zip(questions)
+            .map(|(item, question)| PendingReview { item, question })
+            .collect())
+    }
+
+    // Awards `points` within [0, the points the item is worth] to a pending item. Once no item of
+    // the attempt is pending any more, its score is recomputed and graded, and the score is
+    // returned.
+    pub async fn grade(
+        &self,
+        item: Id,
+        points: f32,
+        comment: Option<String>,
+        grader: String,
+    ) -> Result<Option<Score>, GradingError> {
+        let max = self
+            .score_repository
+            .list_pending()
+            .await
+            .context(LoadItemsSnafu)?
+            .into_iter()
+            .find(|i| i.id == item)
+            .context(PendingItemNotFoundSnafu { id: item })?
+            .points;
+        ensure!(
+            (0.0..=max as f32).contains(&points),
+            PointsInvalidSnafu { points, max }
+        );
+
+        let grade = ItemGrade {
+            credit: points / max as f32,
+            comment: comment.filter(|c| !c.trim().is_empty()),
+            grader,
+            graded_at: Utc::now(),
+        };
+
+        let score_id = self
+            .score_repository
+            .grade_item(item, grade)
+            .await
+            .context(SaveGradeSnafu)?;
+
+        let items = self
+            .score_repository
+            .query_items(score_id)
+            .await
+            .context(LoadItemsSnafu)?;
+
+        if items.iter().any(|i| i.pending) {
+            return Ok(None);
+        }
+
+        let points = Test::points(&items);
+        let grade = self.scale.grade(points.score());
+        self.score_repository
+            .finalize(score_id, points, grade)
+            .await
+            .context(SaveGradeSnafu)?;
+
+        Ok(Some(points.score()))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PendingReview {
+    pub item: PendingItem,
+    pub question: Question,
+}
+
+#[derive(Debug, Snafu)]
+#[non_exhaustive]
+pub enum GradingError {
+    #[snafu(display("Points {points} are not within [0, {max}]"))]
+    PointsInvalid { points: f32, max: u32 },
+    #[snafu(display("Pending item {id} not found"))]
+    PendingItemNotFound { id: Id },
+    #[snafu(display("Could not load items"))]
+    LoadItems { source: ScoreRepositoryError },
+    #[snafu(display("Could not load questions"))]
+    LoadQuestions { source: QuestionRepositoryError },
+    #[snafu(display("Could not save grade"))]
+    SaveGrade { source: ScoreRepositoryError },
+}
+
+#[cfg(test)]
+mod tests {
+    use mockall::predicate::eq;
+
+    use crate::domain::entity::grade::Grade;
+    use crate::domain::entity::score::Points;
+    use crate::domain::entity::test::ItemResult;
+    use crate::domain::entity::user::User;
+    use crate::domain::repository::question::MockQuestionRepository;
+    use crate::domain::repository::score::MockScoreRepository;
+
+    use super::*;
+
+    fn new_items(pending: bool) -> Vec<ItemResult> {
+        vec![
+            ItemResult {
+                question: 0.into(),
+                revision: 0.into(),
+                correct: true,
+                credit: 1.0,
+                points: 1,
+                penalty: 0.0,
+                pending: false,
+                response: None,
+            },
+            ItemResult {
+                question: 1.into(),
+                revision: 1.into(),
+                correct: false,
+                credit: 0.5,
+                points: 2,
+                penalty: 0.0,
+                pending: false,
+                response: Some("essay".to_owned()),
+            },
+            ItemResult {
+                question: 2.into(),
+                revision: 2.into(),
+                correct: false,
+                credit: 0.0,
+                points: 1,
+                penalty: 0.0,
+                pending,
+                response: Some("essay".to_owned()),
+            },
+        ]
+    }
+
+    fn new_pending(id: Id, points: u32) -> Vec<PendingItem> {
+        vec![PendingItem {
+            id,
+            score: 7.into(),
+            revision: id,
+            points,
+            user: User::try_new("alice").unwrap(),
+            response: "essay".to_owned(),
+            end_time: Utc::now(),
+        }]
+    }
+
+    fn new_service(score_repository: MockScoreRepository) -> GradingService {
+        GradingService::new(
+            Arc::new(MockQuestionRepository::new()),
+            Arc::new(score_repository),
+            GradeScale::standard(),
+        )
+    }
+
+    #[tokio::test]
+    async fn grade_finalizes_last_item() {
+        let mut score_repository = MockScoreRepository::new();
+        score_repository
+            .expect_list_pending()
+            .returning(|| Ok(new_pending(2.into(), 1)));
+        score_repository
+            .expect_grade_item()
+            .withf(|id, grade| *id == 2.into() && grade.credit == 0.0 && grade.comment.is_none())
+            .returning(|_, _| Ok(7.into()));
+        score_repository
+            .expect_query_items()
+            .with(eq(Id::from(7)))
+            .returning(|_| Ok(new_items(false)));
+        score_repository
+            .expect_finalize()
+            .with(
+                eq(Id::from(7)),
+                eq(Points::new(2.0, 4)),
+                eq(Grade::new("F", false)),
+            )
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+
+        let service = new_service(score_repository);
+        let score = service
+            .grade(2.into(), 0.0, Some(" ".to_owned()), "teacher".to_owned())
+            .await
+            .unwrap();
+        assert_eq!(score, Some(Score::try_new(50f32).unwrap()));
+    }
+
+    #[tokio::test]
+    async fn grade_keeps_attempt_pending() {
+        let mut score_repository = MockScoreRepository::new();
+        score_repository
+            .expect_list_pending()
+            .returning(|| Ok(new_pending(1.into(), 2)));
+        score_repository
+            .expect_grade_item()
+            .withf(|_, grade| grade.credit == 0.5)
+            .returning(|_, _| Ok(7.into()));
+        score_repository
+            .expect_query_items()
+            .returning(|_| Ok(new_items(true)));
+        score_repository.expect_finalize().never();
+
+        let service = new_service(score_repository);
+        let score = service
+            .grade(1.into(), 1.0, None, "teacher".to_owned())
+            .await
+            .unwrap();
+        assert_eq!(score, None);
+    }
+
+    #[tokio::test]
+    async fn grade_points_invalid() {
+        let mut score_repository = MockScoreRepository::new();
+        score_repository
+            .expect_list_pending()
+            .returning(|| Ok(new_pending(0.into(), 2)));
+        score_repository.expect_grade_item().never();
+
+        let service = new_service(score_repository);
+        assert!(matches!(
+            service
+                .grade(0.into(), 3.0, None, "teacher".to_owned())
+                .await,
+            Err(GradingError::PointsInvalid { max: 2, .. }),
+        ));
+    }
+
+    #[tokio::test]
+    async fn grade_pending_item_not_found() {
+        let mut score_repository = MockScoreRepository::new();
+        score_repository
+            .expect_list_pending()
+            .returning(|| Ok(new_pending(0.into(), 2)));
+        score_repository.expect_grade_item().never();
+
+        let service = new_service(score_repository);
+        assert!(matches!(
+            service
+                .grade(1.into(), 1.0, None, "teacher".to_owned())
+                .await,
+            Err(GradingError::PendingItemNotFound { .. }),
+        ));
+    }
+}
@@ -0,0 +1,4 @@
+pub mod certificate;
+pub mod grading;
+pub mod review;
+pub mod similarity;
@@ -0,0 +1,152 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use snafu::prelude::*;
+
+use crate::domain::entity::id::Id;
+use crate::domain::entity::review::ReviewCard;
+use crate::domain::entity::user::User;
+use crate::domain::repository::review::{ReviewRepository, ReviewRepositoryError};
+
+#[derive(Debug, Clone)]
+pub struct ReviewService {
+    review_repository: Arc<dyn ReviewRepository>,
+}
+
+impl ReviewService {
+    pub fn new(review_repository: Arc<dyn ReviewRepository>) -> Self {
+        Self { review_repository }
+    }
+
+    // Records every result on the card of its question, creating the card on the first answer.
+    // Only questions missed at least once ever come up for review.
+    pub async fn record(
+        &self,
+        user: User,
+        results: Vec<(Id, bool)>,
+        now: DateTime<Utc>,
+    ) -> Result<(), ReviewError> {
+        if results.is_empty() {
+            return Ok(());
+        }
+
+        let mut cards: HashMap<Id, ReviewCard> = self
+            .review_repository
+            .find_cards(&user, results.iter().map(|(id, _)| *id).collect())
+            .await
+            .context(LoadCardsSnafu)?
+            .into_iter()
+            .map(|card| (card.question, card))
+            .collect();
+
+        for (question, correct) in results {
+            cards
+                .entry(question)
+                .or_insert_with(|| ReviewCard::new(question, now))
+                .review(correct, now);
+        }
+
+        self.review_repository
+            .save_cards(user, cards.into_values().collect())
+            .await
+            .context(SaveCardsSnafu)
+    }
+
+    // Cards of questions retired since they were last answered are dropped first.
+    pub async fn due(&self, user: &User, now: DateTime<Utc>) -> Result<Vec<Id>, ReviewError> {
+        self.review_repository
+            .remove_stale_cards(user)
+            .await
+            .context(RemoveCardsSnafu)?;
+
+        self.review_repository
+            .list_due(user, now)
+            .await
+            .context(LoadCardsSnafu)
+    }
+}
+
+#[derive(Debug, Snafu)]
+#[non_exhaustive]
+pub enum ReviewError {
+    #[snafu(display("Could not load review cards"))]
+    LoadCards { source: ReviewRepositoryError },
+    #[snafu(display("Could not save review cards"))]
+    SaveCards { source: ReviewRepositoryError },
+    #[snafu(display("Could not remove stale review cards"))]
+    RemoveCards { source: ReviewRepositoryError },
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeDelta;
+
+    use crate::domain::repository::review::MockReviewRepository;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn review_record() {
+        let now = Utc::now();
+        let mut existing = ReviewCard::new(1.into(), now);
+        existing.review(false, now);
+
+        let mut review_repository = MockReviewRepository::new();
+        let found = existing.clone();
+        review_repository
+            .expect_find_cards()
+            .times(1)
+            .returning(move |_, _| Ok(vec![found.clone()]));
+        review_repository
+            .expect_save_cards()
+            .times(1)
+            .withf(move |_, cards| {
+                let mut cards = cards.clone();
+                cards.sort_by_key(|c| c.question.inner());
+
+                cards.len() == 3
+                    && cards[0].question == 0.into()
+                    && (cards[0].attempts, cards[0].correct) == (1, 1)
+                    && !cards[0].missed()
+                    && cards[1].question == 1.into()
+                    && cards[1].repetitions == 1
+                    && cards[1].attempts == 2
+                    && cards[2].question == 2.into()
+                    && cards[2].due == now + TimeDelta::days(1)
+                    && cards[2].missed()
+            })
+            .returning(|_, _| Ok(()));
+
+        let service = ReviewService::new(Arc::new(review_repository));
+        service
+            .record(
+                User::try_new("user").unwrap(),
+                vec![(0.into(), true), (1.into(), true), (2.into(), false)],
+                now,
+            )
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn review_due() {
+        let now = Utc::now();
+        let mut review_repository = MockReviewRepository::new();
+        review_repository
+            .expect_remove_stale_cards()
+            .times(1)
+            .returning(|_| Ok(()));
+        review_repository
+            .expect_list_due()
+            .times(1)
+            .returning(|_, _| Ok(vec![2.into()]));
+
+        let service = ReviewService::new(Arc::new(review_repository));
+        let due = service
+            .due(&User::try_new("user").unwrap(), now)
+            .await
+            .unwrap();
+        assert_eq!(due, vec![Id::from(2)]);
+    }
+}
@@ -0,0 +1,182 @@
+use std::collections::HashSet;
+
+use crate::domain::entity::content::Content;
+use crate::domain::entity::question::{AbstractQuestion, Question};
+
+pub const DEFAULT_THRESHOLD: f64 = 0.8;
+
+const SHINGLE_SIZE: usize = 4;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fingerprint {
+    shingles: HashSet<String>,
+}
+
+impl Fingerprint {
+    pub fn new(content: &Content, options: &[Content]) -> Self {
+        let mut text = normalize(content.rendered());
+        for option in options {
+            let option = normalize(option.rendered());
+            if !option.is_empty() {
+                text.push(' ');
+                text.push_str(&option);
+            }
+        }
+
+        Self {
+            shingles: shingles(&text),
+        }
+    }
+
+    pub fn similarity(&self, other: &Self) -> f64 {
+        if self.shingles.is_empty() && other.shingles.is_empty() {
+            return 1.0;
+        }
+
+        let intersection = self.shingles.intersection(&other.shingles).count();
+        let union = self.shingles.len() + other.shingles.len() - intersection;
+
+        intersection as f64 / union as f64
+    }
+}
+
+impl From<&Question> for Fingerprint {
+    fn from(question: &Question) -> Self {
+        Self::new(
+            question.content(),
+            &[question.choices(), question.targets()].concat(),
+        )
+    }
+}
+
+// Groups each fingerprint with the later ones whose similarity to it reaches `threshold`, so that
+// every member resembles the first of its group rather than just another member. Only groups of
+// two or more are returned, each sorted by index.
+pub fn clusters(fingerprints: &[Fingerprint], threshold: f64) -> Vec<Vec<usize>> {
+    let mut grouped = vec![false; fingerprints.len()];
+    let mut groups = Vec::new();
+
+    for i in 0..fingerprints.len() {
+        if grouped[i] {
+            continue;
+        }
+
+        let group: Vec<usize> = std::iter::once(i)
+            .chain((i + 1..fingerprints.len()).filter(|&j| {
+                !grouped[j] && fingerprints[i].similarity(&fingerprints[j]) >= threshold
+            }))
+            .collect();
+
+        if group.len() > 1 {
+            for &j in &group {
+                grouped[j] = true;
+            }
+            groups.push(group);
+        }
+    }
+
+    groups
+}
+
+// Markup, entities, case and punctuation are dropped so that only the wording is compared.
+fn normalize(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    let mut in_entity = false;
+
+    for c in html.chars() {
+        match c {
+            '<' => {
+                in_tag = true;
+                text.push(' ');
+            }
+            '>' if in_tag => in_tag = false,
+            _ if in_tag => {}
+            '&' => in_entity = true,
+            ';' if in_entity => {
+                in_entity = false;
+                text.push(' ');
+            }
+            _ if in_entity && (c.is_ascii_alphanumeric() || c == '#') => {}
+            c if c.is_alphanumeric() => {
+                in_entity = false;
+                text.extend(c.to_lowercase());
+            }
+            _ => {
+                in_entity = false;
+                text.push(' ');
+            }
+        }
+    }
+
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn shingles(text: &str) -> HashSet<String> {
+    let chars: Vec<char> = text.chars().collect();
+
+    if chars.len() <= SHINGLE_SIZE {
+        return (!text.is_empty())
+            .then(|| text.to_owned())
+            .into_iter()
+            .collect();
+    }
+
+    chars
+        .windows(SHINGLE_SIZE)
+        .map(|w| w.iter().collect())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::entity::content::ContentFormat;
+
+    use super::*;
+
+    #[test]
+    fn normalize_drops_markup_and_case() {
+        assert_eq!(
+            normalize("<p>Use <code>ls -la</code> &amp; See!</p>"),
+            "use ls la see"
+        );
+    }
+
+    #[test]
+    fn similarity_ignores_formatting() {
+        let a = Fingerprint::new(
+            &Content::sanitized(ContentFormat::Html, "Which command <b>lists</b> files?"),
+            &["ls".into(), "cd".into()],
+        );
+        let b = Fingerprint::new(
+            &Content::sanitized(ContentFormat::Markdown, "which command **lists** files"),
+            &["ls".into(), "cd".into()],
+        );
+
+        assert_eq!(a.similarity(&b), 1.0);
+    }
+
+    #[test]
+    fn similarity_separates_different_questions() {
+        let a = Fingerprint::new(&"Which command lists files?".into(), &[]);
+        let b = Fingerprint::new(&"How do you check free memory?".into(), &[]);
+
+        assert!(a.similarity(&b) < DEFAULT_THRESHOLD);
+    }
+
+    #[test]
+    fn clusters_are_similar_to_first() {
+        let fingerprints: Vec<Fingerprint> = [
+            "Which command lists all files in a directory?",
+            "How do you check free memory?",
+            "Which command lists all the files in a directory?",
+            "Which command lists all the files in the directory?",
+        ]
+        .into_iter()
+        .map(|c| Fingerprint::new(&c.into(), &[]))
+        .collect();
+
+        // The last question resembles the third, but not the first closely enough.
+        assert_eq!(clusters(&fingerprints, DEFAULT_THRESHOLD), vec![vec![0, 2]]);
+    }
+}
@@ -0,0 +1,401 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use snafu::prelude::*;
+use tokio::sync::oneshot::Sender as OneshotSender;
+use tokio::time::Duration;
+
+use crate::domain::entity::ability::{Ability, Difficulty};
+use crate::domain::entity::answer::{Answer, SubmissionSource};
+use crate::domain::entity::grade::Grade;
+use crate::domain::entity::id::Id;
+use crate::domain::entity::question::{AbstractQuestion, Question};
+use crate::domain::entity::score::{Points, Score};
+use crate::domain::entity::test::{ItemFeedback, Submission, Test};
+use crate::domain::entity::user::User;
+use crate::domain::repository::question::{QuestionRepository, QuestionRepositoryError};
+use crate::domain::repository::score::{AttemptKind, ScoreRepository, ScoreRepositoryError};
+use crate::domain::session::base::{NoneSession, Session, SessionBase};
+use crate::domain::session::config::{SessionConfig, Timeouts};
+use crate::domain::session::test::TestSession;
+
+#[derive(Debug)]
+pub struct AdaptiveSession {
+    base: SessionBase<Self>,
+    question_repository: Arc<dyn QuestionRepository>,
+    score_repository: Arc<dyn ScoreRepository>,
+    user: User,
+    // Candidates with their calibrated difficulty, loaded when the first question is requested.
+    pool: Option<Vec<(Question, Difficulty)>>,
+    current: Option<(Question, Difficulty)>,
+    asked: Vec<Question>,
+    answers: HashMap<Id, Answer<SubmissionSource>>,
+    ability: Ability,
+    start_time: Option<DateTime<Utc>>,
+}
+
+impl AdaptiveSession {
+    pub const LENGTH: usize = 10;
+
+    pub fn new(
+        base: SessionBase<Self>,
+        question_repository: Arc<dyn QuestionRepository>,
+        score_repository: Arc<dyn ScoreRepository>,
+        user: User,
+    ) -> Self {
+        Self {
+            base,
+            question_repository,
+            score_repository,
+            user,
+            pool: None,
+            current: None,
+            asked: Vec::new(),
+            answers: HashMap::new(),
+            ability: Ability::new(),
+            start_time: None,
+        }
+    }
+
+    // Serves the current question again until it is answered. `None` means the test should
+    // be finished.
+    async fn handle_next(&mut self) -> Result<Option<Question>, AdaptiveSessionError> {
+        if let Some((question, _)) = &self.current {
+            return Ok(Some(question.clone()));
+        }
+
+        if self.asked.len() >= Self::LENGTH {
+            return Ok(None);
+        }
+
+        if self.pool.is_none() {
+            let mut rng = fastrand::Rng::new();
+            let questions: Vec<Question> = self
+                .question_repository
+                .select_questions(self.base.config.adaptive_questions.clone())
+                .await
+                .context(GenerateSnafu)?
+                .into_iter()
+                .filter(|q| !q.manual())
+                .map(|q| q.shuffled(&mut rng))
+                .collect();
+
+            let statistics = self
+                .score_repository
+                .query_item_statistics(questions.iter().map(|q| q.id()).collect())
+                .await
+                .context(CalibrateSnafu)?;
+
+            let pool = questions
+                .into_iter()
+                .map(|q| {
+                    let s = statistics.get(&q.id()).copied().unwrap_or_default();
+                    (q, Difficulty::calibrate(s.attempts, s.correct))
+                })
+                .collect();
+
+            self.pool = Some(pool);
+            self.start_time = Some(Utc::now());
+        }
+
+        // Items closest to the ability estimate are the most informative under the Rasch
+        // model.
+        let estimate = self.ability.estimate();
+        let pool = self.pool.get_or_insert_with(Vec::new);
+        let closest = pool
+            .iter()
+            .enumerate()
+            .min_by(|(_, (_, a)), (_, (_, b))| {
+                (a.inner() - estimate)
+                    .abs()
+                    .total_cmp(&(b.inner() - estimate).abs())
+            })
+            .map(|(index, _)| index);
+
+        self.current = closest.map(|index| pool.swap_remove(index));
+        Ok(self.current.as_ref().map(|(q, _)| q.clone()))
+    }
+
+    fn handle_answer(
+        &mut self,
+        answer: Answer<SubmissionSource>,
+    ) -> Result<AdaptiveProgress, AdaptiveSessionError> {
+        let (question, difficulty) = self.current.take().context(NoQuestionSnafu)?;
+
+        self.ability.update(difficulty, question.check(&answer));
+        self.answers.insert(question.id(), answer);
+        self.asked.push(question);
+
+        let available = self.pool.as_ref().map_or(0, Vec::len);
+        Ok(AdaptiveProgress {
+            answered: self.asked.len(),
+            remaining: (Self::LENGTH - self.asked.len()).min(available),
+        })
+    }
+
+    // A question served but not answered yet is left out of the result.
+    async fn handle_finish(&mut self) -> Result<AdaptiveSummary, AdaptiveSessionError> {
+        let start_time = self.start_time.take().context(NotStartedSnafu)?;
+
+        let test = Test::new(self.id(), std::mem::take(&mut self.asked))
+            .with_blueprint(TestSession::blueprint(&self.base.config));
+        let submission = Submission::new(std::mem::take(&mut self.answers));
+        let items = test.grade_items(&submission);
+        let points = Test::points(&items);
+        let grade = test.evaluate(&items);
+        let feedback = test.feedback(&items);
+
+        let end_time = Utc::now();
+        let duration = (end_time - start_time).to_std().unwrap_or_default();
+
+        self.score_repository
+            .insert(
+                self.user.clone(),
+                AttemptKind::Adaptive,
+                points,
+                grade.clone(),
+                end_time,
+                duration,
+                items,
+            )
+            .await
+            .context(SaveScoreSnafu)?;
+
+        Ok(AdaptiveSummary {
+            score: points.score(),
+            points,
+            grade,
+            ability: self.ability.estimate(),
+            duration,
+            feedback,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl Session for AdaptiveSession {
+    type ExtraCommand = AdaptiveSessionCommand;
+    type SubSession = NoneSession;
+
+    fn timeouts(config: &SessionConfig) -> &Timeouts {
+        &config.adaptive
+    }
+
+    fn base(&self) -> &SessionBase<Self> {
+        &self.base
+    }
+
+    fn base_mut(&mut self) -> &mut SessionBase<Self> {
+        &mut self.base
+    }
+
+    async fn handle(&mut self, command: Self::ExtraCommand) {
+        self.reset_expire();
+
+        match command {
+            Self::ExtraCommand::Next { responder } => {
+                let res = self.handle_next().await;
+                let _ = responder.send(res);
+            }
+            Self::ExtraCommand::Answer { answer, responder } => {
+                let res = self.handle_answer(answer);
+                let _ = responder.send(res);
+            }
+            Self::ExtraCommand::Finish { responder } => {
+                let res = self.handle_finish().await;
+                let _ = responder.send(res);
+                self.request_exit();
+            }
+        }
+    }
+
+    async fn finalize(&mut self) {}
+}
+
+#[derive(Debug)]
+pub enum AdaptiveSessionCommand {
+    Next {
+        responder: OneshotSender<Result<Option<Question>, AdaptiveSessionError>>,
+    },
+    Answer {
+        answer: Answer<SubmissionSource>,
+        responder: OneshotSender<Result<AdaptiveProgress, AdaptiveSessionError>>,
+    },
+    Finish {
+        responder: OneshotSender<Result<AdaptiveSummary, AdaptiveSessionError>>,
+    },
+}
+
+#[derive(Debug, Snafu)]
+#[non_exhaustive]
+pub enum AdaptiveSessionError {
+    #[snafu(display("Could not select adaptive test questions"))]
+    Generate { source: QuestionRepositoryError },
+    #[snafu(display("Could not calibrate question difficulties"))]
+    Calibrate { source: ScoreRepositoryError },
+    #[snafu(display("Could not save user's score"))]
+    SaveScore { source: ScoreRepositoryError },
+    #[snafu(display("Could not answer before a question is served"))]
+    NoQuestion,
+    #[snafu(display("Could not finish test before it starts"))]
+    NotStarted,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AdaptiveProgress {
+    pub answered: usize,
+    pub remaining: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AdaptiveSummary {
+    pub score: Score,
+    pub points: Points,
+    pub grade: Option<Grade>,
+    pub ability: f32,
+    pub duration: Duration,
+    pub feedback: Vec<ItemFeedback>,
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::mpsc::{self, Receiver as MpscReceiver, Sender as MpscSender};
+
+    use crate::domain::entity::answer::{CompletionAnswer, StandardSource};
+    use crate::domain::entity::id::SequentialIdAllocator;
+    use crate::domain::entity::question::CompletionQuestion;
+    use crate::domain::repository::question::MockQuestionRepository;
+    use crate::domain::repository::score::{ItemStatistics, MockScoreRepository};
+    use crate::domain::session::base::{Command, Report};
+
+    use super::*;
+
+    #[tokio::test(start_paused = true)]
+    async fn adaptive_follows_ability() {
+        let (mut session, _commander, _report) = new_adaptive_session().await;
+
+        // Starts from the item closest to an average student.
+        let question = session.handle_next().await.unwrap().unwrap();
+        assert_eq!(question.id(), 1.into());
+        assert_eq!(session.handle_next().await.unwrap(), Some(question));
+
+        let progress = session.handle_answer(new_answer("answer")).unwrap();
+        assert_eq!(
+            progress,
+            AdaptiveProgress {
+                answered: 1,
+                remaining: 2
+            }
+        );
+        assert!(session.ability.estimate() > 0.0);
+
+        // A correct answer moves on to the hard item.
+        let question = session.handle_next().await.unwrap().unwrap();
+        assert_eq!(question.id(), 2.into());
+        session.handle_answer(new_answer("wrong")).unwrap();
+
+        let question = session.handle_next().await.unwrap().unwrap();
+        assert_eq!(question.id(), 0.into());
+        session.handle_answer(new_answer("answer")).unwrap();
+
+        assert_eq!(session.handle_next().await.unwrap(), None);
+
+        let summary = session.handle_finish().await.unwrap();
+        assert!((summary.score.inner() - 200.0 / 3.0).abs() < 1e-3);
+        assert_eq!(summary.ability, session.ability.estimate());
+        assert_eq!(summary.feedback.len(), 3);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn adaptive_not_started() {
+        let (mut session, _commander, _report) = new_adaptive_session().await;
+
+        assert!(matches!(
+            session.handle_answer(new_answer("answer")),
+            Err(AdaptiveSessionError::NoQuestion),
+        ));
+        assert!(matches!(
+            session.handle_finish().await,
+            Err(AdaptiveSessionError::NotStarted),
+        ));
+    }
+
+    async fn new_adaptive_session() -> (
+        AdaptiveSession,
+        MpscSender<Command<AdaptiveSession>>,
+        MpscReceiver<Report>,
+    ) {
+        let mut question_repository = MockQuestionRepository::new();
+        question_repository
+            .expect_select_questions()
+            .times(..=1)
+            .returning(|_| Ok(new_questions()));
+        let mut score_repository = MockScoreRepository::new();
+        score_repository
+            .expect_query_item_statistics()
+            .times(..=1)
+            .returning(|_| {
+                Ok(HashMap::from([
+                    (
+                        0.into(),
+                        ItemStatistics {
+                            attempts: 10,
+                            correct: 9,
+                        },
+                    ),
+                    (
+                        2.into(),
+                        ItemStatistics {
+                            attempts: 10,
+                            correct: 2,
+                        },
+                    ),
+                ]))
+            });
+        score_repository
+            .expect_insert()
+            .withf(|_, kind, _, _, _, _, _| *kind == AttemptKind::Adaptive)
+            .times(..=1)
+            .returning(|_, _, _, _, _, _, _| Ok(()));
+
+        let id_allocator = Arc::new(SequentialIdAllocator::new());
+        let (commander, command) = mpsc::channel(4);
+        let (reporter, report) = mpsc::channel(4);
+        let base = SessionBase::new(
+            id_allocator,
+            Arc::new(SessionConfig::default()),
+            command,
+            reporter,
+        )
+        .await;
+        let session = AdaptiveSession::new(
+            base,
+            Arc::new(question_repository),
+            Arc::new(score_repository),
+            User::try_new("user").unwrap(),
+        );
+        (session, commander, report)
+    }
+
+    fn new_answer(answer: &str) -> Answer<SubmissionSource> {
+        CompletionAnswer::<SubmissionSource>::try_new(answer)
+            .unwrap()
+            .into()
+    }
+
+    // Question 0 is easy, 1 has never been attempted and 2 is hard.
+    fn new_questions() -> Vec<Question> {
+        (0..3usize)
+            .map(|id| {
+                CompletionQuestion::try_new(
+                    id.into(),
+                    format!("{id}. Completion").into(),
+                    CompletionAnswer::<StandardSource>::try_new("answer").unwrap(),
+                )
+                .unwrap()
+                .into()
+            })
+            .collect()
+    }
+}
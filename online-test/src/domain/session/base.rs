@@ -4,18 +4,19 @@ use std::fmt::Debug;
 use std::sync::Arc;
 
 use tokio::sync::mpsc::{channel, Receiver, Sender};
-use tokio::time::{interval, timeout, Duration, Interval};
+use tokio::time::{interval, timeout, Interval};
 
 use crate::domain::entity::id::{Id, SequentialIdAllocator};
+use crate::domain::session::config::{SessionConfig, Timeouts};
 
 #[async_trait::async_trait]
 pub trait Session: Debug + Sized + Send + 'static {
-    const SESSION_EXPIRE_TIMEOUT: Duration;
-    const CANCEL_AWAIT_TIMEOUT: Duration;
-
     type ExtraCommand: Debug + Send;
     type SubSession: Session;
 
+    // Picks the timeouts of this kind of session out of the shared configuration.
+    fn timeouts(config: &SessionConfig) -> &Timeouts;
+
     fn base(&self) -> &SessionBase<Self>;
 
     fn base_mut(&mut self) -> &mut SessionBase<Self>;
@@ -93,7 +94,8 @@ pub trait Session: Debug + Sized + Send + 'static {
             running += res.map_or(0, |_| 1);
         }
 
-        let _ = timeout(Self::CANCEL_AWAIT_TIMEOUT, async {
+        let cancel_await = Self::timeouts(&self.base().config).cancel_await;
+        let _ = timeout(cancel_await, async {
             while running > 0 {
                 let res = self.base_mut().sub_report.recv().await;
                 running -= res.map_or(0, |_| 1);
@@ -105,21 +107,34 @@ pub trait Session: Debug + Sized + Send + 'static {
     async fn spawn<F>(&mut self, construtor: F) -> Option<Id>
     where
         F: FnOnce(SessionBase<Self::SubSession>) -> Self::SubSession + Send + 'static,
+    {
+        let (id, commander) = self.spawn_detached(construtor).await?;
+        self.base_mut().sub_sessions.insert(id, commander);
+
+        Some(id)
+    }
+
+    // Spawns a session of any type reporting to this one. Unlike `spawn`, the commander is
+    // handed back to the caller, which becomes responsible for cancelling the session.
+    async fn spawn_detached<T, F>(&mut self, construtor: F) -> Option<(Id, Sender<Command<T>>)>
+    where
+        T: Session,
+        F: FnOnce(SessionBase<T>) -> T + Send + 'static,
     {
         let id_allocator = Arc::clone(&self.base().id_allocator);
+        let config = Arc::clone(&self.base().config);
         let (commander, command) = channel(4);
         let reporter = self.base().sub_reporter.clone()?;
-        let sub_base = SessionBase::new(id_allocator, command, reporter).await;
+        let sub_base = SessionBase::new(id_allocator, config, command, reporter).await;
 
         let id = sub_base.id;
-        self.base_mut().sub_sessions.insert(id, commander);
 
         tokio::spawn(async move {
             let mut sub = construtor(sub_base);
             sub.run().await;
         });
 
-        Some(id)
+        Some((id, commander))
     }
 
     fn request_exit(&mut self) {
@@ -136,12 +151,13 @@ pub enum NoneSession {}
 
 #[async_trait::async_trait]
 impl Session for NoneSession {
-    const SESSION_EXPIRE_TIMEOUT: Duration = Duration::from_secs(0);
-    const CANCEL_AWAIT_TIMEOUT: Duration = Duration::from_secs(0);
-
     type ExtraCommand = Infallible;
     type SubSession = NoneSession;
 
+    fn timeouts(_config: &SessionConfig) -> &Timeouts {
+        unreachable!()
+    }
+
     fn base(&self) -> &SessionBase<Self> {
         unreachable!()
     }
@@ -174,6 +190,7 @@ pub enum Report {
 pub struct SessionBase<S: Session> {
     pub(super) id: Id,
     pub(super) id_allocator: Arc<SequentialIdAllocator>,
+    pub(super) config: Arc<SessionConfig>,
     pub(super) command: Receiver<Command<S>>,
     pub(super) sub_sessions: HashMap<Id, Sender<Command<S::SubSession>>>,
     pub(super) reporter: Sender<Report>,
@@ -186,17 +203,19 @@ pub struct SessionBase<S: Session> {
 impl<S: Session> SessionBase<S> {
     pub async fn new(
         id_allocator: Arc<SequentialIdAllocator>,
+        config: Arc<SessionConfig>,
         command: Receiver<Command<S>>,
         reporter: Sender<Report>,
     ) -> Self {
         let id = id_allocator.allocate();
         let (sub_reporter, sub_report) = channel(4);
-        let mut expire_timer = interval(S::SESSION_EXPIRE_TIMEOUT);
+        let mut expire_timer = interval(S::timeouts(&config).expire);
         expire_timer.tick().await;
 
         SessionBase {
             id,
             id_allocator,
+            config,
             command,
             sub_sessions: HashMap::new(),
             reporter,
@@ -213,6 +232,8 @@ mod tests {
     use std::collections::BTreeSet;
     use std::sync::Mutex;
 
+    use tokio::time::Duration;
+
     use super::*;
 
     #[tokio::test(start_paused = true)]
@@ -300,7 +321,14 @@ mod tests {
             let id_allocator = Arc::new(SequentialIdAllocator::new());
             let (commander, command) = channel(4);
             let (reporter, report) = channel(4);
-            let base = SessionBase::new(id_allocator, command, reporter).await;
+            let config = Arc::new(SessionConfig {
+                login: Timeouts {
+                    expire: Duration::from_millis(100),
+                    cancel_await: Duration::from_millis(100),
+                },
+                ..SessionConfig::default()
+            });
+            let base = SessionBase::new(id_allocator, config, command, reporter).await;
 
             let set = Arc::new(Mutex::new(BTreeSet::new()));
             let set2 = Arc::clone(&set);
@@ -316,12 +344,13 @@ mod tests {
 
     #[async_trait::async_trait]
     impl Session for SimpleSession {
-        const SESSION_EXPIRE_TIMEOUT: Duration = Duration::from_millis(100);
-        const CANCEL_AWAIT_TIMEOUT: Duration = Duration::from_millis(100);
-
         type ExtraCommand = SimpleSessionCommand;
         type SubSession = Self;
 
+        fn timeouts(config: &SessionConfig) -> &Timeouts {
+            &config.login
+        }
+
         fn base(&self) -> &SessionBase<Self> {
             &self.base
         }
@@ -334,7 +363,7 @@ mod tests {
             match command {
                 Self::ExtraCommand::Send => {
                     self.set.lock().unwrap().insert(self.id().inner());
-                    for (_, sub) in &self.base.sub_sessions {
+                    for sub in self.base.sub_sessions.values() {
                         let _ = sub.send(Command::Extra(Self::ExtraCommand::Send)).await;
                     }
                 }
@@ -0,0 +1,88 @@
+use tokio::time::Duration;
+
+use crate::domain::entity::grade::GradeScale;
+use crate::domain::entity::test::{Penalty, Weights};
+use crate::domain::repository::question::SelectCount;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Timeouts {
+    // Idle time after which a session is cancelled.
+    pub expire: Duration,
+    // How long a cancelled session waits for its sub sessions to exit.
+    pub cancel_await: Duration,
+}
+
+impl Timeouts {
+    pub const fn from_secs(expire: u64, cancel_await: u64) -> Self {
+        Self {
+            expire: Duration::from_secs(expire),
+            cancel_await: Duration::from_secs(cancel_await),
+        }
+    }
+}
+
+// Runtime settings shared by every session spawned from the same `Core`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SessionConfig {
+    pub login: Timeouts,
+    pub test: Timeouts,
+    pub practice: Timeouts,
+    pub adaptive: Timeouts,
+    // Number of questions of each kind in a generated test.
+    pub test_questions: SelectCount,
+    // Points a test question is worth by kind, unless it has a weight of its own.
+    pub test_points: Weights,
+    // Share of the points deducted for a wrong answer in a test, by kind.
+    pub test_penalty: Penalty,
+    // Letter grades of a test score and the score needed to pass, also used for certificates.
+    pub grade_scale: GradeScale,
+    // Number of questions of each kind drawn for a practice, when none is due for review.
+    pub practice_questions: SelectCount,
+    // Number of questions of each kind in the pool an adaptive test picks from.
+    pub adaptive_questions: SelectCount,
+}
+
+impl Default for SessionConfig {
+    fn default() -> Self {
+        Self {
+            login: Timeouts::from_secs(60 * 45, 5),
+            test: Timeouts::from_secs(60 * 30 + 30, 5),
+            practice: Timeouts::from_secs(60 * 30, 5),
+            adaptive: Timeouts::from_secs(60 * 30, 5),
+            test_questions: SelectCount {
+                single_selection: 8,
+                multiple_selection: 8,
+                completion: 12,
+                true_false: 0,
+                ordering: 0,
+                matching: 0,
+                essay: 0,
+                cloze: 0,
+            },
+            test_points: Weights::UNIFORM,
+            // Negative marking is opt-in.
+            test_penalty: Penalty::NONE,
+            grade_scale: GradeScale::standard(),
+            practice_questions: SelectCount {
+                single_selection: 4,
+                multiple_selection: 3,
+                completion: 3,
+                true_false: 0,
+                ordering: 0,
+                matching: 0,
+                essay: 0,
+                cloze: 0,
+            },
+            adaptive_questions: SelectCount {
+                single_selection: 8,
+                multiple_selection: 8,
+                completion: 12,
+                true_false: 0,
+                ordering: 0,
+                matching: 0,
+                essay: 0,
+                cloze: 0,
+            },
+        }
+    }
+}
@@ -1,15 +1,32 @@
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
-use snafu::{prelude::*, OptionExt, ResultExt};
+use chrono::Utc;
+use snafu::{prelude::*, OptionExt, Report, ResultExt};
+use tokio::sync::mpsc::Sender;
 use tokio::sync::oneshot::{self, Sender as OneshotSender};
-use tokio::time::Duration;
 
+use crate::domain::entity::answer::{Answer, SubmissionSource};
+use crate::domain::entity::certificate::{
+    Certificate, GenerateVerificationCodeError, VerificationCode,
+};
 use crate::domain::entity::id::Id;
-use crate::domain::entity::test::{Submission, Test};
+use crate::domain::entity::question::{AbstractQuestion, Question};
+use crate::domain::entity::test::{ItemFeedback, Submission, Test};
 use crate::domain::entity::user::User;
+use crate::domain::repository::practice::PracticeRepository;
 use crate::domain::repository::question::QuestionRepository;
 use crate::domain::repository::score::{Record, ScoreRepository, ScoreRepositoryError};
+use crate::domain::service::review::{ReviewError, ReviewService};
+use crate::domain::session::adaptive::{
+    AdaptiveProgress, AdaptiveSession, AdaptiveSessionCommand, AdaptiveSessionError,
+    AdaptiveSummary,
+};
 use crate::domain::session::base::{Command, Session, SessionBase};
+use crate::domain::session::config::{SessionConfig, Timeouts};
+use crate::domain::session::practice::{
+    PracticeFeedback, PracticeSession, PracticeSessionCommand, PracticeSessionError,
+};
 use crate::domain::session::test::{TestSession, TestSessionError, TestSummary};
 
 use super::test::TestSessionCommand;
@@ -19,7 +36,15 @@ pub struct LoginSession {
     base: SessionBase<Self>,
     question_repository: Arc<dyn QuestionRepository>,
     score_repository: Arc<dyn ScoreRepository>,
+    practice_repository: Arc<dyn PracticeRepository>,
+    review: ReviewService,
     user: Option<User>,
+    // Questions served by every test or practice started in this session, keyed by its ID.
+    questions: HashMap<Id, HashSet<Id>>,
+    // Practice and adaptive sessions are not sub-sessions, so their commanders are kept here
+    // instead.
+    practices: HashMap<Id, Sender<Command<PracticeSession>>>,
+    adaptives: HashMap<Id, Sender<Command<AdaptiveSession>>>,
 }
 
 impl LoginSession {
@@ -27,12 +52,19 @@ impl LoginSession {
         base: SessionBase<Self>,
         question_repository: Arc<dyn QuestionRepository>,
         score_repository: Arc<dyn ScoreRepository>,
+        practice_repository: Arc<dyn PracticeRepository>,
+        review: ReviewService,
     ) -> Self {
         Self {
             base,
             question_repository,
             score_repository,
+            practice_repository,
+            review,
             user: None,
+            questions: HashMap::new(),
+            practices: HashMap::new(),
+            adaptives: HashMap::new(),
         }
     }
 
@@ -74,6 +106,9 @@ impl LoginSession {
             .unwrap_or_else(|_| unreachable!("TestSession should send response back"))
             .context(TestSnafu)?;
 
+        self.questions
+            .insert(id, test.questions().iter().map(|q| q.id()).collect());
+
         Ok(test)
     }
 
@@ -104,9 +139,311 @@ impl LoginSession {
             .unwrap_or_else(|_| unreachable!("TestSession should send response back"))
             .context(TestSnafu)?;
 
+        self.questions.remove(&test_id);
+        self.record_review(&res.feedback).await;
+
         Ok(res)
     }
 
+    async fn handle_draft(
+        &mut self,
+        test_id: Id,
+        submission: Submission,
+    ) -> Result<(), LoginSessionError> {
+        ensure!(self.user.is_some(), NotLoggedInSnafu);
+
+        let (responder, receiver) = oneshot::channel();
+        let commander = self
+            .base
+            .sub_sessions
+            .get(&test_id)
+            .context(SessionNotFoundSnafu { id: test_id })?;
+
+        let _ = commander
+            .send(Command::Extra(TestSessionCommand::SaveDraft {
+                test_id,
+                submission,
+                responder,
+            }))
+            .await;
+
+        receiver
+            .await
+            .unwrap_or_else(|_| unreachable!("TestSession should send response back"))
+            .context(TestSnafu)
+    }
+
+    // Grades the drafts of the tests still open in this session, returning how many were
+    // graded. Failures are skipped, as the session is about to be cancelled anyway.
+    async fn handle_finish(&mut self) -> usize {
+        let Some(user) = self.user.clone() else {
+            return 0;
+        };
+
+        let tests: Vec<_> = self
+            .base
+            .sub_sessions
+            .iter()
+            .map(|(id, commander)| (*id, commander.clone()))
+            .collect();
+        let mut graded = 0;
+
+        for (id, commander) in tests {
+            let (responder, receiver) = oneshot::channel();
+            let command = TestSessionCommand::Finish {
+                user: user.clone(),
+                responder,
+            };
+
+            if commander.send(Command::Extra(command)).await.is_err() {
+                continue;
+            }
+            if let Ok(Ok(Some(summary))) = receiver.await {
+                self.questions.remove(&id);
+                self.record_review(&summary.feedback).await;
+                graded += 1;
+            }
+        }
+
+        graded
+    }
+
+    async fn handle_practice(&mut self, review: bool) -> Result<Id, LoginSessionError> {
+        let user = self.user.clone().context(NotLoggedInSnafu)?;
+        self.practices.retain(|_, commander| !commander.is_closed());
+
+        let due = if review {
+            let due = self
+                .review
+                .due(&user, Utc::now())
+                .await
+                .context(ReviewSnafu)?;
+            Some(due)
+        } else {
+            None
+        };
+
+        let question_repository = Arc::clone(&self.question_repository);
+        let practice_repository = Arc::clone(&self.practice_repository);
+        let (id, commander) = self
+            .spawn_detached(|base| match due {
+                Some(due) => PracticeSession::review(
+                    base,
+                    question_repository,
+                    practice_repository,
+                    user,
+                    due,
+                ),
+                None => PracticeSession::new(base, question_repository, practice_repository, user),
+            })
+            .await
+            .unwrap_or_else(|| unreachable!("A PracticeSession should start"));
+
+        self.practices.insert(id, commander);
+        Ok(id)
+    }
+
+    async fn handle_practice_next(
+        &mut self,
+        practice_id: Id,
+    ) -> Result<Option<Question>, LoginSessionError> {
+        ensure!(self.user.is_some(), NotLoggedInSnafu);
+
+        let (responder, receiver) = oneshot::channel();
+        send_detached(
+            &mut self.practices,
+            practice_id,
+            PracticeSessionCommand::Next { responder },
+        )
+        .await?;
+
+        // An expired practice drops the command without responding.
+        let res = receiver
+            .await
+            .ok()
+            .context(SessionNotFoundSnafu { id: practice_id })?
+            .context(PracticeSnafu)?;
+
+        if let Some(question) = &res {
+            self.questions
+                .entry(practice_id)
+                .or_default()
+                .insert(question.id());
+        }
+
+        Ok(res)
+    }
+
+    async fn handle_practice_answer(
+        &mut self,
+        practice_id: Id,
+        answer: Answer<SubmissionSource>,
+    ) -> Result<PracticeFeedback, LoginSessionError> {
+        ensure!(self.user.is_some(), NotLoggedInSnafu);
+
+        let (responder, receiver) = oneshot::channel();
+        send_detached(
+            &mut self.practices,
+            practice_id,
+            PracticeSessionCommand::Answer { answer, responder },
+        )
+        .await?;
+
+        let res = receiver
+            .await
+            .ok()
+            .context(SessionNotFoundSnafu { id: practice_id })?
+            .context(PracticeSnafu)?;
+
+        // As with tests, the attempt is already saved, so only the schedule could be lost.
+        self.record_results(vec![(res.question, res.correct)]).await;
+
+        Ok(res)
+    }
+
+    async fn handle_adaptive(&mut self) -> Result<Id, LoginSessionError> {
+        let user = self.user.clone().context(NotLoggedInSnafu)?;
+        self.adaptives.retain(|_, commander| !commander.is_closed());
+
+        let question_repository = Arc::clone(&self.question_repository);
+        let score_repository = Arc::clone(&self.score_repository);
+        let (id, commander) = self
+            .spawn_detached(|base| {
+                AdaptiveSession::new(base, question_repository, score_repository, user)
+            })
+            .await
+            .unwrap_or_else(|| unreachable!("An AdaptiveSession should start"));
+
+        self.adaptives.insert(id, commander);
+        Ok(id)
+    }
+
+    async fn handle_adaptive_next(
+        &mut self,
+        test_id: Id,
+    ) -> Result<Option<Question>, LoginSessionError> {
+        ensure!(self.user.is_some(), NotLoggedInSnafu);
+
+        let (responder, receiver) = oneshot::channel();
+        send_detached(
+            &mut self.adaptives,
+            test_id,
+            AdaptiveSessionCommand::Next { responder },
+        )
+        .await?;
+
+        let res = receiver
+            .await
+            .ok()
+            .context(SessionNotFoundSnafu { id: test_id })?
+            .context(AdaptiveSnafu)?;
+
+        if let Some(question) = &res {
+            self.questions
+                .entry(test_id)
+                .or_default()
+                .insert(question.id());
+        }
+
+        Ok(res)
+    }
+
+    async fn handle_adaptive_answer(
+        &mut self,
+        test_id: Id,
+        answer: Answer<SubmissionSource>,
+    ) -> Result<AdaptiveProgress, LoginSessionError> {
+        ensure!(self.user.is_some(), NotLoggedInSnafu);
+
+        let (responder, receiver) = oneshot::channel();
+        send_detached(
+            &mut self.adaptives,
+            test_id,
+            AdaptiveSessionCommand::Answer { answer, responder },
+        )
+        .await?;
+
+        receiver
+            .await
+            .ok()
+            .context(SessionNotFoundSnafu { id: test_id })?
+            .context(AdaptiveSnafu)
+    }
+
+    async fn handle_adaptive_finish(
+        &mut self,
+        test_id: Id,
+    ) -> Result<AdaptiveSummary, LoginSessionError> {
+        ensure!(self.user.is_some(), NotLoggedInSnafu);
+
+        let (responder, receiver) = oneshot::channel();
+        send_detached(
+            &mut self.adaptives,
+            test_id,
+            AdaptiveSessionCommand::Finish { responder },
+        )
+        .await?;
+
+        // The adaptive test exits once finished, whether or not its score could be saved.
+        self.adaptives.remove(&test_id);
+        self.questions.remove(&test_id);
+
+        let res = receiver
+            .await
+            .ok()
+            .context(SessionNotFoundSnafu { id: test_id })?
+            .context(AdaptiveSnafu)?;
+
+        self.record_review(&res.feedback).await;
+
+        Ok(res)
+    }
+
+    // Graded answers of a finished test reschedule their questions for review. The score is
+    // already saved by then, so a failure here must not fail the submission.
+    async fn record_review(&self, feedback: &[ItemFeedback]) {
+        let results = feedback
+            .iter()
+            .filter(|f| !f.pending)
+            .map(|f| (f.question, f.correct))
+            .collect();
+        self.record_results(results).await;
+    }
+
+    // Failures are only logged, as the answers they come from are already saved.
+    async fn record_results(&self, results: Vec<(Id, bool)>) {
+        let Some(user) = self.user.clone() else {
+            return;
+        };
+
+        if let Err(err) = self.review.record(user.clone(), results, Utc::now()).await {
+            eprintln!(
+                "Could not record review results of {user}: {}",
+                Report::from_error(err)
+            );
+        }
+    }
+
+    // Whether a test or practice still running in this session has served the question.
+    fn handle_access(&mut self, question: Id) -> Result<(), LoginSessionError> {
+        ensure!(self.user.is_some(), NotLoggedInSnafu);
+
+        self.practices.retain(|_, commander| !commander.is_closed());
+        self.adaptives.retain(|_, commander| !commander.is_closed());
+        let running = &self.base.sub_sessions;
+        let practices = &self.practices;
+        let adaptives = &self.adaptives;
+        self.questions.retain(|id, _| {
+            running.contains_key(id) || practices.contains_key(id) || adaptives.contains_key(id)
+        });
+
+        ensure!(
+            self.questions.values().any(|q| q.contains(&question)),
+            QuestionNotInTestSnafu { question }
+        );
+        Ok(())
+    }
+
     async fn handle_query(&mut self, kind: QueryKind) -> Result<Record, LoginSessionError> {
         let user = self.user.clone().context(NotLoggedInSnafu)?;
 
@@ -131,16 +468,26 @@ impl LoginSession {
             .await
             .context(QuerySnafu)
     }
+
+    async fn handle_certificate(&mut self) -> Result<Certificate, LoginSessionError> {
+        let user = self.user.clone().context(NotLoggedInSnafu)?;
+        let code = VerificationCode::generate().context(GenerateCodeSnafu)?;
+        self.score_repository
+            .issue_certificate(&user, code)
+            .await
+            .context(CertificateSnafu)
+    }
 }
 
 #[async_trait::async_trait]
 impl Session for LoginSession {
-    const SESSION_EXPIRE_TIMEOUT: Duration = Duration::from_secs(60 * 45);
-    const CANCEL_AWAIT_TIMEOUT: Duration = Duration::from_secs(5);
-
     type ExtraCommand = LoginSessionCommand;
     type SubSession = TestSession;
 
+    fn timeouts(config: &SessionConfig) -> &Timeouts {
+        &config.login
+    }
+
     fn base(&self) -> &SessionBase<Self> {
         &self.base
     }
@@ -167,6 +514,18 @@ impl Session for LoginSession {
                 let res = self.handle_submit(test_id, submission).await;
                 let _ = responder.send(res);
             }
+            Self::ExtraCommand::Draft {
+                test_id,
+                submission,
+                responder,
+            } => {
+                let res = self.handle_draft(test_id, submission).await;
+                let _ = responder.send(res);
+            }
+            Self::ExtraCommand::Finish { responder } => {
+                let res = self.handle_finish().await;
+                let _ = responder.send(res);
+            }
             Self::ExtraCommand::Query { kind, responder } => {
                 let res = self.handle_query(kind).await;
                 let _ = responder.send(res);
@@ -175,10 +534,82 @@ impl Session for LoginSession {
                 let res = self.handle_query_all().await;
                 let _ = responder.send(res);
             }
+            Self::ExtraCommand::Certificate { responder } => {
+                let res = self.handle_certificate().await;
+                let _ = responder.send(res);
+            }
+            Self::ExtraCommand::Access {
+                question,
+                responder,
+            } => {
+                let res = self.handle_access(question);
+                let _ = responder.send(res);
+            }
+            Self::ExtraCommand::Practice { review, responder } => {
+                let res = self.handle_practice(review).await;
+                let _ = responder.send(res);
+            }
+            Self::ExtraCommand::PracticeNext {
+                practice_id,
+                responder,
+            } => {
+                let res = self.handle_practice_next(practice_id).await;
+                let _ = responder.send(res);
+            }
+            Self::ExtraCommand::PracticeAnswer {
+                practice_id,
+                answer,
+                responder,
+            } => {
+                let res = self.handle_practice_answer(practice_id, answer).await;
+                let _ = responder.send(res);
+            }
+            Self::ExtraCommand::Adaptive { responder } => {
+                let res = self.handle_adaptive().await;
+                let _ = responder.send(res);
+            }
+            Self::ExtraCommand::AdaptiveNext { test_id, responder } => {
+                let res = self.handle_adaptive_next(test_id).await;
+                let _ = responder.send(res);
+            }
+            Self::ExtraCommand::AdaptiveAnswer {
+                test_id,
+                answer,
+                responder,
+            } => {
+                let res = self.handle_adaptive_answer(test_id, answer).await;
+                let _ = responder.send(res);
+            }
+            Self::ExtraCommand::AdaptiveFinish { test_id, responder } => {
+                let res = self.handle_adaptive_finish(test_id).await;
+                let _ = responder.send(res);
+            }
         }
     }
 
-    async fn finalize(&mut self) {}
+    async fn finalize(&mut self) {
+        for (_, commander) in self.practices.drain() {
+            let _ = commander.send(Command::Cancel).await;
+        }
+        for (_, commander) in self.adaptives.drain() {
+            let _ = commander.send(Command::Cancel).await;
+        }
+    }
+}
+
+// Forwards a command to a session kept outside `sub_sessions`, forgetting it once it is gone.
+async fn send_detached<T: Session>(
+    sessions: &mut HashMap<Id, Sender<Command<T>>>,
+    id: Id,
+    command: T::ExtraCommand,
+) -> Result<(), LoginSessionError> {
+    let commander = sessions.get(&id).context(SessionNotFoundSnafu { id })?;
+
+    if commander.send(Command::Extra(command)).await.is_err() {
+        sessions.remove(&id);
+        return SessionNotFoundSnafu { id }.fail();
+    }
+    Ok(())
 }
 
 #[derive(Debug)]
@@ -195,6 +626,15 @@ pub enum LoginSessionCommand {
         submission: Submission,
         responder: OneshotSender<Result<TestSummary, LoginSessionError>>,
     },
+    Draft {
+        test_id: Id,
+        submission: Submission,
+        responder: OneshotSender<Result<(), LoginSessionError>>,
+    },
+    // Sent on shutdown, before the session is cancelled.
+    Finish {
+        responder: OneshotSender<usize>,
+    },
     Query {
         kind: QueryKind,
         responder: OneshotSender<Result<Record, LoginSessionError>>,
@@ -202,6 +642,42 @@ pub enum LoginSessionCommand {
     QueryAll {
         responder: OneshotSender<Result<Vec<Record>, LoginSessionError>>,
     },
+    Certificate {
+        responder: OneshotSender<Result<Certificate, LoginSessionError>>,
+    },
+    Access {
+        question: Id,
+        responder: OneshotSender<Result<(), LoginSessionError>>,
+    },
+    Practice {
+        review: bool,
+        responder: OneshotSender<Result<Id, LoginSessionError>>,
+    },
+    PracticeNext {
+        practice_id: Id,
+        responder: OneshotSender<Result<Option<Question>, LoginSessionError>>,
+    },
+    PracticeAnswer {
+        practice_id: Id,
+        answer: Answer<SubmissionSource>,
+        responder: OneshotSender<Result<PracticeFeedback, LoginSessionError>>,
+    },
+    Adaptive {
+        responder: OneshotSender<Result<Id, LoginSessionError>>,
+    },
+    AdaptiveNext {
+        test_id: Id,
+        responder: OneshotSender<Result<Option<Question>, LoginSessionError>>,
+    },
+    AdaptiveAnswer {
+        test_id: Id,
+        answer: Answer<SubmissionSource>,
+        responder: OneshotSender<Result<AdaptiveProgress, LoginSessionError>>,
+    },
+    AdaptiveFinish {
+        test_id: Id,
+        responder: OneshotSender<Result<AdaptiveSummary, LoginSessionError>>,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -221,8 +697,22 @@ pub enum LoginSessionError {
     SessionNotFound { id: Id },
     #[snafu(display("Could not handle test"))]
     Test { source: TestSessionError },
+    #[snafu(display("Could not handle practice"))]
+    Practice { source: PracticeSessionError },
+    #[snafu(display("Could not handle adaptive test"))]
+    Adaptive { source: AdaptiveSessionError },
+    #[snafu(display("Could not load questions due for review"))]
+    Review { source: ReviewError },
     #[snafu(display("Could not query user's score"))]
     Query { source: ScoreRepositoryError },
+    #[snafu(display("Could not issue certificate"))]
+    Certificate { source: ScoreRepositoryError },
+    #[snafu(display("Could not generate verification code"))]
+    GenerateCode {
+        source: GenerateVerificationCodeError,
+    },
+    #[snafu(display("Question {question} is not part of a running test"))]
+    QuestionNotInTest { question: Id },
 }
 
 #[cfg(test)]
@@ -231,12 +721,17 @@ mod tests {
 
     use chrono::{DateTime, Utc};
     use tokio::sync::mpsc::{self, Receiver as MpscReceiver, Sender as MpscSender};
+    use tokio::time::Duration;
 
+    use crate::domain::entity::answer::{CompletionAnswer, StandardSource};
+    use crate::domain::entity::grade::Grade;
     use crate::domain::entity::id::SequentialIdAllocator;
-    use crate::domain::entity::question::Question;
-    use crate::domain::entity::score::Score;
+    use crate::domain::entity::question::{CompletionQuestion, Question};
+    use crate::domain::entity::score::{Points, Score};
+    use crate::domain::repository::practice::MockPracticeRepository;
     use crate::domain::repository::question::{MockQuestionRepository, SelectCount};
-    use crate::domain::repository::score::MockScoreRepository;
+    use crate::domain::repository::review::{MockReviewRepository, ReviewRepositoryError};
+    use crate::domain::repository::score::{MockScoreRepository, NotPassedSnafu};
     use crate::domain::session::base::Report;
 
     use super::*;
@@ -259,6 +754,30 @@ mod tests {
         assert_eq!(res.score, Score::try_new(100f32).unwrap());
     }
 
+    #[tokio::test(start_paused = true)]
+    async fn login_session_draft_finish() {
+        let (question_repository, score_repository, _) = new_repository();
+        let (mut session, _, _) = new_login_session(question_repository, score_repository).await;
+
+        let user = User::try_new("user").unwrap();
+        session.handle_login(user).unwrap();
+
+        let drafted = session.handle_start().await.unwrap().id();
+        let untouched = session.handle_start().await.unwrap().id();
+        session
+            .handle_draft(drafted, new_submission())
+            .await
+            .unwrap();
+        assert!(matches!(
+            session.handle_draft(3.into(), new_submission()).await,
+            Err(LoginSessionError::SessionNotFound { .. })
+        ));
+
+        assert_eq!(session.handle_finish().await, 1);
+        assert!(!session.questions.contains_key(&drafted));
+        assert!(session.questions.contains_key(&untouched));
+    }
+
     #[tokio::test(start_paused = true)]
     async fn login_session_query() {
         let (question_repository, score_repository, _) = new_repository();
@@ -271,8 +790,11 @@ mod tests {
             session.handle_query(QueryKind::Best).await.unwrap(),
             Record {
                 score: Score::try_new(100f32).unwrap(),
+                points: Points::new(4.0, 4),
+                grade: Some(Grade::new("A", true)),
                 end_time: END_TIME,
                 duration: Duration::from_secs(0),
+                pending: false,
             }
         );
 
@@ -284,12 +806,66 @@ mod tests {
             session.handle_query_all().await.unwrap(),
             vec![Record {
                 score: Score::try_new(100f32).unwrap(),
+                points: Points::new(4.0, 4),
+                grade: Some(Grade::new("A", true)),
                 end_time: END_TIME,
                 duration: Duration::from_secs(0),
+                pending: false,
             }]
         );
     }
 
+    #[tokio::test(start_paused = true)]
+    async fn login_session_certificate() {
+        let (question_repository, _, _) = new_repository();
+        let mut score_repository = MockScoreRepository::new();
+        score_repository
+            .expect_issue_certificate()
+            .returning(|user, code| {
+                ensure!(
+                    user.inner() == "user",
+                    NotPassedSnafu { user: user.clone() }
+                );
+                Ok(Certificate {
+                    code,
+                    user: user.clone(),
+                    score: Score::try_new(100f32).unwrap(),
+                    points: Points::new(4.0, 4),
+                    grade: Grade::new("A", true),
+                    end_time: END_TIME,
+                })
+            });
+        let score_repository: Arc<dyn ScoreRepository> = Arc::new(score_repository);
+
+        let (mut session, _, _) = new_login_session(
+            Arc::clone(&question_repository),
+            Arc::clone(&score_repository),
+        )
+        .await;
+        assert!(matches!(
+            session.handle_certificate().await,
+            Err(LoginSessionError::NotLoggedIn),
+        ));
+
+        session
+            .handle_login(User::try_new("user").unwrap())
+            .unwrap();
+        let res = session.handle_certificate().await.unwrap();
+        assert_eq!(res.user, User::try_new("user").unwrap());
+        assert_eq!(res.grade, Grade::new("A", true));
+
+        let (mut session, _, _) = new_login_session(question_repository, score_repository).await;
+        session
+            .handle_login(User::try_new("user2").unwrap())
+            .unwrap();
+        assert!(matches!(
+            session.handle_certificate().await,
+            Err(LoginSessionError::Certificate {
+                source: ScoreRepositoryError::NotPassed { .. }
+            }),
+        ));
+    }
+
     #[tokio::test(start_paused = true)]
     async fn login_session_already_logged_in() {
         let (question_repository, score_repository, _) = new_repository();
@@ -378,6 +954,194 @@ mod tests {
         ));
     }
 
+    #[tokio::test(start_paused = true)]
+    async fn login_session_access() {
+        let question: Question = CompletionQuestion::try_new(
+            3.into(),
+            "content".into(),
+            CompletionAnswer::<StandardSource>::try_new("answer").unwrap(),
+        )
+        .unwrap()
+        .into();
+        let mut question_repository = MockQuestionRepository::new();
+        let selected = question.clone();
+        question_repository
+            .expect_select_questions()
+            .returning(move |_| Ok(vec![selected.clone()]));
+        question_repository
+            .expect_select_questions_by_revision()
+            .returning(move |_| Ok(vec![question.clone()]));
+        let (_, score_repository, _) = new_repository();
+        let (mut session, _, _) =
+            new_login_session(Arc::new(question_repository), score_repository).await;
+
+        session
+            .handle_login(User::try_new("user").unwrap())
+            .unwrap();
+        let test = session.handle_start().await.unwrap();
+
+        assert!(session.handle_access(3.into()).is_ok());
+        assert!(matches!(
+            session.handle_access(4.into()),
+            Err(LoginSessionError::QuestionNotInTest { .. }),
+        ));
+
+        session
+            .handle_submit(test.id(), new_submission())
+            .await
+            .unwrap();
+        assert!(matches!(
+            session.handle_access(3.into()),
+            Err(LoginSessionError::QuestionNotInTest { .. }),
+        ));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn login_session_practice() {
+        let question: Question = CompletionQuestion::try_new(
+            3.into(),
+            "content".into(),
+            CompletionAnswer::<StandardSource>::try_new("answer").unwrap(),
+        )
+        .unwrap()
+        .into();
+        let mut question_repository = MockQuestionRepository::new();
+        let selected = question.clone();
+        question_repository
+            .expect_select_questions()
+            .times(1)
+            .returning(move |_| Ok(vec![selected.clone()]));
+        let (_, score_repository, _) = new_repository();
+        let mut practice_repository = MockPracticeRepository::new();
+        practice_repository
+            .expect_insert_attempt()
+            .times(1)
+            .returning(|_, _| Ok(()));
+        // Review cards failing to load do not fail an answer that is already saved.
+        let mut review_repository = MockReviewRepository::new();
+        review_repository
+            .expect_find_cards()
+            .times(1)
+            .returning(|_, _| {
+                Err(ReviewRepositoryError::Unknown {
+                    message: "Database is unavailable".to_owned(),
+                    source: None,
+                })
+            });
+
+        let id_allocator = Arc::new(SequentialIdAllocator::new());
+        let (_commander, command) = mpsc::channel(4);
+        let (reporter, _report) = mpsc::channel(4);
+        let base = SessionBase::new(
+            id_allocator,
+            Arc::new(SessionConfig::default()),
+            command,
+            reporter,
+        )
+        .await;
+        let mut session = LoginSession::new(
+            base,
+            Arc::new(question_repository),
+            score_repository,
+            Arc::new(practice_repository),
+            ReviewService::new(Arc::new(review_repository)),
+        );
+
+        assert!(matches!(
+            session.handle_practice(false).await,
+            Err(LoginSessionError::NotLoggedIn),
+        ));
+        session
+            .handle_login(User::try_new("user").unwrap())
+            .unwrap();
+        let practice_id = session.handle_practice(false).await.unwrap();
+
+        let served = session.handle_practice_next(practice_id).await.unwrap();
+        assert_eq!(served, Some(question));
+        assert!(session.handle_access(3.into()).is_ok());
+
+        let feedback = session
+            .handle_practice_answer(
+                practice_id,
+                CompletionAnswer::<SubmissionSource>::try_new("answer")
+                    .unwrap()
+                    .into(),
+            )
+            .await
+            .unwrap();
+        assert!(feedback.correct);
+        assert_eq!(feedback.remaining, 0);
+
+        assert_eq!(
+            session.handle_practice_next(practice_id).await.unwrap(),
+            None
+        );
+        assert!(matches!(
+            session.handle_practice_next(0.into()).await,
+            Err(LoginSessionError::SessionNotFound { .. }),
+        ));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn login_session_adaptive() {
+        let question: Question = CompletionQuestion::try_new(
+            3.into(),
+            "content".into(),
+            CompletionAnswer::<StandardSource>::try_new("answer").unwrap(),
+        )
+        .unwrap()
+        .into();
+        let mut question_repository = MockQuestionRepository::new();
+        let selected = question.clone();
+        question_repository
+            .expect_select_questions()
+            .times(1)
+            .returning(move |_| Ok(vec![selected.clone()]));
+        let mut score_repository = MockScoreRepository::new();
+        score_repository
+            .expect_query_item_statistics()
+            .times(1)
+            .returning(|_| Ok(HashMap::new()));
+        score_repository
+            .expect_insert()
+            .times(1)
+            .returning(|_, _, _, _, _, _, _| Ok(()));
+        let (mut session, _, _) =
+            new_login_session(Arc::new(question_repository), Arc::new(score_repository)).await;
+
+        session
+            .handle_login(User::try_new("user").unwrap())
+            .unwrap();
+        let test_id = session.handle_adaptive().await.unwrap();
+
+        let served = session.handle_adaptive_next(test_id).await.unwrap();
+        assert_eq!(served, Some(question));
+        assert!(session.handle_access(3.into()).is_ok());
+
+        session
+            .handle_adaptive_answer(
+                test_id,
+                CompletionAnswer::<SubmissionSource>::try_new("answer")
+                    .unwrap()
+                    .into(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(session.handle_adaptive_next(test_id).await.unwrap(), None);
+
+        let summary = session.handle_adaptive_finish(test_id).await.unwrap();
+        assert_eq!(summary.score.inner(), 100.0);
+        assert!(summary.ability > 0.0);
+        assert!(matches!(
+            session.handle_access(3.into()),
+            Err(LoginSessionError::QuestionNotInTest { .. }),
+        ));
+        assert!(matches!(
+            session.handle_adaptive_next(test_id).await,
+            Err(LoginSessionError::SessionNotFound { .. }),
+        ));
+    }
+
     async fn new_login_session(
         question_repository: Arc<dyn QuestionRepository>,
         score_repository: Arc<dyn ScoreRepository>,
@@ -389,11 +1153,34 @@ mod tests {
         let id_allocator = Arc::new(SequentialIdAllocator::new());
         let (commander, command) = mpsc::channel(4);
         let (reporter, report) = mpsc::channel(4);
-        let base = SessionBase::new(id_allocator, command, reporter).await;
-        let session = LoginSession::new(base, question_repository, score_repository);
+        let base = SessionBase::new(
+            id_allocator,
+            Arc::new(SessionConfig::default()),
+            command,
+            reporter,
+        )
+        .await;
+        let session = LoginSession::new(
+            base,
+            question_repository,
+            score_repository,
+            Arc::new(MockPracticeRepository::new()),
+            new_review_service(),
+        );
         (session, commander, report)
     }
 
+    fn new_review_service() -> ReviewService {
+        let mut review_repository = MockReviewRepository::new();
+        review_repository
+            .expect_find_cards()
+            .returning(|_, _| Ok(Vec::new()));
+        review_repository
+            .expect_save_cards()
+            .returning(|_, _| Ok(()));
+        ReviewService::new(Arc::new(review_repository))
+    }
+
     fn new_repository() -> (
         Arc<dyn QuestionRepository>,
         Arc<dyn ScoreRepository>,
@@ -404,31 +1191,40 @@ mod tests {
             .expect_select_questions()
             .returning(|_| Ok(new_questions()));
         question_repository
-            .expect_select_questions_by_id()
+            .expect_select_questions_by_revision()
             .returning(|_| Ok(new_questions()));
         let mut score_repository = MockScoreRepository::new();
         score_repository
             .expect_insert()
-            .returning(|_, _, _, _| Ok(()));
+            .returning(|_, _, _, _, _, _, _| Ok(()));
         score_repository.expect_query_latest().returning(|_| {
             Ok(Record {
                 score: Score::try_new(100f32).unwrap(),
+                points: Points::new(4.0, 4),
+                grade: Some(Grade::new("A", true)),
                 end_time: Utc::now(),
                 duration: Duration::from_secs(0),
+                pending: false,
             })
         });
         score_repository.expect_query_best().returning(|_| {
             Ok(Record {
                 score: Score::try_new(100f32).unwrap(),
+                points: Points::new(4.0, 4),
+                grade: Some(Grade::new("A", true)),
                 end_time: END_TIME,
                 duration: Duration::from_secs(0),
+                pending: false,
             })
         });
         score_repository.expect_query_all_sorted().returning(|_| {
             Ok(vec![Record {
                 score: Score::try_new(100f32).unwrap(),
+                points: Points::new(4.0, 4),
+                grade: Some(Grade::new("A", true)),
                 end_time: END_TIME,
                 duration: Duration::from_secs(0),
+                pending: false,
             }])
         });
 
@@ -439,6 +1235,11 @@ mod tests {
                 single_selection: 0,
                 multiple_selection: 0,
                 completion: 0,
+                true_false: 0,
+                ordering: 0,
+                matching: 0,
+                essay: 0,
+                cloze: 0,
             },
         )
     }
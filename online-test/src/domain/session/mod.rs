@@ -1,3 +1,6 @@
+pub mod adaptive;
 pub mod base;
+pub mod config;
 pub mod login;
+pub mod practice;
 pub mod test;
@@ -0,0 +1,388 @@
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use chrono::Utc;
+use snafu::prelude::*;
+use tokio::sync::oneshot::Sender as OneshotSender;
+
+use crate::domain::entity::answer::{Answer, StandardSource, SubmissionSource};
+use crate::domain::entity::content::Content;
+use crate::domain::entity::id::Id;
+use crate::domain::entity::question::{AbstractQuestion, Question};
+use crate::domain::entity::user::User;
+use crate::domain::repository::practice::{
+    PracticeAttempt, PracticeRepository, PracticeRepositoryError,
+};
+use crate::domain::repository::question::{QuestionRepository, QuestionRepositoryError};
+use crate::domain::session::base::{NoneSession, Session, SessionBase};
+use crate::domain::session::config::{SessionConfig, Timeouts};
+
+#[derive(Debug)]
+pub struct PracticeSession {
+    base: SessionBase<Self>,
+    question_repository: Arc<dyn QuestionRepository>,
+    practice_repository: Arc<dyn PracticeRepository>,
+    user: User,
+    // Due questions of a review, or `None` for questions picked at random.
+    due: Option<Vec<Id>>,
+    // Loaded when the first question is requested.
+    queue: Option<VecDeque<Question>>,
+    current: Option<Question>,
+    tally: PracticeTally,
+}
+
+impl PracticeSession {
+    pub const REVIEW_LENGTH: usize = 20;
+
+    pub fn new(
+        base: SessionBase<Self>,
+        question_repository: Arc<dyn QuestionRepository>,
+        practice_repository: Arc<dyn PracticeRepository>,
+        user: User,
+    ) -> Self {
+        Self {
+            base,
+            question_repository,
+            practice_repository,
+            user,
+            due: None,
+            queue: None,
+            current: None,
+            tally: PracticeTally::default(),
+        }
+    }
+
+    // Serves only the given questions, at most `REVIEW_LENGTH` of them in order.
+    pub fn review(
+        base: SessionBase<Self>,
+        question_repository: Arc<dyn QuestionRepository>,
+        practice_repository: Arc<dyn PracticeRepository>,
+        user: User,
+        mut due: Vec<Id>,
+    ) -> Self {
+        due.truncate(Self::REVIEW_LENGTH);
+
+        Self {
+            due: Some(due),
+            ..Self::new(base, question_repository, practice_repository, user)
+        }
+    }
+
+    // Serves the current question again until it is answered. `None` ends the practice.
+    async fn handle_next(&mut self) -> Result<Option<Question>, PracticeSessionError> {
+        if self.current.is_some() {
+            return Ok(self.current.clone());
+        }
+
+        if self.queue.is_none() {
+            let questions = match self.due.take() {
+                // Questions retired since they were listed are skipped.
+                Some(due) => {
+                    self.question_repository
+                        .select_active_questions_by_id(due)
+                        .await
+                }
+                None => {
+                    self.question_repository
+                        .select_questions(self.base.config.practice_questions.clone())
+                        .await
+                }
+            }
+            .context(GenerateSnafu)?;
+
+            // Answers left to teachers could never be checked on the spot.
+            let mut rng = fastrand::Rng::new();
+            self.queue = Some(
+                questions
+                    .into_iter()
+                    .filter(|q| !q.manual())
+                    .map(|q| q.shuffled(&mut rng))
+                    .collect(),
+            );
+        }
+
+        self.current = self.queue.as_mut().and_then(VecDeque::pop_front);
+        Ok(self.current.clone())
+    }
+
+    async fn handle_answer(
+        &mut self,
+        answer: Answer<SubmissionSource>,
+    ) -> Result<PracticeFeedback, PracticeSessionError> {
+        let question = self.current.as_ref().context(NoQuestionSnafu)?;
+        let correct = question.check(&answer);
+
+        self.practice_repository
+            .insert_attempt(
+                self.user.clone(),
+                PracticeAttempt {
+                    question: question.id(),
+                    revision: question.revision(),
+                    correct,
+                    answered_at: Utc::now(),
+                },
+            )
+            .await
+            .context(SaveAttemptSnafu)?;
+
+        self.tally.answered += 1;
+        self.tally.correct += usize::from(correct);
+
+        let feedback = PracticeFeedback {
+            question: question.id(),
+            correct,
+            answer: question.answer(),
+            explanation: question.explanation().cloned(),
+            tally: self.tally,
+            remaining: self.queue.as_ref().map_or(0, VecDeque::len),
+        };
+        self.current = None;
+
+        Ok(feedback)
+    }
+}
+
+#[async_trait::async_trait]
+impl Session for PracticeSession {
+    type ExtraCommand = PracticeSessionCommand;
+    type SubSession = NoneSession;
+
+    fn timeouts(config: &SessionConfig) -> &Timeouts {
+        &config.practice
+    }
+
+    fn base(&self) -> &SessionBase<Self> {
+        &self.base
+    }
+
+    fn base_mut(&mut self) -> &mut SessionBase<Self> {
+        &mut self.base
+    }
+
+    // Practice has no time limit, so the session only expires once left idle.
+    async fn handle(&mut self, command: Self::ExtraCommand) {
+        self.reset_expire();
+
+        match command {
+            Self::ExtraCommand::Next { responder } => {
+                let res = self.handle_next().await;
+                let _ = responder.send(res);
+            }
+            Self::ExtraCommand::Answer { answer, responder } => {
+                let res = self.handle_answer(answer).await;
+                let _ = responder.send(res);
+            }
+        }
+    }
+
+    async fn finalize(&mut self) {}
+}
+
+#[derive(Debug)]
+pub enum PracticeSessionCommand {
+    Next {
+        responder: OneshotSender<Result<Option<Question>, PracticeSessionError>>,
+    },
+    Answer {
+        answer: Answer<SubmissionSource>,
+        responder: OneshotSender<Result<PracticeFeedback, PracticeSessionError>>,
+    },
+}
+
+#[derive(Debug, Snafu)]
+#[non_exhaustive]
+pub enum PracticeSessionError {
+    #[snafu(display("Could not select practice questions"))]
+    Generate { source: QuestionRepositoryError },
+    #[snafu(display("Could not save practice attempt"))]
+    SaveAttempt { source: PracticeRepositoryError },
+    #[snafu(display("Could not answer before a question is served"))]
+    NoQuestion,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PracticeTally {
+    pub answered: usize,
+    pub correct: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PracticeFeedback {
+    pub question: Id,
+    pub correct: bool,
+    pub answer: Answer<StandardSource>,
+    pub explanation: Option<Content>,
+    pub tally: PracticeTally,
+    pub remaining: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::mpsc::{self, Receiver as MpscReceiver, Sender as MpscSender};
+
+    use crate::domain::entity::answer::{CompletionAnswer, TrueFalseAnswer};
+    use crate::domain::entity::id::SequentialIdAllocator;
+    use crate::domain::entity::question::{CompletionQuestion, TrueFalseQuestion};
+    use crate::domain::repository::practice::MockPracticeRepository;
+    use crate::domain::repository::question::MockQuestionRepository;
+    use crate::domain::session::base::{Command, Report};
+
+    use super::*;
+
+    #[tokio::test(start_paused = true)]
+    async fn practice_next_answer() {
+        let (mut session, _commander, _report) = new_practice_session(2).await;
+        let questions = new_questions();
+
+        let question = session.handle_next().await.unwrap();
+        assert_eq!(question.as_ref(), Some(&questions[0]));
+
+        let feedback = session
+            .handle_answer(
+                TrueFalseAnswer::<SubmissionSource>::try_new(true)
+                    .unwrap()
+                    .into(),
+            )
+            .await
+            .unwrap();
+        assert!(feedback.correct);
+        assert_eq!(feedback.answer, questions[0].answer());
+        assert_eq!(feedback.explanation, Some("Because it is".into()));
+        assert_eq!(
+            feedback.tally,
+            PracticeTally {
+                answered: 1,
+                correct: 1
+            }
+        );
+        assert_eq!(feedback.remaining, 1);
+
+        let question = session.handle_next().await.unwrap();
+        assert_eq!(question.as_ref(), Some(&questions[1]));
+        let question = session.handle_next().await.unwrap();
+        assert_eq!(question.as_ref(), Some(&questions[1]));
+
+        let feedback = session
+            .handle_answer(
+                CompletionAnswer::<SubmissionSource>::try_new("wrong")
+                    .unwrap()
+                    .into(),
+            )
+            .await
+            .unwrap();
+        assert!(!feedback.correct);
+        assert_eq!(
+            feedback.tally,
+            PracticeTally {
+                answered: 2,
+                correct: 1
+            }
+        );
+
+        assert_eq!(session.handle_next().await.unwrap(), None);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn practice_no_question() {
+        let (mut session, _commander, _report) = new_practice_session(0).await;
+
+        assert!(matches!(
+            session
+                .handle_answer(
+                    TrueFalseAnswer::<SubmissionSource>::try_new(true)
+                        .unwrap()
+                        .into(),
+                )
+                .await,
+            Err(PracticeSessionError::NoQuestion),
+        ));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn practice_review() {
+        let mut question_repository = MockQuestionRepository::new();
+        question_repository
+            .expect_select_active_questions_by_id()
+            .times(1)
+            .withf(|id| id == &vec![1.into(), 9.into()])
+            .returning(|_| Ok(new_questions().split_off(1)));
+
+        let id_allocator = Arc::new(SequentialIdAllocator::new());
+        let (_commander, command) = mpsc::channel(4);
+        let (reporter, _report) = mpsc::channel(4);
+        let base = SessionBase::new(
+            id_allocator,
+            Arc::new(SessionConfig::default()),
+            command,
+            reporter,
+        )
+        .await;
+        let mut session = PracticeSession::review(
+            base,
+            Arc::new(question_repository),
+            Arc::new(MockPracticeRepository::new()),
+            User::try_new("user").unwrap(),
+            vec![1.into(), 9.into()],
+        );
+
+        let question = session.handle_next().await.unwrap().unwrap();
+        assert_eq!(question.id(), 1.into());
+    }
+
+    async fn new_practice_session(
+        attempts: usize,
+    ) -> (
+        PracticeSession,
+        MpscSender<Command<PracticeSession>>,
+        MpscReceiver<Report>,
+    ) {
+        let mut question_repository = MockQuestionRepository::new();
+        question_repository
+            .expect_select_questions()
+            .times(..=1)
+            .returning(|_| Ok(new_questions()));
+        let mut practice_repository = MockPracticeRepository::new();
+        practice_repository
+            .expect_insert_attempt()
+            .times(attempts)
+            .returning(|_, _| Ok(()));
+
+        let id_allocator = Arc::new(SequentialIdAllocator::new());
+        let (commander, command) = mpsc::channel(4);
+        let (reporter, report) = mpsc::channel(4);
+        let base = SessionBase::new(
+            id_allocator,
+            Arc::new(SessionConfig::default()),
+            command,
+            reporter,
+        )
+        .await;
+        let session = PracticeSession::new(
+            base,
+            Arc::new(question_repository),
+            Arc::new(practice_repository),
+            User::try_new("user").unwrap(),
+        );
+        (session, commander, report)
+    }
+
+    fn new_questions() -> Vec<Question> {
+        vec![
+            TrueFalseQuestion::try_new(
+                0.into(),
+                "0. True-false".into(),
+                TrueFalseAnswer::<StandardSource>::try_new(true).unwrap(),
+            )
+            .unwrap()
+            .with_explanation(Some("Because it is".into()))
+            .into(),
+            CompletionQuestion::try_new(
+                1.into(),
+                "1. Completion".into(),
+                CompletionAnswer::<StandardSource>::try_new("answer").unwrap(),
+            )
+            .unwrap()
+            .into(),
+        ]
+    }
+}
@@ -5,24 +5,28 @@ use snafu::prelude::*;
 use tokio::sync::oneshot::Sender as OneshotSender;
 use tokio::time::Duration;
 
+use crate::domain::entity::grade::Grade;
 use crate::domain::entity::id::Id;
-use crate::domain::entity::question::AbstractQuestion;
-use crate::domain::entity::score::Score;
-use crate::domain::entity::test::{Submission, Test};
+use crate::domain::entity::question::{AbstractQuestion, Question};
+use crate::domain::entity::score::{Points, Score};
+use crate::domain::entity::test::{Blueprint, ItemFeedback, Submission, Test};
 use crate::domain::entity::user::User;
-use crate::domain::repository::question::{
-    QuestionRepository, QuestionRepositoryError, SelectCount,
-};
-use crate::domain::repository::score::{ScoreRepository, ScoreRepositoryError};
+use crate::domain::repository::question::{QuestionRepository, QuestionRepositoryError};
+use crate::domain::repository::score::{AttemptKind, ScoreRepository, ScoreRepositoryError};
 use crate::domain::session::base::{NoneSession, Session, SessionBase};
+use crate::domain::session::config::{SessionConfig, Timeouts};
 
 #[derive(Debug)]
 pub struct TestSession {
     base: SessionBase<Self>,
     question_repository: Arc<dyn QuestionRepository>,
     score_repository: Arc<dyn ScoreRepository>,
-    question_ids: Option<Vec<Id>>,
+    revision_ids: Option<Vec<Id>>,
+    // Shuffles the questions the same way when the test is generated and when it is graded.
+    seed: u64,
     start_time: Option<DateTime<Utc>>,
+    // Answers saved while the test is still open, graded if it is finished on shutdown.
+    draft: Option<Submission>,
 }
 
 impl TestSession {
@@ -35,28 +39,43 @@ impl TestSession {
             base,
             question_repository,
             score_repository,
-            question_ids: None,
+            revision_ids: None,
+            seed: fastrand::u64(..),
             start_time: None,
+            draft: None,
         }
     }
 
-    async fn handle_generate(&mut self) -> Result<Test, TestSessionError> {
-        const SELECT_COUNT: SelectCount = SelectCount {
-            single_selection: 8,
-            multiple_selection: 8,
-            completion: 12,
-        };
+    pub fn blueprint(config: &SessionConfig) -> Blueprint {
+        Blueprint {
+            weights: config.test_points,
+            penalty: config.test_penalty,
+            scale: config.grade_scale.clone(),
+        }
+    }
+
+    fn shuffled(&self, questions: Vec<Question>) -> Vec<Question> {
+        questions
+            .into_iter()
+            .map(|q| {
+                let seed = self.seed ^ q.id().inner() as u64;
+                q.shuffled(&mut fastrand::Rng::with_seed(seed))
+            })
+            .collect()
+    }
 
+    async fn handle_generate(&mut self) -> Result<Test, TestSessionError> {
+        let select_count = self.base.config.test_questions.clone();
         let questions = self
             .question_repository
-            .select_questions(SELECT_COUNT.clone())
+            .select_questions(select_count)
             .await
             .context(GenerateSnafu)?;
 
-        let question_ids = questions.iter().map(|q| q.id()).collect();
-        self.question_ids = Some(question_ids);
+        let revision_ids = questions.iter().map(|q| q.revision()).collect();
+        self.revision_ids = Some(revision_ids);
         self.start_time = Some(Utc::now());
-        Ok(Test::new(self.id(), questions))
+        Ok(Test::new(self.id(), self.shuffled(questions)))
     }
 
     async fn handle_submit(
@@ -73,44 +92,95 @@ impl TestSession {
             }
         );
 
-        let Some(question_ids) = self.question_ids.take() else {
+        let Some(revision_ids) = self.revision_ids.take() else {
             return NotStartedSnafu.fail();
         };
+        self.draft = None;
 
         let questions = self
             .question_repository
-            .select_questions_by_id(question_ids)
+            .select_questions_by_revision(revision_ids)
             .await
             .context(LoadQuestionsSnafu)?;
 
-        let test = Test::new(test_id, questions);
-        let score = test.grade(&submission);
+        let test = Test::new(test_id, self.shuffled(questions))
+            .with_blueprint(Self::blueprint(&self.base.config));
+        let items = test.grade_items(&submission);
+        let points = Test::points(&items);
+        let grade = test.evaluate(&items);
+        let feedback = test.feedback(&items);
 
         let end_time = Utc::now();
         let duration = self
             .start_time
             .take()
-            .map_or(Self::SESSION_EXPIRE_TIMEOUT, |start_time| {
+            .map_or(Self::timeouts(&self.base.config).expire, |start_time| {
                 (end_time - start_time).to_std().unwrap_or_default()
             });
 
         self.score_repository
-            .insert(user, score, end_time, duration)
+            .insert(
+                user,
+                AttemptKind::Test,
+                points,
+                grade.clone(),
+                end_time,
+                duration,
+                items,
+            )
             .await
             .context(SaveScoreSnafu)?;
 
-        Ok(TestSummary { score, duration })
+        Ok(TestSummary {
+            score: points.score(),
+            points,
+            grade,
+            duration,
+            feedback,
+        })
+    }
+
+    fn handle_save_draft(
+        &mut self,
+        test_id: Id,
+        submission: Submission,
+    ) -> Result<(), TestSessionError> {
+        ensure!(
+            test_id == self.id(),
+            TestMismatchedSnafu {
+                expected: self.id(),
+                actual: test_id
+            }
+        );
+        ensure!(self.revision_ids.is_some(), NotStartedSnafu);
+
+        self.draft = Some(submission);
+        Ok(())
+    }
+
+    // Submits the last draft on behalf of the user. Tests without a draft are left ungraded.
+    async fn handle_finish(&mut self, user: User) -> Result<Option<TestSummary>, TestSessionError> {
+        if self.revision_ids.is_none() {
+            return Ok(None);
+        }
+        let Some(draft) = self.draft.take() else {
+            return Ok(None);
+        };
+
+        let id = self.id();
+        self.handle_submit(user, id, draft).await.map(Some)
     }
 }
 
 #[async_trait::async_trait]
 impl Session for TestSession {
-    const SESSION_EXPIRE_TIMEOUT: Duration = Duration::from_secs(60 * 30 + 30);
-    const CANCEL_AWAIT_TIMEOUT: Duration = Duration::from_secs(5);
-
     type ExtraCommand = TestSessionCommand;
     type SubSession = NoneSession;
 
+    fn timeouts(config: &SessionConfig) -> &Timeouts {
+        &config.test
+    }
+
     fn base(&self) -> &SessionBase<Self> {
         &self.base
     }
@@ -135,6 +205,19 @@ impl Session for TestSession {
                 let _ = responder.send(res);
                 self.request_exit();
             }
+            Self::ExtraCommand::SaveDraft {
+                test_id,
+                submission,
+                responder,
+            } => {
+                let res = self.handle_save_draft(test_id, submission);
+                let _ = responder.send(res);
+            }
+            Self::ExtraCommand::Finish { user, responder } => {
+                let res = self.handle_finish(user).await;
+                let _ = responder.send(res);
+                self.request_exit();
+            }
         }
     }
 
@@ -152,6 +235,15 @@ pub enum TestSessionCommand {
         submission: Submission,
         responder: OneshotSender<Result<TestSummary, TestSessionError>>,
     },
+    SaveDraft {
+        test_id: Id,
+        submission: Submission,
+        responder: OneshotSender<Result<(), TestSessionError>>,
+    },
+    Finish {
+        user: User,
+        responder: OneshotSender<Result<Option<TestSummary>, TestSessionError>>,
+    },
 }
 
 #[derive(Debug, Snafu)]
@@ -172,7 +264,10 @@ pub enum TestSessionError {
 #[derive(Debug, Clone, PartialEq)]
 pub struct TestSummary {
     pub score: Score,
+    pub points: Points,
+    pub grade: Option<Grade>,
     pub duration: Duration,
+    pub feedback: Vec<ItemFeedback>,
 }
 
 #[cfg(test)]
@@ -180,14 +275,16 @@ mod tests {
     use tokio::sync::mpsc::{self, Receiver as MpscReceiver, Sender as MpscSender};
 
     use crate::domain::entity::answer::{
-        CompletionAnswer, MultipleSelectionAnswer, SingleSelectionAnswer, StandardSource,
-        SubmissionSource,
+        CompletionAnswer, MultipleSelectionAnswer, OrderingAnswer, SingleSelectionAnswer,
+        StandardSource, SubmissionSource,
     };
+    use crate::domain::entity::content::Content;
     use crate::domain::entity::id::SequentialIdAllocator;
     use crate::domain::entity::question::{
-        CompletionQuestion, MultipleSelectionQuestion, Question, SingleSelectionQuestion,
+        CompletionQuestion, MultipleSelectionQuestion, OrderingQuestion, SingleSelectionQuestion,
     };
-    use crate::domain::repository::question::MockQuestionRepository;
+    use crate::domain::entity::test::{Penalty, Weights};
+    use crate::domain::repository::question::{MockQuestionRepository, SelectCount};
     use crate::domain::repository::score::MockScoreRepository;
     use crate::domain::session::base::{Command, Report};
 
@@ -195,9 +292,9 @@ mod tests {
 
     #[tokio::test(start_paused = true)]
     async fn handle_generate_submit() {
-        let (question_repository, score_repository, _select_count) = new_repository();
+        let (question_repository, score_repository, select_count) = new_repository();
         let (mut session, _commander, _report) =
-            new_test_session(question_repository, score_repository).await;
+            new_test_session(question_repository, score_repository, select_count).await;
         let id = session.id();
 
         let actual = session.handle_generate().await.unwrap();
@@ -208,14 +305,136 @@ mod tests {
             .handle_submit(User::try_new("user").unwrap(), id, new_submission())
             .await
             .unwrap();
-        assert_eq!(actual.score, Score::try_new(75f32).unwrap());
+        // Every question is worth 1 point, and wrong answers lose nothing, as neither points nor
+        // negative marking are configured.
+        let points = Points::new(3.0, 4);
+        assert_eq!(actual.points, points);
+        assert_eq!(actual.score, points.score());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn handle_submit_configured_scoring() {
+        let (question_repository, score_repository, test_questions) = new_repository();
+        let config = Arc::new(SessionConfig {
+            test_questions,
+            test_points: Weights {
+                multiple_selection: 2,
+                completion: 2,
+                ..Weights::UNIFORM
+            },
+            test_penalty: Penalty {
+                single_selection: 1.0,
+                ..Penalty::NONE
+            },
+            ..SessionConfig::default()
+        });
+        let (_commander, command) = mpsc::channel(4);
+        let (reporter, _report) = mpsc::channel(4);
+        let id_allocator = Arc::new(SequentialIdAllocator::new());
+        let base = SessionBase::new(id_allocator, config, command, reporter).await;
+        let mut session = TestSession::new(base, question_repository, score_repository);
+        let id = session.id();
+
+        session.handle_generate().await.unwrap();
+        let actual = session
+            .handle_submit(User::try_new("user").unwrap(), id, new_submission())
+            .await
+            .unwrap();
+        assert_eq!(actual.points, Points::new(5.0, 6).with_penalty(1.0));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn handle_ordering_shuffled() {
+        let items: Vec<Content> = vec!["item a".into(), "item b".into(), "item c".into()];
+        let questions = {
+            let items = items.clone();
+            move || -> Vec<Question> {
+                vec![OrderingQuestion::try_new(
+                    0.into(),
+                    "0. Ordering".into(),
+                    items.clone(),
+                    OrderingAnswer::<StandardSource>::try_new(vec![0, 1, 2]).unwrap(),
+                )
+                .unwrap()
+                .into()]
+            }
+        };
+
+        let mut question_repository = MockQuestionRepository::new();
+        let generated = questions.clone();
+        question_repository
+            .expect_select_questions()
+            .returning(move |_| Ok(generated()));
+        question_repository
+            .expect_select_questions_by_revision()
+            .returning(move |_| Ok(questions()));
+        let mut score_repository = MockScoreRepository::new();
+        score_repository
+            .expect_insert()
+            .returning(|_, _, _, _, _, _, _| Ok(()));
+        let question_repository: Arc<dyn QuestionRepository> = Arc::new(question_repository);
+        let score_repository: Arc<dyn ScoreRepository> = Arc::new(score_repository);
+
+        let select_count = SelectCount {
+            single_selection: 0,
+            multiple_selection: 0,
+            completion: 0,
+            true_false: 0,
+            ordering: 1,
+            matching: 0,
+            essay: 0,
+            cloze: 0,
+        };
+        let submit = |answer: Vec<u32>| {
+            Submission::new(
+                [(
+                    0.into(),
+                    OrderingAnswer::<SubmissionSource>::try_new(answer)
+                        .unwrap()
+                        .into(),
+                )]
+                .into(),
+            )
+        };
+
+        // Items submitted in the order they were served are never correct.
+        let (mut session, _commander, _report) = new_test_session(
+            question_repository.clone(),
+            score_repository.clone(),
+            select_count.clone(),
+        )
+        .await;
+        let id = session.id();
+        let test = session.handle_generate().await.unwrap();
+        assert_ne!(test.questions()[0].choices(), items);
+        let summary = session
+            .handle_submit(User::try_new("user").unwrap(), id, submit(vec![0, 1, 2]))
+            .await
+            .unwrap();
+        assert!(!summary.feedback[0].correct);
+
+        // Items put back in their correct order are graded against the order they were served.
+        let (mut session, _commander, _report) =
+            new_test_session(question_repository, score_repository, select_count).await;
+        let id = session.id();
+        let test = session.handle_generate().await.unwrap();
+        let served = test.questions()[0].choices();
+        let answer = items
+            .iter()
+            .map(|item| served.iter().position(|s| s == item).unwrap() as u32)
+            .collect();
+        let summary = session
+            .handle_submit(User::try_new("user").unwrap(), id, submit(answer))
+            .await
+            .unwrap();
+        assert!(summary.feedback[0].correct);
     }
 
     #[tokio::test(start_paused = true)]
     async fn handle_not_started() {
-        let (question_repository, score_repository, _select_count) = new_repository();
+        let (question_repository, score_repository, select_count) = new_repository();
         let (mut session, _commander, _report) =
-            new_test_session(question_repository, score_repository).await;
+            new_test_session(question_repository, score_repository, select_count).await;
         let id = session.id();
 
         assert!(matches!(
@@ -228,9 +447,9 @@ mod tests {
 
     #[tokio::test(start_paused = true)]
     async fn handle_test_mismatched() {
-        let (question_repository, score_repository, _select_count) = new_repository();
+        let (question_repository, score_repository, select_count) = new_repository();
         let (mut session, _commander, _report) =
-            new_test_session(question_repository, score_repository).await;
+            new_test_session(question_repository, score_repository, select_count).await;
         let id = session.id();
 
         let actual = session.handle_generate().await.unwrap();
@@ -251,37 +470,73 @@ mod tests {
         }
     }
 
+    #[tokio::test(start_paused = true)]
+    async fn handle_finish_draft() {
+        let (question_repository, score_repository, select_count) = new_repository();
+        let (mut session, _commander, _report) =
+            new_test_session(question_repository, score_repository, select_count).await;
+        let id = session.id();
+        let user = User::try_new("user").unwrap();
+
+        assert!(matches!(
+            session.handle_save_draft(id, new_submission()),
+            Err(TestSessionError::NotStarted),
+        ));
+        assert_eq!(session.handle_finish(user.clone()).await.unwrap(), None);
+
+        session.handle_generate().await.unwrap();
+        assert_eq!(session.handle_finish(user.clone()).await.unwrap(), None);
+
+        session.handle_save_draft(id, new_submission()).unwrap();
+        let actual = session.handle_finish(user.clone()).await.unwrap().unwrap();
+        assert_eq!(actual.points, Points::new(3.0, 4));
+
+        // The draft is graded only once.
+        assert_eq!(session.handle_finish(user).await.unwrap(), None);
+    }
+
     fn new_repository() -> (
         Arc<dyn QuestionRepository>,
         Arc<dyn ScoreRepository>,
         SelectCount,
     ) {
+        let select_count = SelectCount {
+            single_selection: 2,
+            multiple_selection: 1,
+            completion: 1,
+            true_false: 0,
+            ordering: 0,
+            matching: 0,
+            essay: 0,
+            cloze: 0,
+        };
+
+        // Questions are only served for the count the session is configured with.
+        let expected = select_count.clone();
         let mut question_repository = MockQuestionRepository::new();
         question_repository
             .expect_select_questions()
+            .withf(move |select_count| *select_count == expected)
             .returning(|_| Ok(new_questions()));
         question_repository
-            .expect_select_questions_by_id()
+            .expect_select_questions_by_revision()
             .returning(|_| Ok(new_questions()));
         let mut score_repository = MockScoreRepository::new();
         score_repository
             .expect_insert()
-            .returning(|_, _, _, _| Ok(()));
+            .returning(|_, _, _, _, _, _, _| Ok(()));
 
         (
             Arc::new(question_repository),
             Arc::new(score_repository),
-            SelectCount {
-                single_selection: 2,
-                multiple_selection: 1,
-                completion: 1,
-            },
+            select_count,
         )
     }
 
     async fn new_test_session(
         question_repository: Arc<dyn QuestionRepository>,
         score_repository: Arc<dyn ScoreRepository>,
+        test_questions: SelectCount,
     ) -> (
         TestSession,
         MpscSender<Command<TestSession>>,
@@ -290,7 +545,11 @@ mod tests {
         let id_allocator = Arc::new(SequentialIdAllocator::new());
         let (commander, command) = mpsc::channel(4);
         let (reporter, report) = mpsc::channel(4);
-        let base = SessionBase::new(id_allocator, command, reporter).await;
+        let config = Arc::new(SessionConfig {
+            test_questions,
+            ..SessionConfig::default()
+        });
+        let base = SessionBase::new(id_allocator, config, command, reporter).await;
         let session = TestSession::new(base, question_repository, score_repository);
         (session, commander, report)
     }
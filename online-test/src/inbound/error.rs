@@ -4,6 +4,7 @@ use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
 use snafu::{prelude::*, Report};
 
+use crate::domain::entity::id::Id;
 use crate::domain::entity::user::User;
 
 #[derive(Debug, Snafu)]
@@ -18,13 +19,34 @@ pub enum ApiError {
     TestInvalidOrExpired,
     #[snafu(display("Could not find information for user {user}"))]
     UserNotFound { user: User },
+    #[snafu(display("Could not serve without a valid admin token"))]
+    Unauthorized,
+    #[snafu(display("Could not find pending item {id}"))]
+    PendingItemNotFound { id: Id },
+    #[snafu(display("Could not find media {id}"))]
+    MediaNotFound { id: usize },
+    #[snafu(display("Media {id} is not part of the running test"))]
+    MediaForbidden { id: usize },
+    #[snafu(display("User {user} has not passed any test"))]
+    NotPassed { user: User },
+    #[snafu(display("Server is shutting down"))]
+    ShuttingDown,
     #[snafu(display("Unknown error occurred"))]
     Unknown { source: Box<dyn Error> },
 }
 
 impl IntoResponse for ApiError {
     fn into_response(self) -> Response {
+        let status = match self {
+            Self::Unauthorized => StatusCode::UNAUTHORIZED,
+            Self::MediaForbidden { .. } => StatusCode::FORBIDDEN,
+            Self::PendingItemNotFound { .. }
+            | Self::MediaNotFound { .. }
+            | Self::NotPassed { .. } => StatusCode::NOT_FOUND,
+            Self::ShuttingDown => StatusCode::SERVICE_UNAVAILABLE,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        };
         let report = Report::from_error(self);
-        (StatusCode::INTERNAL_SERVER_ERROR, report.to_string()).into_response()
+        (status, report.to_string()).into_response()
     }
 }
@@ -0,0 +1,166 @@
+use std::sync::Arc;
+
+use axum::{extract::State, Json};
+use serde::{Deserialize, Serialize};
+use snafu::ResultExt;
+
+use crate::domain::application::{Core, CoreError};
+use crate::domain::entity::grade::Grade;
+use crate::domain::entity::question::Question;
+use crate::domain::session::adaptive::{AdaptiveProgress, AdaptiveSessionError, AdaptiveSummary};
+use crate::domain::session::login::LoginSessionError;
+use crate::inbound::error::{
+    ApiError, DataInvalidSnafu, NotLoggedInSnafu, ShuttingDownSnafu, TestInvalidOrExpiredSnafu,
+    UnknownSnafu,
+};
+use crate::inbound::handler::submit::{AnswerVariant, ItemData};
+
+#[derive(Debug, Deserialize)]
+pub struct AdaptiveStartRequest {
+    login_id: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdaptiveStartResponse {
+    test_id: usize,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AdaptiveTestRequest {
+    login_id: usize,
+    test_id: usize,
+}
+
+// `question` is null once the test should be finished.
+#[derive(Debug, Serialize)]
+pub struct AdaptiveNextResponse {
+    question: Option<Question>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AdaptiveAnswerRequest {
+    login_id: usize,
+    test_id: usize,
+    #[serde(flatten)]
+    answer: AnswerVariant,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdaptiveAnswerResponse {
+    answered: usize,
+    remaining: usize,
+}
+
+impl From<AdaptiveProgress> for AdaptiveAnswerResponse {
+    fn from(value: AdaptiveProgress) -> Self {
+        Self {
+            answered: value.answered,
+            remaining: value.remaining,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdaptiveFinishResponse {
+    score: f32,
+    points: f32,
+    penalty: f32,
+    total_points: u32,
+    grade: Option<String>,
+    passed: Option<bool>,
+    ability: f32,
+    duration: usize,
+    items: Vec<ItemData>,
+}
+
+impl From<AdaptiveSummary> for AdaptiveFinishResponse {
+    fn from(value: AdaptiveSummary) -> Self {
+        Self {
+            score: value.score.inner(),
+            points: value.points.earned(),
+            penalty: value.points.penalty(),
+            total_points: value.points.total(),
+            grade: value.grade.as_ref().map(|g| g.letter().to_owned()),
+            passed: value.grade.as_ref().map(Grade::passed),
+            ability: value.ability,
+            duration: value.duration.as_secs() as usize,
+            items: value.feedback.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+pub async fn handle_adaptive_start(
+    State(core): State<Arc<Core>>,
+    Json(request): Json<AdaptiveStartRequest>,
+) -> Result<Json<AdaptiveStartResponse>, ApiError> {
+    match core.adaptive_start(request.login_id.into()).await {
+        Ok(id) => Ok(Json(AdaptiveStartResponse {
+            test_id: id.inner(),
+        })),
+        Err(err) => handle_error(err),
+    }
+}
+
+pub async fn handle_adaptive_next(
+    State(core): State<Arc<Core>>,
+    Json(request): Json<AdaptiveTestRequest>,
+) -> Result<Json<AdaptiveNextResponse>, ApiError> {
+    let res = core
+        .adaptive_next(request.login_id.into(), request.test_id.into())
+        .await;
+
+    match res {
+        Ok(question) => Ok(Json(AdaptiveNextResponse { question })),
+        Err(err) => handle_error(err),
+    }
+}
+
+pub async fn handle_adaptive_answer(
+    State(core): State<Arc<Core>>,
+    Json(request): Json<AdaptiveAnswerRequest>,
+) -> Result<Json<AdaptiveAnswerResponse>, ApiError> {
+    let answer = request.answer.into_answer()?;
+
+    let res = core
+        .adaptive_answer(request.login_id.into(), request.test_id.into(), answer)
+        .await;
+
+    match res {
+        Ok(progress) => Ok(Json(progress.into())),
+        Err(err) => handle_error(err),
+    }
+}
+
+pub async fn handle_adaptive_finish(
+    State(core): State<Arc<Core>>,
+    Json(request): Json<AdaptiveTestRequest>,
+) -> Result<Json<AdaptiveFinishResponse>, ApiError> {
+    let res = core
+        .adaptive_finish(request.login_id.into(), request.test_id.into())
+        .await;
+
+    match res {
+        Ok(summary) => Ok(Json(summary.into())),
+        Err(err) => handle_error(err),
+    }
+}
+
+fn handle_error<T>(err: CoreError) -> Result<T, ApiError> {
+    match err {
+        CoreError::SessionNotFound { .. } => NotLoggedInSnafu.fail(),
+        CoreError::ShuttingDown => ShuttingDownSnafu.fail(),
+        CoreError::LoginSession {
+            source: LoginSessionError::SessionNotFound { .. },
+            ..
+        } => TestInvalidOrExpiredSnafu.fail(),
+        CoreError::LoginSession {
+            source:
+                LoginSessionError::Adaptive {
+                    source:
+                        source @ (AdaptiveSessionError::NoQuestion | AdaptiveSessionError::NotStarted),
+                },
+            ..
+        } => Err(Box::new(source).into()).context(DataInvalidSnafu),
+        _ => Err(err.into()).context(UnknownSnafu),
+    }
+}
@@ -0,0 +1,54 @@
+use std::sync::Arc;
+
+use axum::extract::{Query, State};
+use axum::http::header;
+use axum::response::{IntoResponse, Response};
+use serde::Deserialize;
+use snafu::ResultExt;
+
+use crate::domain::application::{Core, CoreError};
+use crate::domain::repository::score::ScoreRepositoryError;
+use crate::domain::service::certificate;
+use crate::domain::session::login::LoginSessionError;
+use crate::inbound::error::{ApiError, NotLoggedInSnafu, NotPassedSnafu, UnknownSnafu};
+
+#[derive(Debug, Deserialize)]
+pub struct CertificateQuery {
+    login_id: usize,
+}
+
+pub async fn handle_certificate(
+    State(core): State<Arc<Core>>,
+    Query(query): Query<CertificateQuery>,
+) -> Result<Response, ApiError> {
+    let certificate = match core.certificate(query.login_id.into()).await {
+        Ok(res) => res,
+        Err(CoreError::SessionNotFound { .. })
+        | Err(CoreError::LoginSession {
+            source: LoginSessionError::NotLoggedIn,
+            ..
+        }) => return NotLoggedInSnafu.fail(),
+        Err(CoreError::LoginSession {
+            source:
+                LoginSessionError::Certificate {
+                    source: ScoreRepositoryError::NotPassed { user },
+                },
+            ..
+        }) => return NotPassedSnafu { user }.fail(),
+        Err(err) => return Err(err.into()).context(UnknownSnafu),
+    };
+
+    let headers = [
+        (header::CONTENT_TYPE, "application/pdf".to_owned()),
+        (
+            header::CONTENT_DISPOSITION,
+            format!(
+                "attachment; filename=\"certificate-{}.pdf\"",
+                certificate.code
+            ),
+        ),
+        (header::CACHE_CONTROL, "private, no-store".to_owned()),
+    ];
+
+    Ok((headers, certificate::render(&certificate)).into_response())
+}
@@ -0,0 +1,135 @@
+use std::sync::Arc;
+
+use axum::extract::{Request, State};
+use axum::http::header;
+use axum::middleware::Next;
+use axum::response::Response;
+use axum::Json;
+use serde::{Deserialize, Serialize};
+use snafu::prelude::*;
+use subtle::ConstantTimeEq;
+
+use crate::domain::application::{Core, CoreError};
+use crate::domain::entity::question::{AbstractQuestion, Question};
+use crate::domain::repository::score::ScoreRepositoryError;
+use crate::domain::service::grading::{GradingError, PendingReview};
+use crate::inbound::error::{
+    ApiError, DataInvalidSnafu, PendingItemNotFoundSnafu, UnauthorizedSnafu, UnknownSnafu,
+};
+
+#[derive(Debug, Serialize)]
+pub struct PendingResponse {
+    items: Vec<PendingData>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PendingData {
+    id: usize,
+    user: String,
+    question_id: usize,
+    points: u32,
+    content: String,
+    reference: String,
+    response: String,
+    end_time: String,
+}
+
+impl From<PendingReview> for PendingData {
+    fn from(value: PendingReview) -> Self {
+        let reference = match &value.question {
+            Question::Essay(q) => q.answer().value().to_owned(),
+            _ => String::new(),
+        };
+
+        Self {
+            id: value.item.id.inner(),
+            user: value.item.user.into(),
+            question_id: value.question.id().inner(),
+            points: value.item.points,
+            content: value.question.content().rendered().to_owned(),
+            reference,
+            response: value.item.response,
+            end_time: value.item.end_time.format("%Y-%m-%d %H:%M:%S").to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GradeRequest {
+    item_id: usize,
+    points: f32,
+    comment: Option<String>,
+    grader: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct GradeResponse {
+    completed: bool,
+    score: Option<f32>,
+}
+
+pub async fn require_admin(
+    State(token): State<Arc<str>>,
+    request: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
+    let authorized = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        // Compared in constant time, so that response times do not give the token away.
+        .is_some_and(|value| value.as_bytes().ct_eq(token.as_bytes()).into());
+    ensure!(authorized, UnauthorizedSnafu);
+
+    Ok(next.run(request).await)
+}
+
+pub async fn handle_pending(
+    State(core): State<Arc<Core>>,
+) -> Result<Json<PendingResponse>, ApiError> {
+    let items = core
+        .pending_reviews()
+        .await
+        .map_err(Into::into)
+        .context(UnknownSnafu)?;
+
+    let response = PendingResponse {
+        items: items.into_iter().map(Into::into).collect(),
+    };
+
+    Ok(response.into())
+}
+
+pub async fn handle_grade(
+    State(core): State<Arc<Core>>,
+    Json(request): Json<GradeRequest>,
+) -> Result<Json<GradeResponse>, ApiError> {
+    let res = core
+        .grade(
+            request.item_id.into(),
+            request.points,
+            request.comment,
+            request.grader.unwrap_or("admin".to_owned()),
+        )
+        .await;
+
+    match res {
+        Ok(score) => Ok(GradeResponse {
+            completed: score.is_some(),
+            score: score.map(|s| s.inner()),
+        }
+        .into()),
+        Err(CoreError::Grading {
+            source: source @ GradingError::PointsInvalid { .. },
+        }) => Err(source.into()).context(DataInvalidSnafu),
+        Err(CoreError::Grading {
+            source:
+                GradingError::PendingItemNotFound { id }
+                | GradingError::SaveGrade {
+                    source: ScoreRepositoryError::PendingItemNotFound { id },
+                },
+        }) => PendingItemNotFoundSnafu { id }.fail(),
+        Err(err) => Err(err.into()).context(UnknownSnafu),
+    }
+}
@@ -4,9 +4,9 @@ use axum::{extract::State, Json};
 use serde::{Deserialize, Serialize};
 use snafu::ResultExt;
 
-use crate::domain::application::Core;
+use crate::domain::application::{Core, CoreError};
 use crate::domain::entity::user::User;
-use crate::inbound::error::{ApiError, DataInvalidSnafu, UnknownSnafu};
+use crate::inbound::error::{ApiError, DataInvalidSnafu, ShuttingDownSnafu, UnknownSnafu};
 
 #[derive(Debug, Deserialize)]
 pub struct LoginRequest {
@@ -27,11 +27,11 @@ pub async fn handle_login(
         .map_err(Into::into)
         .context(DataInvalidSnafu)?;
 
-    let login_id = core
-        .login(user)
-        .await
-        .map_err(Into::into)
-        .context(UnknownSnafu)?;
+    let login_id = match core.login(user).await {
+        Ok(login_id) => login_id,
+        Err(CoreError::ShuttingDown) => return ShuttingDownSnafu.fail(),
+        Err(err) => return Err(err.into()).context(UnknownSnafu),
+    };
 
     let response = LoginResponse {
         login_id: login_id.inner(),
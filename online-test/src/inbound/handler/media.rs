@@ -0,0 +1,68 @@
+use std::sync::Arc;
+
+use axum::extract::{Path, Query, State};
+use axum::http::header;
+use axum::response::{IntoResponse, Response};
+use serde::Deserialize;
+use snafu::ResultExt;
+
+use crate::domain::application::{Core, CoreError};
+use crate::domain::repository::media::MediaRepositoryError;
+use crate::domain::session::login::LoginSessionError;
+use crate::inbound::error::{
+    ApiError, MediaForbiddenSnafu, MediaNotFoundSnafu, NotLoggedInSnafu, UnknownSnafu,
+};
+
+#[derive(Debug, Deserialize)]
+pub struct MediaQuery {
+    login_id: usize,
+}
+
+pub async fn handle_media(
+    State(core): State<Arc<Core>>,
+    Path(id): Path<usize>,
+    Query(query): Query<MediaQuery>,
+) -> Result<Response, ApiError> {
+    let (media, data) = match core.media(query.login_id.into(), id.into()).await {
+        Ok(res) => res,
+        Err(CoreError::SessionNotFound { .. })
+        | Err(CoreError::LoginSession {
+            source: LoginSessionError::NotLoggedIn,
+            ..
+        }) => return NotLoggedInSnafu.fail(),
+        Err(CoreError::LoginSession {
+            source: LoginSessionError::QuestionNotInTest { .. },
+            ..
+        }) => return MediaForbiddenSnafu { id }.fail(),
+        Err(CoreError::Media {
+            source: MediaRepositoryError::NotFound { .. },
+        }) => return MediaNotFoundSnafu { id }.fail(),
+        Err(err) => return Err(err.into()).context(UnknownSnafu),
+    };
+
+    let disposition = if media.inline() {
+        "inline"
+    } else {
+        "attachment"
+    };
+    let file_name: String = media
+        .file_name
+        .chars()
+        .map(|c| match c {
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '.' | '-' | '_' => c,
+            _ => '_',
+        })
+        .collect();
+
+    let headers = [
+        (header::CONTENT_TYPE, media.mime_type),
+        (
+            header::CONTENT_DISPOSITION,
+            format!("{disposition}; filename=\"{file_name}\""),
+        ),
+        (header::X_CONTENT_TYPE_OPTIONS, "nosniff".to_owned()),
+        (header::CACHE_CONTROL, "private, no-store".to_owned()),
+    ];
+
+    Ok((headers, data).into_response())
+}
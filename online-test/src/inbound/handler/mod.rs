@@ -1,4 +1,9 @@
+pub mod adaptive;
+pub mod certificate;
+pub mod grading;
 pub mod login;
+pub mod media;
+pub mod practice;
 pub mod query;
 pub mod start;
 pub mod submit;
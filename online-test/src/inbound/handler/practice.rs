@@ -0,0 +1,141 @@
+use std::sync::Arc;
+
+use axum::{extract::State, Json};
+use serde::{Deserialize, Serialize};
+use snafu::ResultExt;
+
+use crate::domain::application::{Core, CoreError};
+use crate::domain::entity::answer::{Answer, StandardSource};
+use crate::domain::entity::content::Content;
+use crate::domain::entity::question::Question;
+use crate::domain::session::login::LoginSessionError;
+use crate::domain::session::practice::{PracticeFeedback, PracticeSessionError, PracticeTally};
+use crate::inbound::error::{
+    ApiError, DataInvalidSnafu, NotLoggedInSnafu, ShuttingDownSnafu, TestInvalidOrExpiredSnafu,
+    UnknownSnafu,
+};
+use crate::inbound::handler::submit::AnswerVariant;
+
+#[derive(Debug, Deserialize)]
+pub struct PracticeStartRequest {
+    login_id: usize,
+    // Serves only the questions due for review.
+    #[serde(default)]
+    review: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PracticeStartResponse {
+    practice_id: usize,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PracticeNextRequest {
+    login_id: usize,
+    practice_id: usize,
+}
+
+// `question` is null once every question of the practice has been answered.
+#[derive(Debug, Serialize)]
+pub struct PracticeNextResponse {
+    question: Option<Question>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PracticeAnswerRequest {
+    login_id: usize,
+    practice_id: usize,
+    #[serde(flatten)]
+    answer: AnswerVariant,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PracticeAnswerResponse {
+    id: usize,
+    correct: bool,
+    answer: Answer<StandardSource>,
+    explanation: Option<Content>,
+    answered: usize,
+    correct_count: usize,
+    remaining: usize,
+}
+
+impl From<PracticeFeedback> for PracticeAnswerResponse {
+    fn from(value: PracticeFeedback) -> Self {
+        let PracticeTally { answered, correct } = value.tally;
+
+        Self {
+            id: value.question.inner(),
+            correct: value.correct,
+            answer: value.answer,
+            explanation: value.explanation,
+            answered,
+            correct_count: correct,
+            remaining: value.remaining,
+        }
+    }
+}
+
+pub async fn handle_practice_start(
+    State(core): State<Arc<Core>>,
+    Json(request): Json<PracticeStartRequest>,
+) -> Result<Json<PracticeStartResponse>, ApiError> {
+    match core
+        .practice_start(request.login_id.into(), request.review)
+        .await
+    {
+        Ok(id) => Ok(Json(PracticeStartResponse {
+            practice_id: id.inner(),
+        })),
+        Err(err) => handle_error(err),
+    }
+}
+
+pub async fn handle_practice_next(
+    State(core): State<Arc<Core>>,
+    Json(request): Json<PracticeNextRequest>,
+) -> Result<Json<PracticeNextResponse>, ApiError> {
+    let res = core
+        .practice_next(request.login_id.into(), request.practice_id.into())
+        .await;
+
+    match res {
+        Ok(question) => Ok(Json(PracticeNextResponse { question })),
+        Err(err) => handle_error(err),
+    }
+}
+
+pub async fn handle_practice_answer(
+    State(core): State<Arc<Core>>,
+    Json(request): Json<PracticeAnswerRequest>,
+) -> Result<Json<PracticeAnswerResponse>, ApiError> {
+    let answer = request.answer.into_answer()?;
+
+    let res = core
+        .practice_answer(request.login_id.into(), request.practice_id.into(), answer)
+        .await;
+
+    match res {
+        Ok(feedback) => Ok(Json(feedback.into())),
+        Err(err) => handle_error(err),
+    }
+}
+
+fn handle_error<T>(err: CoreError) -> Result<T, ApiError> {
+    match err {
+        CoreError::SessionNotFound { .. } => NotLoggedInSnafu.fail(),
+        CoreError::ShuttingDown => ShuttingDownSnafu.fail(),
+        CoreError::LoginSession {
+            source: LoginSessionError::SessionNotFound { .. },
+            ..
+        } => TestInvalidOrExpiredSnafu.fail(),
+        CoreError::LoginSession {
+            source:
+                LoginSessionError::Practice {
+                    source: source @ PracticeSessionError::NoQuestion,
+                },
+            ..
+        } => Err(Box::new(source).into()).context(DataInvalidSnafu),
+        _ => Err(err.into()).context(UnknownSnafu),
+    }
+}
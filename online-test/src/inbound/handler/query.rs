@@ -6,6 +6,7 @@ use serde::{Deserialize, Serialize};
 use snafu::prelude::*;
 
 use crate::domain::application::{Core, CoreError};
+use crate::domain::entity::grade::Grade;
 use crate::domain::repository::score::{Record, ScoreRepositoryError};
 use crate::domain::session::login::{LoginSessionError, QueryKind};
 use crate::inbound::error::{ApiError, NotLoggedInSnafu, UnknownSnafu, UserNotFoundSnafu};
@@ -24,16 +25,28 @@ pub struct QueryResponse {
 #[derive(Debug, Serialize)]
 pub struct ResultData {
     score: f32,
+    points: f32,
+    penalty: f32,
+    total_points: u32,
+    grade: Option<String>,
+    passed: Option<bool>,
     end_time: String,
     duration: usize,
+    pending: bool,
 }
 
 impl From<Record> for ResultData {
     fn from(value: Record) -> Self {
         Self {
             score: value.score.inner(),
+            points: value.points.earned(),
+            penalty: value.points.penalty(),
+            total_points: value.points.total(),
+            grade: value.grade.as_ref().map(|g| g.letter().to_owned()),
+            passed: value.grade.as_ref().map(Grade::passed),
             end_time: value.end_time.format("%Y-%m-%d %H:%M:%S").to_string(),
             duration: value.duration.as_secs() as usize,
+            pending: value.pending,
         }
     }
 }
@@ -59,15 +72,14 @@ pub async fn handle_query(
     if let Err(err) = res {
         match &err {
             CoreError::SessionNotFound { .. } => NotLoggedInSnafu.fail(),
-            CoreError::LoginSession { source, .. } => match source {
-                LoginSessionError::Query { source } => match source {
-                    ScoreRepositoryError::NotFound { user } => {
-                        UserNotFoundSnafu { user: user.clone() }.fail()
-                    }
-                    _ => Err(err.into()).context(UnknownSnafu),
-                },
-                _ => Err(err.into()).context(UnknownSnafu),
-            },
+            CoreError::LoginSession {
+                source:
+                    LoginSessionError::Query {
+                        source: ScoreRepositoryError::NotFound { user },
+                    },
+                ..
+            } => UserNotFoundSnafu { user: user.clone() }.fail(),
+            _ => Err(err.into()).context(UnknownSnafu),
         }
     } else {
         let response = QueryResponse {
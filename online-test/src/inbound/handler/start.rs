@@ -6,7 +6,7 @@ use snafu::ResultExt;
 
 use crate::domain::application::{Core, CoreError};
 use crate::domain::entity::test::Test;
-use crate::inbound::error::{ApiError, NotLoggedInSnafu, UnknownSnafu};
+use crate::inbound::error::{ApiError, NotLoggedInSnafu, ShuttingDownSnafu, UnknownSnafu};
 
 #[derive(Debug, Deserialize)]
 pub struct StartRequest {
@@ -28,6 +28,7 @@ pub async fn handle_start(
     if let Err(err) = res {
         match err {
             CoreError::SessionNotFound { .. } => NotLoggedInSnafu.fail(),
+            CoreError::ShuttingDown => ShuttingDownSnafu.fail(),
             _ => Err(err.into()).context(UnknownSnafu),
         }
     } else {
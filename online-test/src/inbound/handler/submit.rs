@@ -1,16 +1,21 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 
+use axum::http::StatusCode;
 use axum::{extract::State, Json};
 use serde::{Deserialize, Serialize};
 use snafu::ResultExt;
 
 use crate::domain::application::{Core, CoreError};
 use crate::domain::entity::answer::{
-    CompletionAnswer, MultipleSelectionAnswer, SingleSelectionAnswer, SubmissionSource,
+    Answer, ClozeAnswer, CompletionAnswer, EssayAnswer, MatchingAnswer, MultipleSelectionAnswer,
+    OrderingAnswer, SingleSelectionAnswer, SubmissionSource, TrueFalseAnswer,
 };
-use crate::domain::entity::test::Submission;
+use crate::domain::entity::content::Content;
+use crate::domain::entity::grade::Grade;
+use crate::domain::entity::test::{ItemFeedback, Submission};
 use crate::domain::session::login::LoginSessionError;
+use crate::domain::session::test::TestSummary;
 use crate::inbound::error::{
     ApiError, DataInvalidSnafu, NotLoggedInSnafu, TestInvalidOrExpiredSnafu, UnknownSnafu,
 };
@@ -35,56 +40,162 @@ pub enum AnswerVariant {
     SingleSelection { answer: u32 },
     MultipleSelection { answer: Vec<u32> },
     Completion { answer: String },
+    TrueFalse { answer: bool },
+    Ordering { answer: Vec<u32> },
+    Matching { answer: Vec<Option<u32>> },
+    Essay { answer: String },
+    Cloze { answer: Vec<String> },
 }
 
-#[axum_macros::debug_handler]
-pub async fn handle_submit(
-    State(core): State<Arc<Core>>,
-    Json(request): Json<SubmitRequest>,
-) -> Result<(), ApiError> {
-    let mut answers = HashMap::new();
-
-    for AnswerWithId { id, answer } in request.answers {
-        let answer = match answer {
+impl AnswerVariant {
+    pub fn into_answer(self) -> Result<Answer<SubmissionSource>, ApiError> {
+        match self {
             AnswerVariant::SingleSelection { answer } => {
                 SingleSelectionAnswer::<SubmissionSource>::try_new(answer)
                     .map(Into::into)
                     .map_err(Into::into)
-                    .context(DataInvalidSnafu)?
+                    .context(DataInvalidSnafu)
             }
             AnswerVariant::MultipleSelection { answer } => {
                 MultipleSelectionAnswer::<SubmissionSource>::try_new(answer)
                     .map(Into::into)
                     .map_err(Into::into)
-                    .context(DataInvalidSnafu)?
+                    .context(DataInvalidSnafu)
             }
             AnswerVariant::Completion { answer } => {
                 CompletionAnswer::<SubmissionSource>::try_new(answer)
                     .map(Into::into)
                     .map_err(Into::into)
-                    .context(DataInvalidSnafu)?
+                    .context(DataInvalidSnafu)
+            }
+            AnswerVariant::TrueFalse { answer } => {
+                TrueFalseAnswer::<SubmissionSource>::try_new(answer)
+                    .map(Into::into)
+                    .map_err(Into::into)
+                    .context(DataInvalidSnafu)
+            }
+            AnswerVariant::Ordering { answer } => {
+                OrderingAnswer::<SubmissionSource>::try_new(answer)
+                    .map(Into::into)
+                    .map_err(Into::into)
+                    .context(DataInvalidSnafu)
             }
-        };
+            AnswerVariant::Matching { answer } => {
+                MatchingAnswer::<SubmissionSource>::try_new(answer)
+                    .map(Into::into)
+                    .map_err(Into::into)
+                    .context(DataInvalidSnafu)
+            }
+            AnswerVariant::Essay { answer } => EssayAnswer::<SubmissionSource>::try_new(answer)
+                .map(Into::into)
+                .map_err(Into::into)
+                .context(DataInvalidSnafu),
+            AnswerVariant::Cloze { answer } => ClozeAnswer::<SubmissionSource>::try_new(answer)
+                .map(Into::into)
+                .map_err(Into::into)
+                .context(DataInvalidSnafu),
+        }
+    }
+}
 
-        let _ = answers.insert(id.into(), answer);
+// Explanations are only ever sent here, never along with the questions of a running test.
+#[derive(Debug, Serialize)]
+pub struct SubmitResponse {
+    score: f32,
+    points: f32,
+    penalty: f32,
+    total_points: u32,
+    grade: Option<String>,
+    passed: Option<bool>,
+    duration: usize,
+    items: Vec<ItemData>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ItemData {
+    id: usize,
+    correct: bool,
+    pending: bool,
+    penalty: f32,
+    explanation: Option<Content>,
+}
+
+impl From<TestSummary> for SubmitResponse {
+    fn from(value: TestSummary) -> Self {
+        Self {
+            score: value.score.inner(),
+            points: value.points.earned(),
+            penalty: value.points.penalty(),
+            total_points: value.points.total(),
+            grade: value.grade.as_ref().map(|g| g.letter().to_owned()),
+            passed: value.grade.as_ref().map(Grade::passed),
+            duration: value.duration.as_secs() as usize,
+            items: value.feedback.into_iter().map(Into::into).collect(),
+        }
     }
+}
 
-    let submission = Submission::new(answers);
+impl From<ItemFeedback> for ItemData {
+    fn from(value: ItemFeedback) -> Self {
+        Self {
+            id: value.question.inner(),
+            correct: value.correct,
+            pending: value.pending,
+            penalty: value.penalty,
+            explanation: value.explanation,
+        }
+    }
+}
 
+#[axum_macros::debug_handler]
+pub async fn handle_submit(
+    State(core): State<Arc<Core>>,
+    Json(request): Json<SubmitRequest>,
+) -> Result<Json<SubmitResponse>, ApiError> {
+    let submission = into_submission(request.answers)?;
     let res = core
         .submit(request.login_id.into(), request.test_id.into(), submission)
         .await;
 
-    if let Err(err) = res {
-        match err {
-            CoreError::LoginSession {
-                source: LoginSessionError::SessionNotFound { .. },
-                ..
-            } => TestInvalidOrExpiredSnafu.fail(),
-            CoreError::SessionNotFound { .. } => NotLoggedInSnafu.fail(),
-            _ => Err(err.into()).context(UnknownSnafu),
-        }
-    } else {
-        Ok(())
+    match res {
+        Ok(summary) => Ok(Json(summary.into())),
+        Err(CoreError::LoginSession {
+            source: LoginSessionError::SessionNotFound { .. },
+            ..
+        }) => TestInvalidOrExpiredSnafu.fail(),
+        Err(CoreError::SessionNotFound { .. }) => NotLoggedInSnafu.fail(),
+        Err(err) => Err(err.into()).context(UnknownSnafu),
     }
 }
+
+// Drafts share the body of a submission, but are only kept until the test is submitted.
+#[axum_macros::debug_handler]
+pub async fn handle_draft(
+    State(core): State<Arc<Core>>,
+    Json(request): Json<SubmitRequest>,
+) -> Result<StatusCode, ApiError> {
+    let submission = into_submission(request.answers)?;
+    let res = core
+        .save_draft(request.login_id.into(), request.test_id.into(), submission)
+        .await;
+
+    match res {
+        Ok(()) => Ok(StatusCode::NO_CONTENT),
+        Err(CoreError::LoginSession {
+            source: LoginSessionError::SessionNotFound { .. },
+            ..
+        }) => TestInvalidOrExpiredSnafu.fail(),
+        Err(CoreError::SessionNotFound { .. }) => NotLoggedInSnafu.fail(),
+        Err(err) => Err(err.into()).context(UnknownSnafu),
+    }
+}
+
+fn into_submission(answers: Vec<AnswerWithId>) -> Result<Submission, ApiError> {
+    let mut submission = HashMap::new();
+
+    for AnswerWithId { id, answer } in answers {
+        let _ = submission.insert(id.into(), answer.into_answer()?);
+    }
+
+    Ok(Submission::new(submission))
+}
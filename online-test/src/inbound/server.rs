@@ -1,9 +1,15 @@
+use std::future;
 use std::net::SocketAddr;
+use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 
-use axum::{routing::post, Router};
+use axum::routing::{get, post};
+use axum::{middleware, Router};
 use snafu::{prelude::*, Whatever};
 use tokio::net::TcpListener;
+use tokio::sync::watch;
+use tokio::time::{sleep_until, timeout_at, Instant};
 use tower_http::services::ServeDir;
 
 use crate::domain::application::Core;
@@ -12,6 +18,10 @@ use crate::domain::application::Core;
 pub struct Server {
     listener: TcpListener,
     core: Arc<Core>,
+    admin_token: Option<String>,
+    static_dir: PathBuf,
+    shutdown_timeout: Duration,
+    auto_grade: bool,
 }
 
 impl Server {
@@ -20,20 +30,160 @@ impl Server {
             .await
             .whatever_context(format!("Could not bind to {addr}"))?;
 
-        Ok(Self { listener, core })
+        Ok(Self {
+            listener,
+            core,
+            admin_token: None,
+            static_dir: PathBuf::from("static"),
+            shutdown_timeout: Duration::from_secs(30),
+            auto_grade: false,
+        })
+    }
+
+    // Admin API is only served when a token is given.
+    pub fn with_admin_token(mut self, admin_token: Option<String>) -> Self {
+        self.admin_token = admin_token.filter(|t| !t.is_empty());
+        self
+    }
+
+    pub fn with_static_dir(mut self, static_dir: impl Into<PathBuf>) -> Self {
+        self.static_dir = static_dir.into();
+        self
+    }
+
+    // On shutdown, requests in flight and then the sessions get until `timeout` in total to
+    // finish. With `auto_grade`, tests still open are graded from their last draft.
+    pub fn with_shutdown(mut self, timeout: Duration, auto_grade: bool) -> Self {
+        self.shutdown_timeout = timeout;
+        self.auto_grade = auto_grade;
+        self
     }
 
     pub async fn serve(self) -> Result<(), Whatever> {
-        let router = Router::new()
-            .nest_service("/", ServeDir::new("static"))
+        let mut router = Router::new()
+            .nest_service("/", ServeDir::new(self.static_dir))
             .route("/api/login", post(super::handler::login::handle_login))
             .route("/api/start", post(super::handler::start::handle_start))
             .route("/api/submit", post(super::handler::submit::handle_submit))
+            .route("/api/draft", post(super::handler::submit::handle_draft))
             .route("/api/query", post(super::handler::query::handle_query))
-            .with_state(self.core);
+            .route("/api/media/:id", get(super::handler::media::handle_media))
+            .route(
+                "/api/certificate",
+                get(super::handler::certificate::handle_certificate),
+            )
+            .route(
+                "/api/adaptive/start",
+                post(super::handler::adaptive::handle_adaptive_start),
+            )
+            .route(
+                "/api/adaptive/next",
+                post(super::handler::adaptive::handle_adaptive_next),
+            )
+            .route(
+                "/api/adaptive/answer",
+                post(super::handler::adaptive::handle_adaptive_answer),
+            )
+            .route(
+                "/api/adaptive/finish",
+                post(super::handler::adaptive::handle_adaptive_finish),
+            )
+            .route(
+                "/api/practice/start",
+                post(super::handler::practice::handle_practice_start),
+            )
+            .route(
+                "/api/practice/next",
+                post(super::handler::practice::handle_practice_next),
+            )
+            .route(
+                "/api/practice/answer",
+                post(super::handler::practice::handle_practice_answer),
+            );
 
-        axum::serve(self.listener, router.into_make_service())
-            .await
-            .whatever_context("Server error")
+        if let Some(admin_token) = self.admin_token {
+            let admin = Router::new()
+                .route(
+                    "/api/admin/pending",
+                    post(super::handler::grading::handle_pending),
+                )
+                .route(
+                    "/api/admin/grade",
+                    post(super::handler::grading::handle_grade),
+                )
+                .route_layer(middleware::from_fn_with_state(
+                    Arc::<str>::from(admin_token),
+                    super::handler::grading::require_admin,
+                ));
+            router = router.merge(admin);
+        }
+
+        let core = Arc::clone(&self.core);
+        let router = router.with_state(self.core);
+
+        let (deadline_sender, mut deadline) = watch::channel(None);
+        let shutdown_timeout = self.shutdown_timeout;
+        let signal_core = Arc::clone(&core);
+        let serving = axum::serve(self.listener, router.into_make_service())
+            .with_graceful_shutdown(async move {
+                shutdown_signal().await;
+                println!("Shutting down, no longer accepting logins and tests");
+                signal_core.close();
+                let _ = deadline_sender.send(Some(Instant::now() + shutdown_timeout));
+            });
+
+        let mut signalled = deadline.clone();
+        let timed_out = async move {
+            match signalled.wait_for(Option::is_some).await {
+                Ok(deadline) => {
+                    let deadline = deadline.unwrap_or_else(Instant::now);
+                    sleep_until(deadline).await;
+                }
+                Err(_) => future::pending().await,
+            }
+        };
+
+        tokio::select! {
+            res = serving => res.whatever_context("Server error")?,
+            _ = timed_out => eprintln!("Timed out waiting for requests in flight"),
+        }
+
+        let deadline = deadline
+            .borrow_and_update()
+            .unwrap_or_else(|| Instant::now() + shutdown_timeout);
+        match timeout_at(deadline, core.shutdown(self.auto_grade)).await {
+            Ok(summary) => println!(
+                "Cancelled {} sessions, graded {} open tests",
+                summary.sessions, summary.graded
+            ),
+            Err(_) => eprintln!("Timed out waiting for sessions to finalize"),
+        }
+
+        Ok(())
+    }
+}
+
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        let _ = tokio::signal::ctrl_c().await;
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        match signal(SignalKind::terminate()) {
+            Ok(mut terminate) => {
+                terminate.recv().await;
+            }
+            Err(_) => future::pending().await,
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
     }
 }
@@ -1,16 +1,37 @@
+use std::net::IpAddr;
+use std::path::PathBuf;
 use std::sync::Arc;
 
+use clap::{Parser, Subcommand};
+use online_test::cli;
+use online_test::config::Config;
 use online_test::domain::application::Core;
+use online_test::domain::repository::group::GroupRepository;
+use online_test::domain::repository::media::MediaRepository;
+use online_test::domain::repository::practice::PracticeRepository;
+use online_test::domain::repository::question::QuestionRepository;
+use online_test::domain::repository::review::ReviewRepository;
+use online_test::domain::repository::score::ScoreRepository;
+use online_test::domain::service::grading::GradingService;
 use online_test::inbound::server::Server;
-use online_test::repository::connection::{AsyncSqliteConnectionManager, AsyncSqlitePool};
-use online_test::repository::question::QuestionSqliteRepository;
-use online_test::repository::score::ScoreSqliteRepository;
-use online_test::cli;
+use online_test::repository::connection::DatabasePool;
+use online_test::repository::group::GroupSqlRepository;
+use online_test::repository::media::MediaSqlRepository;
+use online_test::repository::migration::Migrator;
+use online_test::repository::practice::PracticeSqlRepository;
+use online_test::repository::question::QuestionSqlRepository;
+use online_test::repository::review::ReviewSqlRepository;
+use online_test::repository::score::ScoreSqlRepository;
 use snafu::{prelude::*, Whatever};
-use clap::{Parser, Subcommand};
 
 #[derive(Parser)]
 struct Args {
+    /// Config file to read (defaults to online-test.toml if it exists)
+    #[clap(long, global = true)]
+    config: Option<PathBuf>,
+    /// Database to connect to, overriding the config file and $DATABASE_URL
+    #[clap(long, global = true)]
+    database_url: Option<String>,
     #[clap(subcommand)]
     command: Option<AppCommand>,
 }
@@ -19,21 +40,195 @@ struct Args {
 enum AppCommand {
     /// Operate on database via CLI
     Cli {
+        /// Author recorded on question revisions and grades (defaults to $USER)
+        #[clap(long)]
+        author: Option<String>,
         #[clap(subcommand)]
         command: CliCommand,
     },
     /// Run web server
-    Serve,
+    Serve(ServeArgs),
+    /// Inspect the effective configuration
+    Config {
+        #[clap(subcommand)]
+        command: ConfigCommand,
+    },
+}
+
+#[derive(clap::Args, Default)]
+struct ServeArgs {
+    /// Address to listen on
+    #[clap(long)]
+    ip: Option<IpAddr>,
+    /// Port to listen on
+    #[clap(long)]
+    port: Option<u16>,
+    /// Directory of the static web pages
+    #[clap(long)]
+    static_dir: Option<PathBuf>,
+}
+
+#[derive(Subcommand)]
+enum ConfigCommand {
+    /// Validate the configuration and print the values in effect
+    Check,
 }
 
 #[derive(Subcommand)]
 enum CliCommand {
     /// Add a question to database
-    Add,
-    /// Remove a question from database
-    Remove,
-    /// List the last 100 questions
+    Add(Box<cli::AddArgs>),
+    /// Edit an existing question in place
+    Edit {
+        /// ID of the question to edit
+        id: usize,
+    },
+    /// Retire questions so that they are no longer served in new tests
+    Remove(cli::RemoveArgs),
+    /// List questions, newest 20 by default
+    List(cli::ListArgs),
+    /// Replace the tags of a question
+    Tag(cli::TagArgs),
+    /// Report suspected duplicate questions, optionally merging them
+    Dedupe(cli::DedupeArgs),
+    /// Inspect the revision history of a question
+    Revisions {
+        #[clap(subcommand)]
+        command: RevisionsCommand,
+    },
+    /// Review answers that need manual grading
+    Grade {
+        #[clap(subcommand)]
+        command: GradeCommand,
+    },
+    /// Report pass rates of graded attempts
+    Report(cli::ReportArgs),
+    /// Look up the attempt a certificate was issued for
+    VerifyCertificate {
+        /// Verification code printed on the certificate
+        code: String,
+    },
+    /// Export attempts to CSV or XLSX, optionally filtered by user, group and date
+    ExportScores(cli::ExportArgs),
+    /// Manage groups of users, such as classes
+    Group {
+        #[clap(subcommand)]
+        command: GroupCommand,
+    },
+    /// Attach images and files to questions
+    Media {
+        #[clap(subcommand)]
+        command: MediaCommand,
+    },
+    /// Inspect or apply the database migrations built into this binary
+    Migrate {
+        #[clap(subcommand)]
+        command: MigrateCommand,
+    },
+    /// Add the sample questions to the database
+    Seed {
+        /// Seed even if the database already has questions
+        #[clap(long)]
+        force: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum MigrateCommand {
+    /// List all migrations and whether they are applied
+    Status,
+    /// Apply all pending migrations
+    Up,
+    /// Revert the latest applied migrations
+    Down {
+        /// Number of migrations to revert
+        #[clap(long, default_value_t = 1)]
+        steps: usize,
+    },
+}
+
+#[derive(Subcommand)]
+enum GroupCommand {
+    /// Add users to a group, creating it if needed
+    Add(cli::GroupMembersArgs),
+    /// Remove users from a group
+    Remove(cli::GroupMembersArgs),
+    /// List all groups, or the members of one
+    List {
+        /// Name of the group
+        group: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum MediaCommand {
+    /// Upload a file and attach it to a question
+    Add(cli::MediaAddArgs),
+    /// List the files attached to a question
+    List {
+        /// ID of the question
+        question: usize,
+    },
+    /// Delete an attached file
+    Remove {
+        /// ID of the media
+        id: usize,
+    },
+}
+
+#[derive(Subcommand)]
+enum GradeCommand {
+    /// List items pending review, oldest first
     List,
+    /// Award points and a comment to a pending item
+    Item(cli::GradeArgs),
+}
+
+#[derive(Subcommand)]
+enum RevisionsCommand {
+    /// List all revisions of a question
+    List {
+        /// ID of the question
+        id: usize,
+    },
+    /// Show field-level changes between two revisions (defaults to the latest two)
+    Diff {
+        /// ID of the question
+        id: usize,
+        /// Revision to compare from
+        from: Option<usize>,
+        /// Revision to compare to
+        to: Option<usize>,
+    },
+}
+
+struct Repositories {
+    question: Arc<dyn QuestionRepository>,
+    score: Arc<dyn ScoreRepository>,
+    media: Arc<dyn MediaRepository>,
+    practice: Arc<dyn PracticeRepository>,
+    review: Arc<dyn ReviewRepository>,
+    group: Arc<dyn GroupRepository>,
+}
+
+impl Repositories {
+    // The backend is chosen by the scheme of the database URL.
+    fn connect(config: &Config) -> Result<Self, Whatever> {
+        let url = &config.database.url;
+        let media_dir = &config.media.dir;
+
+        let pool =
+            DatabasePool::build(url).whatever_context("Could not initialize database pool")?;
+
+        Ok(Self {
+            question: Arc::new(QuestionSqlRepository::new(pool.clone())),
+            score: Arc::new(ScoreSqlRepository::new(pool.clone())),
+            media: Arc::new(MediaSqlRepository::new(pool.clone(), media_dir)),
+            practice: Arc::new(PracticeSqlRepository::new(pool.clone())),
+            review: Arc::new(ReviewSqlRepository::new(pool.clone())),
+            group: Arc::new(GroupSqlRepository::new(pool)),
+        })
+    }
 }
 
 #[tokio::main(flavor = "current_thread")]
@@ -41,45 +236,188 @@ enum CliCommand {
 async fn main() -> Result<(), Whatever> {
     let args = Args::parse();
 
-    dotenvy::dotenv().whatever_context("Could not read server configurations for .env")?;
-    let database_url = std::env::var("DATABASE_URL").unwrap_or("production/data.db".to_owned());
-    let listening_ip = std::env::var("LISTENING_IP").unwrap_or("0.0.0.0".to_owned());
-    let listening_port = std::env::var("LISTENING_PORT").unwrap_or("8080".to_owned());
-
-    let config = AsyncSqliteConnectionManager::new(&database_url);
-    let database_pool = AsyncSqlitePool::builder(config)
-        .build()
-        .map(|pool| Arc::new(pool))
-        .whatever_context("Could not initialize database pool")?;
-
-    let question_repository = Arc::new(QuestionSqliteRepository::new(Arc::clone(&database_pool)));
-    let score_repository = Arc::new(ScoreSqliteRepository::new(Arc::clone(&database_pool)));
-
-    match args.command.unwrap_or(AppCommand::Serve) {
-        AppCommand::Cli { command } => match command {
-            CliCommand::Add => {
-                cli::run_add(question_repository).await?;
-            },
-            CliCommand::Remove => {
-                cli::run_remove(question_repository).await?;
-            },
-            CliCommand::List => {
-                cli::run_list(question_repository).await?;
-            },
+    if let Err(err) = dotenvy::dotenv() {
+        if !err.not_found() {
+            return Err(err).whatever_context("Could not read environment variables from .env");
         }
-        AppCommand::Serve => {
-            let core = Arc::new(Core::new(question_repository, score_repository));
+    }
 
-            let listening_addr = format!("{listening_ip}:{listening_port}");
+    let mut config = Config::load(args.config.as_deref())
+        .whatever_context("Could not load configuration")?
+        .with_env(|name| std::env::var(name).ok())
+        .whatever_context("Could not load configuration")?;
+    if let Some(database_url) = args.database_url {
+        config.database.url = database_url;
+    }
+
+    let command = args
+        .command
+        .unwrap_or(AppCommand::Serve(ServeArgs::default()));
+    if let AppCommand::Serve(serve) = &command {
+        config.server.ip = serve.ip.unwrap_or(config.server.ip);
+        config.server.port = serve.port.unwrap_or(config.server.port);
+        if let Some(static_dir) = &serve.static_dir {
+            config.server.static_dir = static_dir.clone();
+        }
+    }
+
+    if let AppCommand::Config {
+        command: ConfigCommand::Check,
+    } = command
+    {
+        return cli::run_config_check(config);
+    }
+    config
+        .validate()
+        .whatever_context("Invalid configuration")?;
+
+    // Pending migrations are applied on every start, unless they are being managed by hand.
+    let migrator = Migrator::new(&config.database.url);
+    if !matches!(
+        command,
+        AppCommand::Cli {
+            command: CliCommand::Migrate { .. },
+            ..
+        }
+    ) {
+        let applied = migrator
+            .run_pending()
+            .await
+            .whatever_context("Could not migrate database")?;
+        for name in applied {
+            eprintln!("Applied migration {name}");
+        }
+    }
+
+    let Repositories {
+        question: question_repository,
+        score: score_repository,
+        media: media_repository,
+        practice: practice_repository,
+        review: review_repository,
+        group: group_repository,
+    } = Repositories::connect(&config)?;
+
+    match command {
+        AppCommand::Cli { author, command } => {
+            let author = author
+                .or_else(|| std::env::var("USER").ok())
+                .unwrap_or("unknown".to_owned());
+
+            match command {
+                CliCommand::Add(args) => {
+                    cli::run_add(question_repository, author, *args).await?;
+                }
+                CliCommand::Edit { id } => {
+                    cli::run_edit(question_repository, id, author).await?;
+                }
+                CliCommand::Remove(args) => {
+                    cli::run_remove(question_repository, args).await?;
+                }
+                CliCommand::List(args) => {
+                    cli::run_list(question_repository, args).await?;
+                }
+                CliCommand::Tag(args) => {
+                    cli::run_tag(question_repository, args).await?;
+                }
+                CliCommand::Dedupe(args) => {
+                    cli::run_dedupe(question_repository, args).await?;
+                }
+                CliCommand::Revisions { command } => match command {
+                    RevisionsCommand::List { id } => {
+                        cli::run_revisions_list(question_repository, id).await?;
+                    }
+                    RevisionsCommand::Diff { id, from, to } => {
+                        cli::run_revisions_diff(question_repository, id, from, to).await?;
+                    }
+                },
+                CliCommand::Grade { command } => {
+                    let service = GradingService::new(
+                        question_repository,
+                        score_repository,
+                        config.session_config().grade_scale,
+                    );
+
+                    match command {
+                        GradeCommand::List => {
+                            cli::run_grade_list(service).await?;
+                        }
+                        GradeCommand::Item(args) => {
+                            cli::run_grade(service, author, args).await?;
+                        }
+                    }
+                }
+                CliCommand::Report(args) => {
+                    cli::run_report(score_repository, args).await?;
+                }
+                CliCommand::VerifyCertificate { code } => {
+                    cli::run_verify_certificate(score_repository, code).await?;
+                }
+                CliCommand::ExportScores(args) => {
+                    cli::run_export_scores(score_repository, group_repository, args).await?;
+                }
+                CliCommand::Group { command } => match command {
+                    GroupCommand::Add(args) => {
+                        cli::run_group_add(group_repository, args).await?;
+                    }
+                    GroupCommand::Remove(args) => {
+                        cli::run_group_remove(group_repository, args).await?;
+                    }
+                    GroupCommand::List { group } => {
+                        cli::run_group_list(group_repository, group).await?;
+                    }
+                },
+                CliCommand::Migrate { command } => match command {
+                    MigrateCommand::Status => {
+                        cli::run_migrate_status(migrator).await?;
+                    }
+                    MigrateCommand::Up => {
+                        cli::run_migrate_up(migrator).await?;
+                    }
+                    MigrateCommand::Down { steps } => {
+                        cli::run_migrate_down(migrator, steps).await?;
+                    }
+                },
+                CliCommand::Seed { force } => {
+                    cli::run_seed(migrator, force).await?;
+                }
+                CliCommand::Media { command } => match command {
+                    MediaCommand::Add(args) => {
+                        cli::run_media_add(media_repository, args).await?;
+                    }
+                    MediaCommand::List { question } => {
+                        cli::run_media_list(media_repository, question).await?;
+                    }
+                    MediaCommand::Remove { id } => {
+                        cli::run_media_remove(media_repository, id).await?;
+                    }
+                },
+            }
+        }
+        AppCommand::Serve(_) => {
+            let core = Arc::new(Core::new(
+                question_repository,
+                score_repository,
+                media_repository,
+                practice_repository,
+                review_repository,
+                config.session_config(),
+            ));
+
+            let listening_addr = config.listening_addr();
             println!("Listening on {listening_addr}");
 
-            Server::new(listening_addr.parse().unwrap(), core)
+            Server::new(listening_addr, core)
                 .await
                 .whatever_context("Could not initialize server")?
+                .with_admin_token(config.server.admin_token)
+                .with_static_dir(config.server.static_dir)
+                .with_shutdown(config.shutdown.timeout(), config.shutdown.auto_grade)
                 .serve()
                 .await
                 .whatever_context("Server error occurred")?;
-        },
+        }
+        AppCommand::Config { .. } => unreachable!("Configuration is checked before connecting"),
     }
 
     Ok(())
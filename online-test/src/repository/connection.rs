@@ -1,8 +1,80 @@
+use std::sync::Arc;
+
 use diesel::SqliteConnection;
-use diesel_async::pooled_connection::deadpool::Pool;
+use diesel_async::pooled_connection::deadpool::{BuildError, Object, Pool, PoolError};
 use diesel_async::pooled_connection::AsyncDieselConnectionManager;
 use diesel_async::sync_connection_wrapper::SyncConnectionWrapper;
+use diesel_async::AsyncPgConnection;
 
 pub type AsyncSqliteConnection = SyncConnectionWrapper<SqliteConnection>;
 pub type AsyncSqliteConnectionManager = AsyncDieselConnectionManager<AsyncSqliteConnection>;
 pub type AsyncSqlitePool = Pool<AsyncSqliteConnection>;
+
+pub type AsyncPgConnectionManager = AsyncDieselConnectionManager<AsyncPgConnection>;
+pub type AsyncPgPool = Pool<AsyncPgConnection>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DatabaseBackend {
+    Sqlite,
+    Postgres,
+}
+
+impl DatabaseBackend {
+    // URLs without a PostgreSQL scheme are SQLite paths, like `production/data.db`.
+    pub fn from_url(url: &str) -> Self {
+        if url.starts_with("postgres://") || url.starts_with("postgresql://") {
+            Self::Postgres
+        } else {
+            Self::Sqlite
+        }
+    }
+}
+
+#[derive(Clone)]
+pub enum DatabasePool {
+    Sqlite(Arc<AsyncSqlitePool>),
+    Postgres(Arc<AsyncPgPool>),
+}
+
+impl DatabasePool {
+    pub fn build(url: &str) -> Result<Self, BuildError> {
+        let pool = match DatabaseBackend::from_url(url) {
+            DatabaseBackend::Sqlite => Self::Sqlite(Arc::new(
+                AsyncSqlitePool::builder(AsyncSqliteConnectionManager::new(url)).build()?,
+            )),
+            DatabaseBackend::Postgres => Self::Postgres(Arc::new(
+                AsyncPgPool::builder(AsyncPgConnectionManager::new(url)).build()?,
+            )),
+        };
+
+        Ok(pool)
+    }
+
+    pub async fn get(&self) -> Result<DatabaseConnection, PoolError> {
+        let connection = match self {
+            Self::Sqlite(pool) => DatabaseConnection::Sqlite(pool.get().await?),
+            Self::Postgres(pool) => DatabaseConnection::Postgres(pool.get().await?),
+        };
+
+        Ok(connection)
+    }
+}
+
+pub enum DatabaseConnection {
+    Sqlite(Object<AsyncSqliteConnection>),
+    Postgres(Object<AsyncPgConnection>),
+}
+
+// Expands `$body` once for each backend with `$name` bound to its connection, so that queries
+// are written once and type-checked against both. Whatever differs between the backends goes
+// through traits implemented for both connection types.
+macro_rules! with_connection {
+    ($connection:expr, |$name:ident| $body:expr) => {
+        match $connection {
+            $crate::repository::connection::DatabaseConnection::Sqlite(mut $name) => $body,
+            $crate::repository::connection::DatabaseConnection::Postgres(mut $name) => $body,
+        }
+    };
+}
+
+pub(crate) use with_connection;
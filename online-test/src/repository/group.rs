@@ -0,0 +1,151 @@
+use std::error::Error;
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+use snafu::prelude::*;
+
+use crate::domain::entity::user::User;
+use crate::domain::repository::group::{GroupRepository, GroupRepositoryError, NotFoundSnafu};
+
+use super::connection::{with_connection, DatabasePool};
+
+pub struct GroupSqlRepository {
+    pool: DatabasePool,
+}
+
+impl GroupSqlRepository {
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl GroupRepository for GroupSqlRepository {
+    async fn add_members(&self, group: &str, users: Vec<User>) -> Result<(), GroupRepositoryError> {
+        use crate::repository::schema::group_members::dsl;
+
+        let connection = self
+            .pool
+            .get()
+            .await
+            .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
+            .whatever_context("Could not connect to database")?;
+
+        with_connection!(connection, |connection| {
+            for user in users {
+                diesel::insert_into(dsl::group_members)
+                    .values(DbMemberInsertion {
+                        group_name: group.to_owned(),
+                        name: user.into(),
+                    })
+                    .on_conflict_do_nothing()
+                    .execute(&mut connection)
+                    .await
+                    .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
+                    .whatever_context("Could not save group members to database")?;
+            }
+
+            Ok(())
+        })
+    }
+
+    async fn remove_members(
+        &self,
+        group: &str,
+        users: Vec<User>,
+    ) -> Result<usize, GroupRepositoryError> {
+        use crate::repository::schema::group_members::dsl;
+
+        let connection = self
+            .pool
+            .get()
+            .await
+            .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
+            .whatever_context("Could not connect to database")?;
+
+        with_connection!(connection, |connection| {
+            let names: Vec<String> = users.into_iter().map(Into::into).collect();
+            let removed = diesel::delete(dsl::group_members)
+                .filter(dsl::group_name.eq(group))
+                .filter(dsl::name.eq_any(names))
+                .execute(&mut connection)
+                .await
+                .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
+                .whatever_context("Could not remove group members from database")?;
+
+            Ok(removed)
+        })
+    }
+
+    async fn list_members(&self, group: &str) -> Result<Vec<User>, GroupRepositoryError> {
+        use crate::repository::schema::group_members::dsl;
+
+        let connection = self
+            .pool
+            .get()
+            .await
+            .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
+            .whatever_context("Could not connect to database")?;
+
+        with_connection!(connection, |connection| {
+            let members: Vec<User> = dsl::group_members
+                .select(dsl::name)
+                .filter(dsl::group_name.eq(group))
+                .order_by(dsl::name.asc())
+                .load::<String>(&mut connection)
+                .await
+                .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
+                .whatever_context("Could not load group members from database")?
+                .into_iter()
+                .map(|name| {
+                    User::try_new(name)
+                        .unwrap_or_else(|_| unreachable!("User should be already validated"))
+                })
+                .collect();
+
+            ensure!(!members.is_empty(), NotFoundSnafu { group });
+            Ok(members)
+        })
+    }
+
+    async fn list_groups(&self) -> Result<Vec<(String, usize)>, GroupRepositoryError> {
+        use crate::repository::schema::group_members::dsl;
+
+        let connection = self
+            .pool
+            .get()
+            .await
+            .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
+            .whatever_context("Could not connect to database")?;
+
+        with_connection!(connection, |connection| {
+            let groups = dsl::group_members
+                .group_by(dsl::group_name)
+                .select((dsl::group_name, diesel::dsl::count_star()))
+                .order_by(dsl::group_name.asc())
+                .load::<(String, i64)>(&mut connection)
+                .await
+                .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
+                .whatever_context("Could not load groups from database")?
+                .into_iter()
+                .map(|(group, count)| (group, count as usize))
+                .collect();
+
+            Ok(groups)
+        })
+    }
+}
+
+impl Debug for GroupSqlRepository {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "GroupSqlRepository {{ pool: DatabasePool }}")
+    }
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = crate::repository::schema::group_members)]
+struct DbMemberInsertion {
+    group_name: String,
+    name: String,
+}
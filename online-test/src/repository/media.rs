@@ -0,0 +1,255 @@
+use std::error::Error;
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+use snafu::prelude::*;
+
+use crate::domain::entity::id::Id;
+use crate::domain::repository::media::{
+    FileNameEmptySnafu, Media, MediaRepository, MediaRepositoryError, NotFoundSnafu,
+    QuestionNotFoundSnafu,
+};
+
+use super::connection::{with_connection, DatabasePool};
+
+// Files are stored under `dir`, named after their media ID.
+pub struct MediaSqlRepository {
+    pool: DatabasePool,
+    dir: PathBuf,
+}
+
+impl MediaSqlRepository {
+    pub fn new<P: AsRef<Path>>(pool: DatabasePool, dir: P) -> Self {
+        Self {
+            pool,
+            dir: dir.as_ref().to_owned(),
+        }
+    }
+
+    fn path(&self, id: i32) -> PathBuf {
+        self.dir.join(id.to_string())
+    }
+}
+
+#[async_trait::async_trait]
+impl MediaRepository for MediaSqlRepository {
+    async fn insert_media(
+        &self,
+        question: Id,
+        file_name: String,
+        data: Vec<u8>,
+    ) -> Result<Id, MediaRepositoryError> {
+        use crate::repository::schema::question_media::dsl;
+        use crate::repository::schema::questions::dsl as questions_dsl;
+
+        let file_name = file_name.trim().to_owned();
+        ensure!(!file_name.is_empty(), FileNameEmptySnafu);
+
+        let connection = self
+            .pool
+            .get()
+            .await
+            .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
+            .whatever_context("Could not connect to database")?;
+
+        with_connection!(connection, |connection| {
+            let found: i64 = questions_dsl::questions
+                .filter(questions_dsl::id.eq(question.inner() as i32))
+                .filter(questions_dsl::retired.eq(false))
+                .count()
+                .get_result(&mut connection)
+                .await
+                .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
+                .whatever_context("Could not load question from database")?;
+            ensure!(found > 0, QuestionNotFoundSnafu { id: question });
+
+            tokio::fs::create_dir_all(&self.dir)
+                .await
+                .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
+                .with_whatever_context(|_| {
+                    format!("Could not create media directory {}", self.dir.display())
+                })?;
+
+            let insertion = DbMediaInsertion {
+                question_id: question.inner() as i32,
+                mime_type: mime_type(&file_name).to_owned(),
+                file_name,
+                size: data.len() as i32,
+                created_at: Utc::now().to_rfc3339(),
+            };
+
+            let id: i32 = diesel::insert_into(dsl::question_media)
+                .values(&insertion)
+                .returning(dsl::id)
+                .get_result(&mut connection)
+                .await
+                .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
+                .whatever_context("Could not save media to database")?;
+
+            if let Err(err) = tokio::fs::write(self.path(id), data).await {
+                let _ = diesel::delete(dsl::question_media.filter(dsl::id.eq(id)))
+                    .execute(&mut connection)
+                    .await;
+
+                return Err(Box::new(err) as Box<dyn Error + Send>)
+                    .whatever_context("Could not write media file");
+            }
+
+            Ok((id as usize).into())
+        })
+    }
+
+    async fn list_media(&self, question: Id) -> Result<Vec<Media>, MediaRepositoryError> {
+        use crate::repository::schema::question_media::dsl;
+
+        let connection = self
+            .pool
+            .get()
+            .await
+            .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
+            .whatever_context("Could not connect to database")?;
+
+        with_connection!(connection, |connection| {
+            let media: Vec<DbMedia> = dsl::question_media
+                .select(DbMedia::as_select())
+                .filter(dsl::question_id.eq(question.inner() as i32))
+                .order_by(dsl::id)
+                .load(&mut connection)
+                .await
+                .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
+                .whatever_context("Could not load media from database")?;
+
+            Ok(media.into_iter().map(Into::into).collect())
+        })
+    }
+
+    async fn find_media(&self, id: Id) -> Result<Media, MediaRepositoryError> {
+        use crate::repository::schema::question_media::dsl;
+
+        let connection = self
+            .pool
+            .get()
+            .await
+            .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
+            .whatever_context("Could not connect to database")?;
+
+        with_connection!(connection, |connection| {
+            let media: Option<DbMedia> = dsl::question_media
+                .select(DbMedia::as_select())
+                .filter(dsl::id.eq(id.inner() as i32))
+                .first(&mut connection)
+                .await
+                .optional()
+                .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
+                .whatever_context("Could not load media from database")?;
+
+            media.map(Into::into).context(NotFoundSnafu { id })
+        })
+    }
+
+    async fn read_media(&self, id: Id) -> Result<Vec<u8>, MediaRepositoryError> {
+        match tokio::fs::read(self.path(id.inner() as i32)).await {
+            Ok(data) => Ok(data),
+            Err(err) if err.kind() == ErrorKind::NotFound => NotFoundSnafu { id }.fail(),
+            Err(err) => Err(Box::new(err) as Box<dyn Error + Send>)
+                .whatever_context("Could not read media file"),
+        }
+    }
+
+    async fn remove_media(&self, id: Id) -> Result<(), MediaRepositoryError> {
+        use crate::repository::schema::question_media::dsl;
+
+        let connection = self
+            .pool
+            .get()
+            .await
+            .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
+            .whatever_context("Could not connect to database")?;
+
+        with_connection!(connection, |connection| {
+            let removed = diesel::delete(dsl::question_media.filter(dsl::id.eq(id.inner() as i32)))
+                .execute(&mut connection)
+                .await
+                .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
+                .whatever_context("Could not remove media from database")?;
+            ensure!(removed > 0, NotFoundSnafu { id });
+
+            match tokio::fs::remove_file(self.path(id.inner() as i32)).await {
+                Err(err) if err.kind() != ErrorKind::NotFound => {
+                    Err(Box::new(err) as Box<dyn Error + Send>)
+                        .whatever_context("Could not remove media file")
+                }
+                _ => Ok(()),
+            }
+        })
+    }
+}
+
+impl Debug for MediaSqlRepository {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(
+            f,
+            "MediaSqlRepository {{ pool: DatabasePool, dir: {:?} }}",
+            self.dir
+        )
+    }
+}
+
+// SVG is deliberately left out as it may carry scripts.
+fn mime_type(file_name: &str) -> &'static str {
+    let extension = Path::new(file_name)
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_ascii_lowercase);
+
+    match extension.as_deref() {
+        Some("png") => "image/png",
+        Some("jpg") | Some("jpeg") => "image/jpeg",
+        Some("gif") => "image/gif",
+        Some("webp") => "image/webp",
+        Some("txt") | Some("log") => "text/plain",
+        Some("pdf") => "application/pdf",
+        _ => "application/octet-stream",
+    }
+}
+
+#[derive(Debug, Queryable, Selectable)]
+#[diesel(table_name = crate::repository::schema::question_media)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite, diesel::pg::Pg))]
+struct DbMedia {
+    id: i32,
+    question_id: i32,
+    file_name: String,
+    mime_type: String,
+    size: i32,
+    created_at: String,
+}
+
+impl From<DbMedia> for Media {
+    fn from(value: DbMedia) -> Self {
+        Self {
+            id: (value.id as usize).into(),
+            question: (value.question_id as usize).into(),
+            file_name: value.file_name,
+            mime_type: value.mime_type,
+            size: value.size as usize,
+            created_at: DateTime::parse_from_rfc3339(&value.created_at)
+                .unwrap_or_else(|_| unreachable!("Creation time should be already validated"))
+                .into(),
+        }
+    }
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = crate::repository::schema::question_media)]
+struct DbMediaInsertion {
+    question_id: i32,
+    file_name: String,
+    mime_type: String,
+    size: i32,
+    created_at: String,
+}
@@ -0,0 +1,321 @@
+use std::collections::HashSet;
+use std::error::Error;
+use std::marker::PhantomData;
+
+use diesel::connection::SimpleConnection;
+use diesel::migration::MigrationSource;
+use diesel::pg::PgConnection;
+use diesel::prelude::*;
+use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
+use snafu::prelude::*;
+
+use crate::repository::connection::{DatabaseBackend, DatabasePool};
+use crate::repository::question::index_search_text;
+
+const SQLITE_MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations/sqlite");
+const POSTGRES_MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations/postgres");
+
+const SEED_QUESTIONS: &str = include_str!("../../seeds/questions.sql");
+
+// Seeded questions are inserted without a revision, so the first one is recorded afterwards.
+const SQLITE_SEED_REVISIONS: &str = "
+INSERT INTO question_revisions (question_id, kind, format, content, option0, option1, option2, option3, answer, author, created_at, items, targets, explanation, points)
+SELECT id, kind, format, content, option0, option1, option2, option3, answer, 'seed', strftime('%Y-%m-%dT%H:%M:%S+00:00', 'now'), items, targets, explanation, points
+FROM questions
+WHERE revision_id = 0;
+
+UPDATE questions
+SET revision_id = (SELECT MAX(r.id) FROM question_revisions r WHERE r.question_id = questions.id)
+WHERE revision_id = 0;
+";
+
+const POSTGRES_SEED_REVISIONS: &str = "
+INSERT INTO question_revisions (question_id, kind, format, content, option0, option1, option2, option3, answer, author, created_at, items, targets, explanation, points)
+SELECT id, kind, format, content, option0, option1, option2, option3, answer, 'seed', to_char(now() AT TIME ZONE 'UTC', 'YYYY-MM-DD\"T\"HH24:MI:SS\"+00:00\"'), items, targets, explanation, points
+FROM questions
+WHERE revision_id = 0;
+
+UPDATE questions
+SET revision_id = (SELECT MAX(r.id) FROM question_revisions r WHERE r.question_id = questions.id)
+WHERE revision_id = 0;
+";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationStatus {
+    pub name: String,
+    pub applied: bool,
+}
+
+// Applies the migrations embedded in the binary for the backend of `database_url`. Diesel's
+// migration harness is synchronous, so every operation opens its own connection on a blocking
+// thread.
+#[derive(Debug, Clone)]
+pub struct Migrator {
+    database_url: String,
+    backend: DatabaseBackend,
+}
+
+impl Migrator {
+    pub fn new<S: Into<String>>(database_url: S) -> Self {
+        let database_url = database_url.into();
+
+        Self {
+            backend: DatabaseBackend::from_url(&database_url),
+            database_url,
+        }
+    }
+
+    pub async fn status(&self) -> Result<Vec<MigrationStatus>, MigrationError> {
+        match self.backend {
+            DatabaseBackend::Sqlite => {
+                BackendMigrator::<SqliteConnection>::new(self)
+                    .status()
+                    .await
+            }
+            DatabaseBackend::Postgres => BackendMigrator::<PgConnection>::new(self).status().await,
+        }
+    }
+
+    // Returns the names of the migrations applied, oldest first.
+    pub async fn run_pending(&self) -> Result<Vec<String>, MigrationError> {
+        let names = match self.backend {
+            DatabaseBackend::Sqlite => {
+                BackendMigrator::<SqliteConnection>::new(self)
+                    .run_pending()
+                    .await
+            }
+            DatabaseBackend::Postgres => {
+                BackendMigrator::<PgConnection>::new(self)
+                    .run_pending()
+                    .await
+            }
+        }?;
+
+        self.index().await?;
+        Ok(names)
+    }
+
+    // Returns the names of the migrations reverted, newest first.
+    pub async fn revert(&self, steps: usize) -> Result<Vec<String>, MigrationError> {
+        match self.backend {
+            DatabaseBackend::Sqlite => {
+                BackendMigrator::<SqliteConnection>::new(self)
+                    .revert(steps)
+                    .await
+            }
+            DatabaseBackend::Postgres => {
+                BackendMigrator::<PgConnection>::new(self)
+                    .revert(steps)
+                    .await
+            }
+        }
+    }
+
+    // Loads the sample questions, returning how many were added. Unless `force` is set, only an
+    // empty question bank is seeded, so that the samples are not added twice.
+    pub async fn seed(&self, force: bool) -> Result<usize, MigrationError> {
+        let seeded = match self.backend {
+            DatabaseBackend::Sqlite => {
+                BackendMigrator::<SqliteConnection>::new(self)
+                    .seed(force)
+                    .await
+            }
+            DatabaseBackend::Postgres => {
+                BackendMigrator::<PgConnection>::new(self).seed(force).await
+            }
+        }?;
+
+        self.index().await?;
+        Ok(seeded)
+    }
+
+    // Questions inserted by SQL are indexed for search afterwards, as the text to index is
+    // extracted from the rendered content.
+    async fn index(&self) -> Result<usize, MigrationError> {
+        let pool = DatabasePool::build(&self.database_url)
+            .map_err(|err| -> Box<dyn Error + Send + Sync> { Box::new(err) })
+            .context(IndexSnafu)?;
+
+        index_search_text(&pool).await.context(IndexSnafu)
+    }
+}
+
+// What differs between the backends when migrating and seeding.
+trait MigrationConnection:
+    Connection + MigrationHarness<Self::Backend> + SimpleConnection + 'static
+{
+    const MIGRATIONS: EmbeddedMigrations;
+    const SEED_REVISIONS: &'static str;
+
+    fn count_questions(&mut self) -> QueryResult<i64>;
+}
+
+impl MigrationConnection for SqliteConnection {
+    const MIGRATIONS: EmbeddedMigrations = SQLITE_MIGRATIONS;
+    const SEED_REVISIONS: &'static str = SQLITE_SEED_REVISIONS;
+
+    fn count_questions(&mut self) -> QueryResult<i64> {
+        use crate::repository::schema::questions::dsl;
+
+        dsl::questions.count().get_result(self)
+    }
+}
+
+impl MigrationConnection for PgConnection {
+    const MIGRATIONS: EmbeddedMigrations = POSTGRES_MIGRATIONS;
+    const SEED_REVISIONS: &'static str = POSTGRES_SEED_REVISIONS;
+
+    fn count_questions(&mut self) -> QueryResult<i64> {
+        use crate::repository::schema::questions::dsl;
+
+        dsl::questions.count().get_result(self)
+    }
+}
+
+struct BackendMigrator<C> {
+    database_url: String,
+    connection: PhantomData<C>,
+}
+
+impl<C: MigrationConnection> BackendMigrator<C> {
+    fn new(migrator: &Migrator) -> Self {
+        Self {
+            database_url: migrator.database_url.clone(),
+            connection: PhantomData,
+        }
+    }
+
+    async fn status(&self) -> Result<Vec<MigrationStatus>, MigrationError> {
+        self.with_connection(|connection| {
+            let applied: HashSet<_> = connection
+                .applied_migrations()
+                .context(MigrateSnafu)?
+                .into_iter()
+                .map(|version| version.to_string())
+                .collect();
+
+            let migrations = MigrationSource::<C::Backend>::migrations(&C::MIGRATIONS)
+                .context(MigrateSnafu)?
+                .into_iter()
+                .map(|migration| MigrationStatus {
+                    name: migration.name().to_string(),
+                    applied: applied.contains(&migration.name().version().to_string()),
+                })
+                .collect();
+
+            Ok(migrations)
+        })
+        .await
+    }
+
+    async fn run_pending(&self) -> Result<Vec<String>, MigrationError> {
+        self.with_connection(|connection| {
+            let versions: Vec<_> = connection
+                .run_pending_migrations(C::MIGRATIONS)
+                .context(MigrateSnafu)?
+                .into_iter()
+                .map(|version| version.to_string())
+                .collect();
+
+            names_of::<C>(&versions)
+        })
+        .await
+    }
+
+    async fn revert(&self, steps: usize) -> Result<Vec<String>, MigrationError> {
+        self.with_connection(move |connection| {
+            let applied = connection.applied_migrations().context(MigrateSnafu)?.len();
+            ensure!(steps <= applied, NothingToRevertSnafu { steps, applied });
+
+            let mut versions = Vec::with_capacity(steps);
+            for _ in 0..steps {
+                let version = connection
+                    .revert_last_migration(C::MIGRATIONS)
+                    .context(MigrateSnafu)?;
+                versions.push(version.to_string());
+            }
+
+            names_of::<C>(&versions)
+        })
+        .await
+    }
+
+    async fn seed(&self, force: bool) -> Result<usize, MigrationError> {
+        self.with_connection(move |connection| {
+            let existing = connection.count_questions().context(SeedSnafu)?;
+            ensure!(
+                force || existing == 0,
+                NotEmptySnafu {
+                    count: existing as usize
+                }
+            );
+
+            connection
+                .transaction(|connection| {
+                    connection.batch_execute(SEED_QUESTIONS)?;
+                    connection.batch_execute(C::SEED_REVISIONS)
+                })
+                .context(SeedSnafu)?;
+
+            let seeded = connection.count_questions().context(SeedSnafu)?;
+            Ok((seeded - existing) as usize)
+        })
+        .await
+    }
+
+    async fn with_connection<T, F>(&self, operation: F) -> Result<T, MigrationError>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut C) -> Result<T, MigrationError> + Send + 'static,
+    {
+        let url = self.database_url.clone();
+
+        tokio::task::spawn_blocking(move || {
+            let mut connection = C::establish(&url).context(ConnectSnafu { url: url.clone() })?;
+            operation(&mut connection)
+        })
+        .await
+        .unwrap_or_else(|err| panic!("Migration task should not fail: {err}"))
+    }
+}
+
+fn names_of<C: MigrationConnection>(versions: &[String]) -> Result<Vec<String>, MigrationError> {
+    let migrations =
+        MigrationSource::<C::Backend>::migrations(&C::MIGRATIONS).context(MigrateSnafu)?;
+
+    let names = versions
+        .iter()
+        .map(|version| {
+            migrations
+                .iter()
+                .find(|m| m.name().version().to_string() == *version)
+                .map_or_else(|| version.clone(), |m| m.name().to_string())
+        })
+        .collect();
+
+    Ok(names)
+}
+
+#[derive(Debug, Snafu)]
+#[non_exhaustive]
+pub enum MigrationError {
+    #[snafu(display("Could not connect to database {url}"))]
+    Connect {
+        url: String,
+        source: ConnectionError,
+    },
+    #[snafu(display("Could not migrate database"))]
+    Migrate {
+        source: Box<dyn Error + Send + Sync>,
+    },
+    #[snafu(display("Could not revert {steps} migrations when {applied} are applied"))]
+    NothingToRevert { steps: usize, applied: usize },
+    #[snafu(display("Could not seed database"))]
+    Seed { source: diesel::result::Error },
+    #[snafu(display("Database already has {count} questions"))]
+    NotEmpty { count: usize },
+    #[snafu(display("Could not index questions for search"))]
+    Index {
+        source: Box<dyn Error + Send + Sync>,
+    },
+}
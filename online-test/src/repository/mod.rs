@@ -1,5 +1,13 @@
 pub mod connection;
+pub mod group;
+pub mod media;
+pub mod migration;
+pub mod practice;
 pub mod question;
+pub mod review;
 pub mod score;
 
 mod schema;
+
+#[cfg(test)]
+mod tests;
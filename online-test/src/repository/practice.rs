@@ -0,0 +1,76 @@
+use std::error::Error;
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+use snafu::prelude::*;
+
+use crate::domain::entity::user::User;
+use crate::domain::repository::practice::{
+    PracticeAttempt, PracticeRepository, PracticeRepositoryError,
+};
+
+use super::connection::{with_connection, DatabasePool};
+
+pub struct PracticeSqlRepository {
+    pool: DatabasePool,
+}
+
+impl PracticeSqlRepository {
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl PracticeRepository for PracticeSqlRepository {
+    async fn insert_attempt(
+        &self,
+        user: User,
+        attempt: PracticeAttempt,
+    ) -> Result<(), PracticeRepositoryError> {
+        use crate::repository::schema::practice_attempts::dsl;
+
+        let connection = self
+            .pool
+            .get()
+            .await
+            .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
+            .whatever_context("Could not connect to database")?;
+
+        with_connection!(connection, |connection| {
+            let insertion = DbAttemptInsertion {
+                name: user.inner().to_owned(),
+                question_id: attempt.question.inner() as i32,
+                revision_id: attempt.revision.inner() as i32,
+                correct: attempt.correct,
+                answered_at: attempt.answered_at.to_rfc3339(),
+            };
+
+            diesel::insert_into(dsl::practice_attempts)
+                .values(&insertion)
+                .execute(&mut connection)
+                .await
+                .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
+                .whatever_context("Could not save practice attempt to database")?;
+
+            Ok(())
+        })
+    }
+}
+
+impl Debug for PracticeSqlRepository {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "PracticeSqlRepository {{ pool: DatabasePool }}")
+    }
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = crate::repository::schema::practice_attempts)]
+struct DbAttemptInsertion {
+    name: String,
+    question_id: i32,
+    revision_id: i32,
+    correct: bool,
+    answered_at: String,
+}
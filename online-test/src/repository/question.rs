@@ -1,176 +1,358 @@
+use std::collections::{BTreeSet, HashMap};
 use std::error::Error;
 use std::fmt::{Debug, Formatter, Result as FmtResult};
-use std::sync::Arc;
 
+use chrono::{DateTime, Utc};
+use diesel::dsl::AsExprOf;
+use diesel::expression::{SqlLiteral, UncheckedBind};
 use diesel::prelude::*;
-use diesel::sql_types::Integer;
-use diesel_async::RunQueryDsl;
+use diesel::sql_types::{Bool, Text};
+use diesel_async::pooled_connection::deadpool::Object;
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::{AsyncConnection, AsyncPgConnection, RunQueryDsl};
 use snafu::prelude::*;
 
 use crate::domain::entity::answer::{
-    CompletionAnswer, MultipleSelectionAnswer, SingleSelectionAnswer, StandardSource,
+    ClozeAnswer, CompletionAnswer, EssayAnswer, MatchingAnswer, MultipleSelectionAnswer,
+    OrderingAnswer, SingleSelectionAnswer, StandardSource, TrueFalseAnswer,
 };
+use crate::domain::entity::content::{Content, ContentFormat};
 use crate::domain::entity::question::{
-    CompletionQuestion, MultipleSelectionQuestion, SingleSelectionQuestion,
+    ClozeQuestion, CompletionQuestion, EssayQuestion, MatchingQuestion, MultipleSelectionQuestion,
+    OrderingQuestion, QuestionKind, SingleSelectionQuestion, TrueFalseQuestion,
 };
 use crate::domain::entity::{id::Id, question::Question};
 use crate::domain::repository::question::{
-    AnswerInvalidSnafu, InsufficientSnafu, QuestionInvalidSnafu, QuestionRepository,
-    QuestionRepositoryError, SelectCount,
+    AnswerInvalidSnafu, ContentInvalidSnafu, InsufficientSnafu, ListFilter, NotFoundSnafu,
+    QuestionDraft, QuestionInvalidSnafu, QuestionRepository, QuestionRepositoryError,
+    QuestionRevision, QuestionSummary, RevisionNotFoundSnafu, SelectCount,
 };
 
-use crate::repository::connection::AsyncSqlitePool;
+use crate::repository::connection::{with_connection, AsyncSqliteConnection, DatabasePool};
 
-pub struct QuestionSqliteRepository {
-    pool: Arc<AsyncSqlitePool>,
+pub struct QuestionSqlRepository {
+    pool: DatabasePool,
 }
 
-impl QuestionSqliteRepository {
-    pub fn new(pool: Arc<AsyncSqlitePool>) -> Self {
+impl QuestionSqlRepository {
+    pub fn new(pool: DatabasePool) -> Self {
         Self { pool }
     }
 }
 
 #[async_trait::async_trait]
-impl QuestionRepository for QuestionSqliteRepository {
-    async fn insert_single_selection(
+impl QuestionRepository for QuestionSqlRepository {
+    async fn insert_question(
         &self,
-        content: String,
-        options: Vec<String>,
-        answer: u32,
-    ) -> Result<(), QuestionRepositoryError> {
+        draft: QuestionDraft,
+        author: String,
+    ) -> Result<Id, QuestionRepositoryError> {
+        use crate::repository::schema::question_revisions::dsl as revisions_dsl;
         use crate::repository::schema::questions::dsl;
 
-        let mut connection = self
+        let connection = self
             .pool
             .get()
             .await
             .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
             .whatever_context("Could not connect to database")?;
 
-        diesel::insert_into(dsl::questions)
-            .values(DbQuestionInsertion::new_single_selection(
-                content, options, answer,
-            )?)
-            .execute(&mut connection)
-            .await
-            .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
-            .whatever_context("Could not save question (single-selection) to database")?;
+        with_connection!(connection, |connection| {
+            let insertion = DbQuestionInsertion::from_draft(draft)?;
+            let created_at = Utc::now().to_rfc3339();
+
+            let id = connection
+                .transaction(|connection| {
+                    async move {
+                        let id: i32 = diesel::insert_into(dsl::questions)
+                            .values(&insertion)
+                            .returning(dsl::id)
+                            .get_result(connection)
+                            .await?;
+
+                        let revision_id: i32 =
+                            diesel::insert_into(revisions_dsl::question_revisions)
+                                .values(DbRevisionInsertion::new(
+                                    id, &insertion, author, created_at,
+                                ))
+                                .returning(revisions_dsl::id)
+                                .get_result(connection)
+                                .await?;
+
+                        diesel::update(dsl::questions)
+                            .filter(dsl::id.eq(id))
+                            .set(dsl::revision_id.eq(revision_id))
+                            .execute(connection)
+                            .await?;
+
+                        Ok(id)
+                    }
+                    .scope_boxed()
+                })
+                .await
+                .map_err(|err: diesel::result::Error| -> Box<dyn Error + Send> { Box::new(err) })
+                .whatever_context("Could not save question to database")?;
 
-        Ok(())
+            Ok((id as usize).into())
+        })
     }
 
-    async fn insert_multiple_selection(
+    async fn update_question(
         &self,
-        content: String,
-        options: Vec<String>,
-        answer: Vec<u32>,
+        id: Id,
+        draft: QuestionDraft,
+        author: String,
     ) -> Result<(), QuestionRepositoryError> {
+        use crate::repository::schema::question_revisions::dsl as revisions_dsl;
         use crate::repository::schema::questions::dsl;
 
-        let mut connection = self
+        let connection = self
             .pool
             .get()
             .await
             .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
             .whatever_context("Could not connect to database")?;
 
-        DbQuestionInsertion::new_multiple_selection(content, options, answer)?
-            .insert_into(dsl::questions)
-            .execute(&mut connection)
-            .await
-            .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
-            .whatever_context("Could not save question (multiple-selection) to database")?;
+        with_connection!(connection, |connection| {
+            let insertion = DbQuestionInsertion::from_draft(draft)?;
+            let created_at = Utc::now().to_rfc3339();
+            let question_id = id.inner() as i32;
+
+            let updated = connection
+                .transaction(|connection| {
+                    async move {
+                        let updated = diesel::update(dsl::questions)
+                            .filter(dsl::id.eq(question_id))
+                            .filter(dsl::retired.eq(false))
+                            .set(&insertion)
+                            .execute(connection)
+                            .await?;
+
+                        if updated == 0 {
+                            return Ok(false);
+                        }
+
+                        let revision_id: i32 =
+                            diesel::insert_into(revisions_dsl::question_revisions)
+                                .values(DbRevisionInsertion::new(
+                                    question_id,
+                                    &insertion,
+                                    author,
+                                    created_at,
+                                ))
+                                .returning(revisions_dsl::id)
+                                .get_result(connection)
+                                .await?;
+
+                        diesel::update(dsl::questions)
+                            .filter(dsl::id.eq(question_id))
+                            .set(dsl::revision_id.eq(revision_id))
+                            .execute(connection)
+                            .await?;
+
+                        Ok(true)
+                    }
+                    .scope_boxed()
+                })
+                .await
+                .map_err(|err: diesel::result::Error| -> Box<dyn Error + Send> { Box::new(err) })
+                .whatever_context("Could not update question")?;
+
+            ensure!(updated, NotFoundSnafu { id });
 
-        Ok(())
+            Ok(())
+        })
     }
 
-    async fn insert_completion(
-        &self,
-        content: String,
-        answer: String,
-    ) -> Result<(), QuestionRepositoryError> {
+    async fn remove_questions(&self, ids: Vec<Id>) -> Result<(), QuestionRepositoryError> {
         use crate::repository::schema::questions::dsl;
 
-        let mut connection = self
+        let connection = self
             .pool
             .get()
             .await
             .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
             .whatever_context("Could not connect to database")?;
 
-        DbQuestionInsertion::new_completion(content, answer)?
-            .insert_into(dsl::questions)
-            .execute(&mut connection)
-            .await
-            .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
-            .whatever_context("Could not save question (completion) to database")?;
+        with_connection!(connection, |connection| {
+            let question_ids: Vec<i32> = ids.iter().map(|id| id.inner() as i32).collect();
+
+            let missing = connection
+                .transaction(|connection| {
+                    async move {
+                        let found: Vec<i32> = dsl::questions
+                            .select(dsl::id)
+                            .filter(dsl::id.eq_any(&question_ids))
+                            .filter(dsl::retired.eq(false))
+                            .load(connection)
+                            .await?;
+
+                        if let Some(&missing) = question_ids.iter().find(|id| !found.contains(id)) {
+                            return Ok(Some(missing));
+                        }
+
+                        diesel::update(dsl::questions)
+                            .filter(dsl::id.eq_any(&question_ids))
+                            .set(dsl::retired.eq(true))
+                            .execute(connection)
+                            .await?;
+
+                        Ok(None)
+                    }
+                    .scope_boxed()
+                })
+                .await
+                .map_err(|err: diesel::result::Error| -> Box<dyn Error + Send> { Box::new(err) })
+                .whatever_context("Could not retire questions")?;
 
-        Ok(())
+            if let Some(id) = missing {
+                return NotFoundSnafu {
+                    id: Id::from(id as usize),
+                }
+                .fail();
+            }
+
+            Ok(())
+        })
     }
 
-    async fn remove_question(&self, id: Id) -> Result<(), QuestionRepositoryError> {
+    async fn set_tags(&self, id: Id, tags: Vec<String>) -> Result<(), QuestionRepositoryError> {
+        use crate::repository::schema::question_tags::dsl as tags_dsl;
         use crate::repository::schema::questions::dsl;
 
-        let mut connection = self
+        let connection = self
             .pool
             .get()
             .await
             .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
             .whatever_context("Could not connect to database")?;
 
-        diesel::delete(dsl::questions)
-            .filter(dsl::id.eq(id.inner() as i32))
-            .execute(&mut connection)
-            .await
-            .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
-            .whatever_context("Could not delete question")?;
+        with_connection!(connection, |connection| {
+            let question_id = id.inner() as i32;
+            let tags: BTreeSet<String> = tags
+                .iter()
+                .map(|tag| tag.trim().to_owned())
+                .filter(|tag| !tag.is_empty())
+                .collect();
+
+            let found = connection
+                .transaction(|connection| {
+                    async move {
+                        let found: i64 = dsl::questions
+                            .filter(dsl::id.eq(question_id))
+                            .filter(dsl::retired.eq(false))
+                            .count()
+                            .get_result(connection)
+                            .await?;
+
+                        if found == 0 {
+                            return Ok(false);
+                        }
+
+                        diesel::delete(tags_dsl::question_tags)
+                            .filter(tags_dsl::question_id.eq(question_id))
+                            .execute(connection)
+                            .await?;
+
+                        for tag in tags {
+                            diesel::insert_into(tags_dsl::question_tags)
+                                .values((
+                                    tags_dsl::question_id.eq(question_id),
+                                    tags_dsl::tag.eq(tag),
+                                ))
+                                .execute(connection)
+                                .await?;
+                        }
+
+                        Ok(true)
+                    }
+                    .scope_boxed()
+                })
+                .await
+                .map_err(|err: diesel::result::Error| -> Box<dyn Error + Send> { Box::new(err) })
+                .whatever_context("Could not save question tags")?;
+
+            ensure!(found, NotFoundSnafu { id });
 
-        Ok(())
+            Ok(())
+        })
     }
 
     async fn list_questions(
         &self,
-        limits: usize,
-    ) -> Result<Vec<Vec<String>>, QuestionRepositoryError> {
+        filter: ListFilter,
+    ) -> Result<Vec<QuestionSummary>, QuestionRepositoryError> {
+        use crate::repository::schema::question_tags::dsl as tags_dsl;
         use crate::repository::schema::questions::dsl;
 
-        let mut connection = self
+        let connection = self
             .pool
             .get()
             .await
             .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
             .whatever_context("Could not connect to database")?;
 
-        let questions: Vec<DbQuestionPreview> = dsl::questions
-            .select(DbQuestionPreview::as_select())
-            .limit(limits as i64)
-            .order_by(dsl::id.desc())
-            .load(&mut connection)
-            .await
-            .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
-            .whatever_context("Could not load questions from database")?;
+        with_connection!(connection, |connection| {
+            let mut query = dsl::questions
+                .select(DbQuestion::as_select())
+                .filter(dsl::retired.eq(false))
+                .into_boxed();
 
-        let questions = questions
-            .into_iter()
-            .rev()
-            .map(|qp| {
-                let id = qp.id.to_string();
-                let kind = match qp.kind {
-                    DbQuestionKind::SINGLE_SELECTION => "Single-Selection".to_owned(),
-                    DbQuestionKind::MULTIPLE_SELECTION => "Multiple-Selection".to_owned(),
-                    DbQuestionKind::COMPLETION => "Completion".to_owned(),
-                    _ => unreachable!(),
-                };
-                let mut content = qp.content.chars().take(65).collect::<String>();
-                if content.len() == 65 {
-                    content.push_str("...");
-                }
-                vec![id, kind, content]
-            })
-            .collect();
+            if let Some(kind) = filter.kind {
+                query = query.filter(dsl::kind.eq(DbQuestionKind::to_db(kind)));
+            }
 
-        Ok(questions)
+            if let Some(tag) = filter.tag {
+                query = query.filter(
+                    dsl::id.eq_any(
+                        tags_dsl::question_tags
+                            .select(tags_dsl::question_id)
+                            .filter(tags_dsl::tag.eq(tag)),
+                    ),
+                );
+            }
+
+            if let Some(search) = filter
+                .search
+                .as_deref()
+                .and_then(|search| connection.search_filter(search))
+            {
+                query = query.filter(search);
+            }
+
+            let questions: Vec<DbQuestion> = query
+                .order_by(dsl::id.desc())
+                .limit(filter.limit as i64)
+                .offset(filter.offset as i64)
+                .load(&mut connection)
+                .await
+                .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
+                .whatever_context("Could not load questions from database")?;
+
+            let ids: Vec<i32> = questions.iter().map(|q| q.id).collect();
+            let mut tags: HashMap<i32, Vec<String>> = HashMap::new();
+            tags_dsl::question_tags
+                .select((tags_dsl::question_id, tags_dsl::tag))
+                .filter(tags_dsl::question_id.eq_any(ids))
+                .order_by(tags_dsl::tag.asc())
+                .load::<(i32, String)>(&mut connection)
+                .await
+                .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
+                .whatever_context("Could not load question tags from database")?
+                .into_iter()
+                .for_each(|(id, tag)| tags.entry(id).or_default().push(tag));
+
+            let questions = questions
+                .into_iter()
+                .rev()
+                .map(|q| QuestionSummary {
+                    tags: tags.remove(&q.id).unwrap_or_default(),
+                    question: q.into_question(),
+                })
+                .collect();
+
+            Ok(questions)
+        })
     }
 
     async fn select_questions(
@@ -179,151 +361,327 @@ impl QuestionRepository for QuestionSqliteRepository {
     ) -> Result<Vec<Question>, QuestionRepositoryError> {
         use crate::repository::schema::questions::dsl;
 
-        let mut connection = self
+        let connection = self
             .pool
             .get()
             .await
             .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
             .whatever_context("Could not connect to database")?;
 
-        let single_selection: Vec<DbQuestion> = dsl::questions
-            .select(DbQuestion::as_select())
-            .filter(dsl::kind.eq(DbQuestionKind::SINGLE_SELECTION))
-            .limit(select_count.single_selection as i64)
-            .order_by(diesel::dsl::sql::<Integer>("RANDOM()"))
-            .load(&mut connection)
-            .await
-            .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
-            .whatever_context("Could not load questions (single-selection) from database")?;
-
-        ensure!(
-            single_selection.len() >= select_count.single_selection,
-            InsufficientSnafu {
-                which: "single-selection",
-                expected: select_count.single_selection,
-                total: single_selection.len(),
+        with_connection!(connection, |connection| {
+            let mut res = Vec::new();
+
+            for (kind, which, count) in kinds_to_select(&select_count) {
+                if count == 0 {
+                    continue;
+                }
+
+                let ids: Vec<i32> = dsl::questions
+                    .select(dsl::id)
+                    .filter(dsl::kind.eq(kind))
+                    .filter(dsl::retired.eq(false))
+                    .load(&mut connection)
+                    .await
+                    .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
+                    .with_whatever_context(|_| {
+                        format!("Could not load questions ({which}) from database")
+                    })?;
+
+                ensure!(
+                    ids.len() >= count,
+                    InsufficientSnafu {
+                        which,
+                        expected: count,
+                        total: ids.len(),
+                    }
+                );
+
+                let mut questions: Vec<DbQuestion> = dsl::questions
+                    .select(DbQuestion::as_select())
+                    .filter(dsl::id.eq_any(pick_random(ids, count)))
+                    .load(&mut connection)
+                    .await
+                    .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
+                    .with_whatever_context(|_| {
+                        format!("Could not load questions ({which}) from database")
+                    })?;
+                fastrand::shuffle(&mut questions);
+
+                res.extend(questions.into_iter().map(DbQuestion::into_question));
             }
-        );
 
-        let multiple_selection: Vec<DbQuestion> = dsl::questions
-            .select(DbQuestion::as_select())
-            .filter(dsl::kind.eq(DbQuestionKind::MULTIPLE_SELECTION))
-            .limit(select_count.multiple_selection as i64)
-            .order_by(diesel::dsl::sql::<Integer>("RANDOM()"))
-            .load(&mut connection)
+            Ok(res)
+        })
+    }
+
+    async fn select_questions_by_id(
+        &self,
+        id: Vec<Id>,
+    ) -> Result<Vec<Question>, QuestionRepositoryError> {
+        use crate::repository::schema::questions::dsl;
+
+        let connection = self
+            .pool
+            .get()
             .await
             .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
-            .whatever_context("Could not load questions (multiple-selection) from database")?;
-
-        ensure!(
-            multiple_selection.len() >= select_count.multiple_selection,
-            InsufficientSnafu {
-                which: "multiple-selection",
-                expected: select_count.multiple_selection,
-                total: multiple_selection.len(),
+            .whatever_context("Could not connect to database")?;
+
+        with_connection!(connection, |connection| {
+            let mut res = Vec::with_capacity(id.len());
+
+            for i in id {
+                let question: DbQuestion = dsl::questions
+                    .select(DbQuestion::as_select())
+                    .filter(dsl::id.eq(i.inner() as i32))
+                    .first(&mut connection)
+                    .await
+                    .optional()
+                    .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
+                    .whatever_context("Could not load question from database by ID")?
+                    .context(NotFoundSnafu { id: i })?;
+
+                res.push(question.into_question());
             }
-        );
 
-        let completion: Vec<DbQuestion> = dsl::questions
-            .select(DbQuestion::as_select())
-            .filter(dsl::kind.eq(DbQuestionKind::COMPLETION))
-            .limit(select_count.completion as i64)
-            .order_by(diesel::dsl::sql::<Integer>("RANDOM()"))
-            .load(&mut connection)
+            Ok(res)
+        })
+    }
+
+    async fn select_active_questions_by_id(
+        &self,
+        id: Vec<Id>,
+    ) -> Result<Vec<Question>, QuestionRepositoryError> {
+        use crate::repository::schema::questions::dsl;
+
+        let connection = self
+            .pool
+            .get()
             .await
             .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
-            .whatever_context("Could not load questions (completion) from database")?;
-
-        ensure!(
-            completion.len() >= select_count.completion,
-            InsufficientSnafu {
-                which: "completion",
-                expected: select_count.completion,
-                total: completion.len(),
-            }
-        );
+            .whatever_context("Could not connect to database")?;
 
-        let mut res = Vec::new();
+        with_connection!(connection, |connection| {
+            let ids: Vec<i32> = id.iter().map(|i| i.inner() as i32).collect();
+            let mut questions: HashMap<i32, DbQuestion> = dsl::questions
+                .select(DbQuestion::as_select())
+                .filter(dsl::id.eq_any(&ids))
+                .filter(dsl::retired.eq(false))
+                .load(&mut connection)
+                .await
+                .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
+                .whatever_context("Could not load questions from database by ID")?
+                .into_iter()
+                .map(|q: DbQuestion| (q.id, q))
+                .collect();
+
+            Ok(ids
+                .into_iter()
+                .filter_map(|i| questions.remove(&i))
+                .map(DbQuestion::into_question)
+                .collect())
+        })
+    }
 
-        for q in single_selection {
-            res.push(q.into_single_question().into());
-        }
+    async fn select_questions_by_revision(
+        &self,
+        revision: Vec<Id>,
+    ) -> Result<Vec<Question>, QuestionRepositoryError> {
+        use crate::repository::schema::question_revisions::dsl;
 
-        for q in multiple_selection {
-            res.push(q.into_multiple_question().into());
-        }
+        let connection = self
+            .pool
+            .get()
+            .await
+            .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
+            .whatever_context("Could not connect to database")?;
 
-        for q in completion {
-            res.push(q.into_completion().into());
-        }
+        with_connection!(connection, |connection| {
+            let mut res = Vec::with_capacity(revision.len());
+
+            for r in revision {
+                let revision: DbRevision = dsl::question_revisions
+                    .select(DbRevision::as_select())
+                    .filter(dsl::id.eq(r.inner() as i32))
+                    .first(&mut connection)
+                    .await
+                    .optional()
+                    .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
+                    .whatever_context("Could not load question revision from database")?
+                    .context(RevisionNotFoundSnafu { id: r })?;
+
+                res.push(QuestionRevision::from(revision).question);
+            }
 
-        Ok(res)
+            Ok(res)
+        })
     }
 
-    async fn select_questions_by_id(
+    async fn list_revisions(
         &self,
-        id: Vec<Id>,
-    ) -> Result<Vec<Question>, QuestionRepositoryError> {
-        use crate::repository::schema::questions::dsl;
+        id: Id,
+    ) -> Result<Vec<QuestionRevision>, QuestionRepositoryError> {
+        use crate::repository::schema::question_revisions::dsl;
 
-        let mut connection = self
+        let connection = self
             .pool
             .get()
             .await
             .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
             .whatever_context("Could not connect to database")?;
 
-        let mut res = Vec::with_capacity(id.len());
-
-        for i in id {
-            let question: DbQuestion = dsl::questions
-                .select(DbQuestion::as_select())
-                .filter(dsl::id.eq(i.inner() as i32))
-                .first(&mut connection)
+        with_connection!(connection, |connection| {
+            let revisions: Vec<DbRevision> = dsl::question_revisions
+                .select(DbRevision::as_select())
+                .filter(dsl::question_id.eq(id.inner() as i32))
+                .order_by(dsl::id.asc())
+                .load(&mut connection)
                 .await
-                .optional()
                 .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
-                .whatever_context("Could not load question from database by ID")?
-                .unwrap_or_else(|| unreachable!("Question of given ID should exist"));
+                .whatever_context("Could not load question revisions from database")?;
+
+            ensure!(!revisions.is_empty(), NotFoundSnafu { id });
+
+            Ok(revisions.into_iter().map(Into::into).collect())
+        })
+    }
+}
+
+// Indexes the questions that were written by SQL rather than by the repository, such as those
+// migrated from before `search_text` existed and seeded ones. Returns how many were indexed.
+pub(super) async fn index_search_text(
+    pool: &DatabasePool,
+) -> Result<usize, Box<dyn Error + Send + Sync>> {
+    use crate::repository::schema::questions::dsl;
 
-            let question = match question.kind {
-                DbQuestionKind::SINGLE_SELECTION => question.into_single_question().into(),
-                DbQuestionKind::MULTIPLE_SELECTION => question.into_multiple_question().into(),
-                DbQuestionKind::COMPLETION => question.into_completion().into(),
-                _ => unreachable!(),
-            };
+    let connection = pool.get().await?;
 
-            res.push(question);
+    with_connection!(connection, |connection| {
+        let questions: Vec<DbQuestion> = dsl::questions
+            .select(DbQuestion::as_select())
+            .filter(dsl::search_text.eq(""))
+            .load(&mut connection)
+            .await?;
+
+        for question in &questions {
+            diesel::update(dsl::questions)
+                .filter(dsl::id.eq(question.id))
+                .set(dsl::search_text.eq(question.search_text()))
+                .execute(&mut connection)
+                .await?;
         }
 
-        Ok(res)
-    }
+        Ok(questions.len())
+    })
 }
 
-impl Debug for QuestionSqliteRepository {
+impl Debug for QuestionSqlRepository {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
-        write!(
-            f,
-            "QuestionSqliteRepository {{ pool: Arc<AsyncSqlitePool> }}"
-        )
+        write!(f, "QuestionSqlRepository {{ pool: DatabasePool }}")
     }
 }
 
 #[derive(Debug, Queryable, Selectable)]
 #[diesel(table_name = crate::repository::schema::questions)]
-#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite, diesel::pg::Pg))]
 struct DbQuestion {
     id: i32,
+    revision_id: i32,
     kind: i32,
+    format: i32,
     content: String,
     option0: Option<String>,
     option1: Option<String>,
     option2: Option<String>,
     option3: Option<String>,
     answer: String,
+    items: Option<String>,
+    targets: Option<String>,
+    explanation: Option<String>,
+    points: Option<i32>,
 }
 
 impl DbQuestion {
+    fn into_question(self) -> Question {
+        let explanation = self.explanation();
+        let points = self.points.map(|p| p as u32);
+        let question: Question = match self.kind {
+            DbQuestionKind::SINGLE_SELECTION => self.into_single_question().into(),
+            DbQuestionKind::MULTIPLE_SELECTION => self.into_multiple_question().into(),
+            DbQuestionKind::COMPLETION => self.into_completion().into(),
+            DbQuestionKind::TRUE_FALSE => self.into_true_false().into(),
+            DbQuestionKind::ORDERING => self.into_ordering().into(),
+            DbQuestionKind::MATCHING => self.into_matching().into(),
+            DbQuestionKind::ESSAY => self.into_essay().into(),
+            DbQuestionKind::CLOZE => self.into_cloze().into(),
+            _ => unreachable!(),
+        };
+
+        question.with_explanation(explanation).with_points(points)
+    }
+
+    fn content(&self) -> Content {
+        Content::sanitized(DbContentFormat::from_db(self.format), &self.content)
+    }
+
+    fn search_text(&self) -> String {
+        search_text(
+            self.format,
+            &self.content,
+            [&self.option0, &self.option1, &self.option2, &self.option3],
+            [&self.items, &self.targets],
+        )
+    }
+
+    fn explanation(&self) -> Option<Content> {
+        let format = DbContentFormat::from_db(self.format);
+        self.explanation
+            .as_ref()
+            .map(|explanation| Content::sanitized(format, explanation))
+    }
+
+    fn options(&self) -> Vec<Content> {
+        let format = DbContentFormat::from_db(self.format);
+        [&self.option0, &self.option1, &self.option2, &self.option3]
+            .into_iter()
+            .map(|option| {
+                option
+                    .as_ref()
+                    .unwrap_or_else(|| unreachable!("Option should not be None"))
+            })
+            .map(|option| Content::sanitized(format, option))
+            .collect()
+    }
+
+    fn items(&self) -> Vec<Content> {
+        self.json_contents(&self.items)
+    }
+
+    fn targets(&self) -> Vec<Content> {
+        self.json_contents(&self.targets)
+    }
+
+    fn json_contents(&self, column: &Option<String>) -> Vec<Content> {
+        let format = DbContentFormat::from_db(self.format);
+        let contents: Vec<String> = column
+            .as_deref()
+            .and_then(|contents| serde_json::from_str(contents).ok())
+            .unwrap_or_else(|| unreachable!("Column should be a JSON array of strings"));
+
+        contents
+            .iter()
+            .map(|content| Content::sanitized(format, content))
+            .collect()
+    }
+
+    fn indices(&self) -> Vec<u32> {
+        self.answer
+            .split_whitespace()
+            .map(|index| index.parse())
+            .collect::<Result<Vec<u32>, _>>()
+            .unwrap_or_else(|_| unreachable!("Answer should be indices separated by space"))
+    }
+
     fn into_single_question(self) -> SingleSelectionQuestion {
         let answer = self
             .answer
@@ -335,20 +693,12 @@ impl DbQuestion {
 
         SingleSelectionQuestion::try_new(
             (self.id as usize).into(),
-            self.content,
-            vec![
-                self.option0
-                    .unwrap_or_else(|| unreachable!("Option should not be None")),
-                self.option1
-                    .unwrap_or_else(|| unreachable!("Option should not be None")),
-                self.option2
-                    .unwrap_or_else(|| unreachable!("Option should not be None")),
-                self.option3
-                    .unwrap_or_else(|| unreachable!("Option should not be None")),
-            ],
+            self.content(),
+            self.options(),
             answer,
         )
         .unwrap_or_else(|_| unreachable!("Question should be already validated"))
+        .with_revision((self.revision_id as usize).into())
     }
 
     fn into_multiple_question(self) -> MultipleSelectionQuestion {
@@ -358,38 +708,186 @@ impl DbQuestion {
 
         MultipleSelectionQuestion::try_new(
             (self.id as usize).into(),
-            self.content,
-            vec![
-                self.option0
-                    .unwrap_or_else(|| unreachable!("Option should not be None")),
-                self.option1
-                    .unwrap_or_else(|| unreachable!("Option should not be None")),
-                self.option2
-                    .unwrap_or_else(|| unreachable!("Option should not be None")),
-                self.option3
-                    .unwrap_or_else(|| unreachable!("Option should not be None")),
-            ],
+            self.content(),
+            self.options(),
             answer,
         )
         .unwrap_or_else(|_| unreachable!("Question should be already validated"))
+        .with_revision((self.revision_id as usize).into())
     }
 
     fn into_completion(self) -> CompletionQuestion {
+        let content = self.content();
         let answer = CompletionAnswer::<StandardSource>::try_new(self.answer)
             .unwrap_or_else(|_| unreachable!("Answer should be already validated"));
 
-        CompletionQuestion::try_new((self.id as usize).into(), self.content, answer)
+        CompletionQuestion::try_new((self.id as usize).into(), content, answer)
             .unwrap_or_else(|_| unreachable!("Question should be already validated"))
+            .with_revision((self.revision_id as usize).into())
+    }
+
+    fn into_true_false(self) -> TrueFalseQuestion {
+        let answer = self
+            .answer
+            .parse()
+            .unwrap_or_else(|_| unreachable!("Answer of true-false should be a boolean"));
+
+        let answer = TrueFalseAnswer::<StandardSource>::try_new(answer)
+            .unwrap_or_else(|_| unreachable!("Answer should be already validated"));
+
+        TrueFalseQuestion::try_new((self.id as usize).into(), self.content(), answer)
+            .unwrap_or_else(|_| unreachable!("Question should be already validated"))
+            .with_revision((self.revision_id as usize).into())
+    }
+
+    fn into_ordering(self) -> OrderingQuestion {
+        let answer = OrderingAnswer::<StandardSource>::try_new(self.indices())
+            .unwrap_or_else(|_| unreachable!("Answer should be already validated"));
+
+        OrderingQuestion::try_new(
+            (self.id as usize).into(),
+            self.content(),
+            self.items(),
+            answer,
+        )
+        .unwrap_or_else(|_| unreachable!("Question should be already validated"))
+        .with_revision((self.revision_id as usize).into())
+    }
+
+    fn into_matching(self) -> MatchingQuestion {
+        let answer = MatchingAnswer::<StandardSource>::try_new(self.indices())
+            .unwrap_or_else(|_| unreachable!("Answer should be already validated"));
+
+        MatchingQuestion::try_new(
+            (self.id as usize).into(),
+            self.content(),
+            self.items(),
+            self.targets(),
+            answer,
+        )
+        .unwrap_or_else(|_| unreachable!("Question should be already validated"))
+        .with_revision((self.revision_id as usize).into())
+    }
+
+    fn into_essay(self) -> EssayQuestion {
+        let content = self.content();
+        let answer = EssayAnswer::<StandardSource>::try_new(self.answer)
+            .unwrap_or_else(|_| unreachable!("Answer should be already validated"));
+
+        EssayQuestion::try_new((self.id as usize).into(), content, answer)
+            .unwrap_or_else(|_| unreachable!("Question should be already validated"))
+            .with_revision((self.revision_id as usize).into())
+    }
+
+    fn into_cloze(self) -> ClozeQuestion {
+        let content = self.content();
+        let answer = serde_json::from_str(&self.answer)
+            .unwrap_or_else(|_| unreachable!("Answer should be a JSON array of string arrays"));
+        let answer = ClozeAnswer::<StandardSource>::try_new(answer)
+            .unwrap_or_else(|_| unreachable!("Answer should be already validated"));
+
+        ClozeQuestion::try_new((self.id as usize).into(), content, answer)
+            .unwrap_or_else(|_| unreachable!("Question should be already validated"))
+            .with_revision((self.revision_id as usize).into())
     }
 }
 
 #[derive(Debug, Queryable, Selectable)]
-#[diesel(table_name = crate::repository::schema::questions)]
-#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
-struct DbQuestionPreview {
+#[diesel(table_name = crate::repository::schema::question_revisions)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite, diesel::pg::Pg))]
+struct DbRevision {
     id: i32,
+    question_id: i32,
     kind: i32,
+    format: i32,
     content: String,
+    option0: Option<String>,
+    option1: Option<String>,
+    option2: Option<String>,
+    option3: Option<String>,
+    answer: String,
+    author: String,
+    created_at: String,
+    items: Option<String>,
+    targets: Option<String>,
+    explanation: Option<String>,
+    points: Option<i32>,
+}
+
+impl From<DbRevision> for QuestionRevision {
+    fn from(value: DbRevision) -> Self {
+        let question = DbQuestion {
+            id: value.question_id,
+            revision_id: value.id,
+            kind: value.kind,
+            format: value.format,
+            content: value.content,
+            option0: value.option0,
+            option1: value.option1,
+            option2: value.option2,
+            option3: value.option3,
+            answer: value.answer,
+            items: value.items,
+            targets: value.targets,
+            explanation: value.explanation,
+            points: value.points,
+        };
+
+        Self {
+            question: question.into_question(),
+            author: value.author,
+            created_at: DateTime::parse_from_rfc3339(&value.created_at)
+                .unwrap_or_else(|_| unreachable!("Creation time should be already validated"))
+                .into(),
+        }
+    }
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = crate::repository::schema::question_revisions)]
+struct DbRevisionInsertion {
+    question_id: i32,
+    kind: i32,
+    format: i32,
+    content: String,
+    option0: Option<String>,
+    option1: Option<String>,
+    option2: Option<String>,
+    option3: Option<String>,
+    answer: String,
+    author: String,
+    created_at: String,
+    items: Option<String>,
+    targets: Option<String>,
+    explanation: Option<String>,
+    points: Option<i32>,
+}
+
+impl DbRevisionInsertion {
+    fn new(
+        question_id: i32,
+        question: &DbQuestionInsertion,
+        author: String,
+        created_at: String,
+    ) -> Self {
+        Self {
+            question_id,
+            kind: question.kind,
+            format: question.format,
+            content: question.content.clone(),
+            option0: question.option0.clone(),
+            option1: question.option1.clone(),
+            option2: question.option2.clone(),
+            option3: question.option3.clone(),
+            answer: question.answer.clone(),
+            author,
+            created_at,
+            items: question.items.clone(),
+            targets: question.targets.clone(),
+            explanation: question.explanation.clone(),
+            points: question.points,
+        }
+    }
 }
 
 struct DbQuestionKind;
@@ -398,23 +896,265 @@ impl DbQuestionKind {
     const SINGLE_SELECTION: i32 = 0;
     const MULTIPLE_SELECTION: i32 = 1;
     const COMPLETION: i32 = 2;
+    const TRUE_FALSE: i32 = 3;
+    const ORDERING: i32 = 4;
+    const MATCHING: i32 = 5;
+    const ESSAY: i32 = 6;
+    const CLOZE: i32 = 7;
+
+    fn to_db(kind: QuestionKind) -> i32 {
+        match kind {
+            QuestionKind::SingleSelection => Self::SINGLE_SELECTION,
+            QuestionKind::MultipleSelection => Self::MULTIPLE_SELECTION,
+            QuestionKind::Completion => Self::COMPLETION,
+            QuestionKind::TrueFalse => Self::TRUE_FALSE,
+            QuestionKind::Ordering => Self::ORDERING,
+            QuestionKind::Matching => Self::MATCHING,
+            QuestionKind::Essay => Self::ESSAY,
+            QuestionKind::Cloze => Self::CLOZE,
+        }
+    }
 }
 
-#[derive(Debug, Insertable)]
+fn kinds_to_select(select_count: &SelectCount) -> [(i32, &'static str, usize); 8] {
+    [
+        (
+            DbQuestionKind::SINGLE_SELECTION,
+            "single-selection",
+            select_count.single_selection,
+        ),
+        (
+            DbQuestionKind::MULTIPLE_SELECTION,
+            "multiple-selection",
+            select_count.multiple_selection,
+        ),
+        (
+            DbQuestionKind::COMPLETION,
+            "completion",
+            select_count.completion,
+        ),
+        (
+            DbQuestionKind::TRUE_FALSE,
+            "true-false",
+            select_count.true_false,
+        ),
+        (DbQuestionKind::ORDERING, "ordering", select_count.ordering),
+        (DbQuestionKind::MATCHING, "matching", select_count.matching),
+        (DbQuestionKind::ESSAY, "essay", select_count.essay),
+        (DbQuestionKind::CLOZE, "cloze", select_count.cloze),
+    ]
+}
+
+// Questions are drawn here rather than by `ORDER BY RANDOM()`, so that every backend selects
+// them the same way.
+fn pick_random(mut ids: Vec<i32>, count: usize) -> Vec<i32> {
+    fastrand::shuffle(&mut ids);
+    ids.truncate(count);
+    ids
+}
+
+type SearchFilter = SqlLiteral<Bool, UncheckedBind<SqlLiteral<Bool>, AsExprOf<String, Text>>>;
+
+// Full-text search is the only query that differs between the backends, as SQLite searches an
+// FTS5 table and PostgreSQL the generated `search` column, which is not in the Diesel schema.
+trait SearchBackend {
+    fn search_filter(&self, search: &str) -> Option<SearchFilter>;
+}
+
+impl SearchBackend for Object<AsyncSqliteConnection> {
+    fn search_filter(&self, search: &str) -> Option<SearchFilter> {
+        let search = fts_query(search)?;
+
+        Some(
+            diesel::dsl::sql::<Bool>(
+                "id IN (SELECT rowid FROM questions_fts WHERE questions_fts MATCH ",
+            )
+            .bind::<Text, _>(search)
+            .sql(")"),
+        )
+    }
+}
+
+impl SearchBackend for Object<AsyncPgConnection> {
+    fn search_filter(&self, search: &str) -> Option<SearchFilter> {
+        if search.trim().is_empty() {
+            return None;
+        }
+
+        Some(
+            diesel::dsl::sql::<Bool>("search @@ plainto_tsquery('simple', ")
+                .bind::<Text, _>(search.to_owned())
+                .sql(")"),
+        )
+    }
+}
+
+// Text that search matches: the content, options, items and targets as rendered, without markup.
+// Neither SQLite nor PostgreSQL can strip HTML, so it is stored along with the question.
+fn search_text(
+    format: i32,
+    content: &str,
+    options: [&Option<String>; 4],
+    lists: [&Option<String>; 2],
+) -> String {
+    let format = DbContentFormat::from_db(format);
+    let listed = lists
+        .into_iter()
+        .flatten()
+        .flat_map(|list| serde_json::from_str::<Vec<String>>(list).unwrap_or_default());
+
+    std::iter::once(content.to_owned())
+        .chain(options.into_iter().flatten().cloned())
+        .chain(listed)
+        .map(|source| Content::sanitized(format, source).text())
+        .filter(|text| !text.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// Every word is quoted so that user input is never parsed as FTS5 query syntax.
+fn fts_query(search: &str) -> Option<String> {
+    let terms: Vec<String> = search
+        .split_whitespace()
+        .map(|term| format!("\"{}\"", term.replace('"', "\"\"")))
+        .collect();
+
+    (!terms.is_empty()).then(|| terms.join(" "))
+}
+
+struct DbContentFormat;
+
+impl DbContentFormat {
+    const PLAIN: i32 = 0;
+    const MARKDOWN: i32 = 1;
+    const HTML: i32 = 2;
+
+    fn from_db(format: i32) -> ContentFormat {
+        match format {
+            Self::PLAIN => ContentFormat::Plain,
+            Self::MARKDOWN => ContentFormat::Markdown,
+            Self::HTML => ContentFormat::Html,
+            _ => unreachable!("Content format should be already validated"),
+        }
+    }
+
+    fn to_db(format: ContentFormat) -> i32 {
+        match format {
+            ContentFormat::Plain => Self::PLAIN,
+            ContentFormat::Markdown => Self::MARKDOWN,
+            ContentFormat::Html => Self::HTML,
+        }
+    }
+}
+
+#[derive(Debug, Insertable, AsChangeset)]
 #[diesel(table_name = crate::repository::schema::questions)]
+#[diesel(treat_none_as_null = true)]
 struct DbQuestionInsertion {
     kind: i32,
+    format: i32,
     content: String,
     option0: Option<String>,
     option1: Option<String>,
     option2: Option<String>,
     option3: Option<String>,
     answer: String,
+    items: Option<String>,
+    targets: Option<String>,
+    explanation: Option<String>,
+    points: Option<i32>,
+    search_text: String,
 }
 
 impl DbQuestionInsertion {
+    fn from_draft(draft: QuestionDraft) -> Result<Self, QuestionRepositoryError> {
+        let explanation = draft
+            .explanation()
+            .filter(|e| !e.trim().is_empty())
+            .map(|e| {
+                Self::check_content(draft.format(), e)?;
+                Ok(e.to_owned())
+            })
+            .transpose()?;
+        let points = draft.points().map(|p| p as i32);
+
+        let insertion = match draft {
+            QuestionDraft::SingleSelection {
+                format,
+                content,
+                options,
+                answer,
+                ..
+            } => Self::new_single_selection(format, content, options, answer),
+            QuestionDraft::MultipleSelection {
+                format,
+                content,
+                options,
+                answer,
+                ..
+            } => Self::new_multiple_selection(format, content, options, answer),
+            QuestionDraft::Completion {
+                format,
+                content,
+                answer,
+                ..
+            } => Self::new_completion(format, content, answer),
+            QuestionDraft::TrueFalse {
+                format,
+                content,
+                answer,
+                ..
+            } => Self::new_true_false(format, content, answer),
+            QuestionDraft::Ordering {
+                format,
+                content,
+                items,
+                answer,
+                ..
+            } => Self::new_ordering(format, content, items, answer),
+            QuestionDraft::Matching {
+                format,
+                content,
+                items,
+                targets,
+                answer,
+                ..
+            } => Self::new_matching(format, content, items, targets, answer),
+            QuestionDraft::Essay {
+                format,
+                content,
+                answer,
+                ..
+            } => Self::new_essay(format, content, answer),
+            QuestionDraft::Cloze {
+                format,
+                content,
+                answer,
+                ..
+            } => Self::new_cloze(format, content, answer),
+        }?;
+
+        Ok(Self {
+            explanation,
+            points,
+            search_text: search_text(
+                insertion.format,
+                &insertion.content,
+                [
+                    &insertion.option0,
+                    &insertion.option1,
+                    &insertion.option2,
+                    &insertion.option3,
+                ],
+                [&insertion.items, &insertion.targets],
+            ),
+            ..insertion
+        })
+    }
+
     #[allow(clippy::get_first)]
     fn new_single_selection(
+        format: ContentFormat,
         content: String,
         options: Vec<String>,
         answer: u32,
@@ -424,8 +1164,8 @@ impl DbQuestionInsertion {
                 .context(AnswerInvalidSnafu)?;
             let _checked_question = SingleSelectionQuestion::try_new(
                 0.into(),
-                content.clone(),
-                options.clone(),
+                Self::check_content(format, &content)?,
+                Self::check_options(format, &options)?,
                 checked_answer,
             )
             .context(QuestionInvalidSnafu)?;
@@ -433,17 +1173,24 @@ impl DbQuestionInsertion {
 
         Ok(Self {
             kind: DbQuestionKind::SINGLE_SELECTION,
+            format: DbContentFormat::to_db(format),
             content,
             option0: options.get(0).cloned(),
             option1: options.get(1).cloned(),
             option2: options.get(2).cloned(),
             option3: options.get(3).cloned(),
             answer: answer.to_string(),
+            items: None,
+            targets: None,
+            explanation: None,
+            points: None,
+            search_text: String::new(),
         })
     }
 
     #[allow(clippy::get_first)]
     fn new_multiple_selection(
+        format: ContentFormat,
         content: String,
         options: Vec<String>,
         answer: Vec<u32>,
@@ -453,8 +1200,8 @@ impl DbQuestionInsertion {
                 .context(AnswerInvalidSnafu)?;
             let _checked_question = MultipleSelectionQuestion::try_new(
                 0.into(),
-                content.clone(),
-                options.clone(),
+                Self::check_content(format, &content)?,
+                Self::check_options(format, &options)?,
                 checked_answer,
             )
             .context(QuestionInvalidSnafu)?;
@@ -462,6 +1209,7 @@ impl DbQuestionInsertion {
 
         Ok(Self {
             kind: DbQuestionKind::MULTIPLE_SELECTION,
+            format: DbContentFormat::to_db(format),
             content,
             option0: options.get(0).cloned(),
             option1: options.get(1).cloned(),
@@ -471,26 +1219,264 @@ impl DbQuestionInsertion {
                 res.push_str(&option.to_string());
                 res
             }),
+            items: None,
+            targets: None,
+            explanation: None,
+            points: None,
+            search_text: String::new(),
         })
     }
 
-    fn new_completion(content: String, answer: String) -> Result<Self, QuestionRepositoryError> {
+    fn new_completion(
+        format: ContentFormat,
+        content: String,
+        answer: String,
+    ) -> Result<Self, QuestionRepositoryError> {
         {
             let checked_answer = CompletionAnswer::<StandardSource>::try_new(answer.clone())
                 .context(AnswerInvalidSnafu)?;
-            let _checked_question =
-                CompletionQuestion::try_new(0.into(), content.clone(), checked_answer)
-                    .context(QuestionInvalidSnafu)?;
+            let _checked_question = CompletionQuestion::try_new(
+                0.into(),
+                Self::check_content(format, &content)?,
+                checked_answer,
+            )
+            .context(QuestionInvalidSnafu)?;
         }
 
         Ok(Self {
             kind: DbQuestionKind::COMPLETION,
+            format: DbContentFormat::to_db(format),
             content,
             option0: None,
             option1: None,
             option2: None,
             option3: None,
             answer,
+            items: None,
+            targets: None,
+            explanation: None,
+            points: None,
+            search_text: String::new(),
         })
     }
+
+    fn new_true_false(
+        format: ContentFormat,
+        content: String,
+        answer: bool,
+    ) -> Result<Self, QuestionRepositoryError> {
+        {
+            let checked_answer =
+                TrueFalseAnswer::<StandardSource>::try_new(answer).context(AnswerInvalidSnafu)?;
+            let _checked_question = TrueFalseQuestion::try_new(
+                0.into(),
+                Self::check_content(format, &content)?,
+                checked_answer,
+            )
+            .context(QuestionInvalidSnafu)?;
+        }
+
+        Ok(Self {
+            kind: DbQuestionKind::TRUE_FALSE,
+            format: DbContentFormat::to_db(format),
+            content,
+            option0: None,
+            option1: None,
+            option2: None,
+            option3: None,
+            answer: answer.to_string(),
+            items: None,
+            targets: None,
+            explanation: None,
+            points: None,
+            search_text: String::new(),
+        })
+    }
+
+    fn new_ordering(
+        format: ContentFormat,
+        content: String,
+        items: Vec<String>,
+        answer: Vec<u32>,
+    ) -> Result<Self, QuestionRepositoryError> {
+        {
+            let checked_answer = OrderingAnswer::<StandardSource>::try_new(answer.clone())
+                .context(AnswerInvalidSnafu)?;
+            let _checked_question = OrderingQuestion::try_new(
+                0.into(),
+                Self::check_content(format, &content)?,
+                Self::check_options(format, &items)?,
+                checked_answer,
+            )
+            .context(QuestionInvalidSnafu)?;
+        }
+
+        Ok(Self {
+            kind: DbQuestionKind::ORDERING,
+            format: DbContentFormat::to_db(format),
+            content,
+            option0: None,
+            option1: None,
+            option2: None,
+            option3: None,
+            answer: Self::indices_text(&answer),
+            items: Some(Self::json_text(&items)),
+            targets: None,
+            explanation: None,
+            points: None,
+            search_text: String::new(),
+        })
+    }
+
+    fn new_matching(
+        format: ContentFormat,
+        content: String,
+        items: Vec<String>,
+        targets: Vec<String>,
+        answer: Vec<u32>,
+    ) -> Result<Self, QuestionRepositoryError> {
+        {
+            let checked_answer = MatchingAnswer::<StandardSource>::try_new(answer.clone())
+                .context(AnswerInvalidSnafu)?;
+            let _checked_question = MatchingQuestion::try_new(
+                0.into(),
+                Self::check_content(format, &content)?,
+                Self::check_options(format, &items)?,
+                Self::check_options(format, &targets)?,
+                checked_answer,
+            )
+            .context(QuestionInvalidSnafu)?;
+        }
+
+        Ok(Self {
+            kind: DbQuestionKind::MATCHING,
+            format: DbContentFormat::to_db(format),
+            content,
+            option0: None,
+            option1: None,
+            option2: None,
+            option3: None,
+            answer: Self::indices_text(&answer),
+            items: Some(Self::json_text(&items)),
+            targets: Some(Self::json_text(&targets)),
+            explanation: None,
+            points: None,
+            search_text: String::new(),
+        })
+    }
+
+    fn new_essay(
+        format: ContentFormat,
+        content: String,
+        answer: String,
+    ) -> Result<Self, QuestionRepositoryError> {
+        {
+            let checked_answer = EssayAnswer::<StandardSource>::try_new(answer.clone())
+                .context(AnswerInvalidSnafu)?;
+            let _checked_question = EssayQuestion::try_new(
+                0.into(),
+                Self::check_content(format, &content)?,
+                checked_answer,
+            )
+            .context(QuestionInvalidSnafu)?;
+        }
+
+        Ok(Self {
+            kind: DbQuestionKind::ESSAY,
+            format: DbContentFormat::to_db(format),
+            content,
+            option0: None,
+            option1: None,
+            option2: None,
+            option3: None,
+            answer,
+            items: None,
+            targets: None,
+            explanation: None,
+            points: None,
+            search_text: String::new(),
+        })
+    }
+
+    fn new_cloze(
+        format: ContentFormat,
+        content: String,
+        answer: Vec<Vec<String>>,
+    ) -> Result<Self, QuestionRepositoryError> {
+        {
+            let checked_answer = ClozeAnswer::<StandardSource>::try_new(answer.clone())
+                .context(AnswerInvalidSnafu)?;
+            let _checked_question = ClozeQuestion::try_new(
+                0.into(),
+                Self::check_content(format, &content)?,
+                checked_answer,
+            )
+            .context(QuestionInvalidSnafu)?;
+        }
+
+        Ok(Self {
+            kind: DbQuestionKind::CLOZE,
+            format: DbContentFormat::to_db(format),
+            content,
+            option0: None,
+            option1: None,
+            option2: None,
+            option3: None,
+            answer: serde_json::to_string(&answer)
+                .unwrap_or_else(|_| unreachable!("Strings should be serialized to JSON")),
+            items: None,
+            targets: None,
+            explanation: None,
+            points: None,
+            search_text: String::new(),
+        })
+    }
+
+    fn indices_text(indices: &[u32]) -> String {
+        indices
+            .iter()
+            .map(u32::to_string)
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    fn json_text(contents: &[String]) -> String {
+        serde_json::to_string(contents)
+            .unwrap_or_else(|_| unreachable!("Strings should be serialized to JSON"))
+    }
+
+    fn check_content(
+        format: ContentFormat,
+        content: &str,
+    ) -> Result<Content, QuestionRepositoryError> {
+        Content::try_new(format, content).context(ContentInvalidSnafu)
+    }
+
+    fn check_options(
+        format: ContentFormat,
+        options: &[String],
+    ) -> Result<Vec<Content>, QuestionRepositoryError> {
+        options
+            .iter()
+            .map(|option| Self::check_content(format, option))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fts_query_quotes_terms() {
+        assert_eq!(fts_query("  "), None);
+        assert_eq!(
+            fts_query("shell OR NOT"),
+            Some(r#""shell" "OR" "NOT""#.to_owned())
+        );
+        assert_eq!(
+            fts_query(r#"say "hi" ls*"#),
+            Some(r#""say" """hi""" "ls*""#.to_owned())
+        );
+    }
 }
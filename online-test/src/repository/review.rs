@@ -0,0 +1,223 @@
+use std::error::Error;
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use diesel::prelude::*;
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::{AsyncConnection, RunQueryDsl};
+use snafu::prelude::*;
+
+use crate::domain::entity::id::Id;
+use crate::domain::entity::review::ReviewCard;
+use crate::domain::entity::user::User;
+use crate::domain::repository::review::{ReviewRepository, ReviewRepositoryError};
+
+use super::connection::{with_connection, DatabasePool};
+
+pub struct ReviewSqlRepository {
+    pool: DatabasePool,
+}
+
+impl ReviewSqlRepository {
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl ReviewRepository for ReviewSqlRepository {
+    async fn find_cards(
+        &self,
+        user: &User,
+        questions: Vec<Id>,
+    ) -> Result<Vec<ReviewCard>, ReviewRepositoryError> {
+        use crate::repository::schema::review_cards::dsl;
+
+        let connection = self
+            .pool
+            .get()
+            .await
+            .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
+            .whatever_context("Could not connect to database")?;
+
+        with_connection!(connection, |connection| {
+            let question_ids: Vec<i32> = questions.iter().map(|q| q.inner() as i32).collect();
+            let cards: Vec<DbReviewCard> = dsl::review_cards
+                .select(DbReviewCard::as_select())
+                .filter(dsl::name.eq(user.inner()))
+                .filter(dsl::question_id.eq_any(question_ids))
+                .load(&mut connection)
+                .await
+                .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
+                .whatever_context("Could not load review cards from database")?;
+
+            Ok(cards.into_iter().map(Into::into).collect())
+        })
+    }
+
+    async fn save_cards(
+        &self,
+        user: User,
+        cards: Vec<ReviewCard>,
+    ) -> Result<(), ReviewRepositoryError> {
+        use crate::repository::schema::review_cards::dsl;
+
+        let connection = self
+            .pool
+            .get()
+            .await
+            .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
+            .whatever_context("Could not connect to database")?;
+
+        with_connection!(connection, |connection| {
+            let cards: Vec<DbReviewCard> = cards
+                .into_iter()
+                .map(|card| DbReviewCard::from_card(&user, card))
+                .collect();
+
+            connection
+                .transaction(|connection| {
+                    async move {
+                        for card in cards {
+                            diesel::insert_into(dsl::review_cards)
+                                .values(&card)
+                                .on_conflict((dsl::name, dsl::question_id))
+                                .do_update()
+                                .set(&card)
+                                .execute(connection)
+                                .await?;
+                        }
+
+                        Ok(())
+                    }
+                    .scope_boxed()
+                })
+                .await
+                .map_err(|err: diesel::result::Error| -> Box<dyn Error + Send> { Box::new(err) })
+                .whatever_context("Could not save review cards to database")?;
+
+            Ok(())
+        })
+    }
+
+    async fn remove_stale_cards(&self, user: &User) -> Result<(), ReviewRepositoryError> {
+        use crate::repository::schema::questions::dsl as questions_dsl;
+        use crate::repository::schema::review_cards::dsl;
+
+        let connection = self
+            .pool
+            .get()
+            .await
+            .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
+            .whatever_context("Could not connect to database")?;
+
+        with_connection!(connection, |connection| {
+            let active = questions_dsl::questions
+                .select(questions_dsl::id)
+                .filter(questions_dsl::retired.eq(false));
+
+            diesel::delete(dsl::review_cards)
+                .filter(dsl::name.eq(user.inner()))
+                .filter(diesel::dsl::not(dsl::question_id.eq_any(active)))
+                .execute(&mut connection)
+                .await
+                .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
+                .whatever_context("Could not remove stale review cards from database")?;
+
+            Ok(())
+        })
+    }
+
+    async fn list_due(
+        &self,
+        user: &User,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<Id>, ReviewRepositoryError> {
+        use crate::repository::schema::questions::dsl as questions_dsl;
+        use crate::repository::schema::review_cards::dsl;
+
+        let connection = self
+            .pool
+            .get()
+            .await
+            .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
+            .whatever_context("Could not connect to database")?;
+
+        with_connection!(connection, |connection| {
+            let active = questions_dsl::questions
+                .select(questions_dsl::id)
+                .filter(questions_dsl::retired.eq(false));
+
+            let due: Vec<i32> = dsl::review_cards
+                .select(dsl::question_id)
+                .filter(dsl::name.eq(user.inner()))
+                .filter(dsl::due_at.le(format_due(now)))
+                .filter(dsl::correct.lt(dsl::attempts))
+                .filter(dsl::question_id.eq_any(active))
+                .order_by(dsl::due_at.asc())
+                .load(&mut connection)
+                .await
+                .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
+                .whatever_context("Could not load due review cards from database")?;
+
+            Ok(due.into_iter().map(|id| (id as usize).into()).collect())
+        })
+    }
+}
+
+impl Debug for ReviewSqlRepository {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "ReviewSqlRepository {{ pool: DatabasePool }}")
+    }
+}
+
+// Due times are compared as text, so they are kept at a fixed width.
+fn format_due(due: DateTime<Utc>) -> String {
+    due.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+#[derive(Debug, Queryable, Selectable, Insertable, AsChangeset)]
+#[diesel(table_name = crate::repository::schema::review_cards)]
+#[diesel(primary_key(name, question_id))]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite, diesel::pg::Pg))]
+struct DbReviewCard {
+    name: String,
+    question_id: i32,
+    repetitions: i32,
+    interval_days: i32,
+    ease: f32,
+    due_at: String,
+    attempts: i32,
+    correct: i32,
+}
+
+impl DbReviewCard {
+    fn from_card(user: &User, card: ReviewCard) -> Self {
+        Self {
+            name: user.inner().to_owned(),
+            question_id: card.question.inner() as i32,
+            repetitions: card.repetitions as i32,
+            interval_days: card.interval_days as i32,
+            ease: card.ease,
+            due_at: format_due(card.due),
+            attempts: card.attempts as i32,
+            correct: card.correct as i32,
+        }
+    }
+}
+
+impl From<DbReviewCard> for ReviewCard {
+    fn from(value: DbReviewCard) -> Self {
+        Self {
+            question: (value.question_id as usize).into(),
+            repetitions: value.repetitions as u32,
+            interval_days: value.interval_days as u32,
+            ease: value.ease,
+            due: DateTime::parse_from_rfc3339(&value.due_at)
+                .unwrap_or_else(|_| unreachable!("Due time should be already validated"))
+                .into(),
+            attempts: value.attempts as u32,
+            correct: value.correct as u32,
+        }
+    }
+}
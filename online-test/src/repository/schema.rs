@@ -1,5 +1,79 @@
 // @generated automatically by Diesel CLI.
 
+diesel::table! {
+    attempt_items (id) {
+        id -> Integer,
+        score_id -> Integer,
+        revision_id -> Integer,
+        correct -> Bool,
+        credit -> Float,
+        pending -> Bool,
+        response -> Nullable<Text>,
+        comment -> Nullable<Text>,
+        grader -> Nullable<Text>,
+        graded_at -> Nullable<Text>,
+        points -> Integer,
+        penalty -> Float,
+    }
+}
+
+diesel::table! {
+    group_members (group_name, name) {
+        group_name -> Text,
+        name -> Text,
+    }
+}
+
+diesel::table! {
+    practice_attempts (id) {
+        id -> Integer,
+        name -> Text,
+        question_id -> Integer,
+        revision_id -> Integer,
+        correct -> Bool,
+        answered_at -> Text,
+    }
+}
+
+diesel::table! {
+    question_media (id) {
+        id -> Integer,
+        question_id -> Integer,
+        file_name -> Text,
+        mime_type -> Text,
+        size -> Integer,
+        created_at -> Text,
+    }
+}
+
+diesel::table! {
+    question_revisions (id) {
+        id -> Integer,
+        question_id -> Integer,
+        kind -> Integer,
+        format -> Integer,
+        content -> Text,
+        option0 -> Nullable<Text>,
+        option1 -> Nullable<Text>,
+        option2 -> Nullable<Text>,
+        option3 -> Nullable<Text>,
+        answer -> Text,
+        author -> Text,
+        created_at -> Text,
+        items -> Nullable<Text>,
+        targets -> Nullable<Text>,
+        explanation -> Nullable<Text>,
+        points -> Nullable<Integer>,
+    }
+}
+
+diesel::table! {
+    question_tags (question_id, tag) {
+        question_id -> Integer,
+        tag -> Text,
+    }
+}
+
 diesel::table! {
     questions (id) {
         id -> Integer,
@@ -10,6 +84,27 @@ diesel::table! {
         option2 -> Nullable<Text>,
         option3 -> Nullable<Text>,
         answer -> Text,
+        format -> Integer,
+        revision_id -> Integer,
+        retired -> Bool,
+        items -> Nullable<Text>,
+        targets -> Nullable<Text>,
+        explanation -> Nullable<Text>,
+        points -> Nullable<Integer>,
+        search_text -> Text,
+    }
+}
+
+diesel::table! {
+    review_cards (name, question_id) {
+        name -> Text,
+        question_id -> Integer,
+        repetitions -> Integer,
+        interval_days -> Integer,
+        ease -> Float,
+        due_at -> Text,
+        attempts -> Integer,
+        correct -> Integer,
     }
 }
 
@@ -20,10 +115,25 @@ diesel::table! {
         score -> Float,
         end_time -> Text,
         duration -> Integer,
+        pending -> Bool,
+        points -> Float,
+        total_points -> Integer,
+        penalty -> Float,
+        grade -> Nullable<Text>,
+        passed -> Nullable<Bool>,
+        certificate -> Nullable<Text>,
+        adaptive -> Bool,
     }
 }
 
 diesel::allow_tables_to_appear_in_same_query!(
+    attempt_items,
+    group_members,
+    practice_attempts,
+    question_media,
+    question_revisions,
+    question_tags,
     questions,
+    review_cards,
     scores,
 );
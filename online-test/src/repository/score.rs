@@ -1,26 +1,34 @@
+use std::collections::HashMap;
 use std::error::Error;
 use std::fmt::{Debug, Formatter, Result as FmtResult};
-use std::sync::Arc;
 
 use chrono::{DateTime, Utc};
 use diesel::prelude::*;
-use diesel_async::RunQueryDsl;
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::{AsyncConnection, RunQueryDsl};
 use snafu::{prelude::*, OptionExt};
 use tokio::time::Duration;
 
-use crate::domain::entity::{score::Score, user::User};
+use crate::domain::entity::certificate::{Certificate, VerificationCode};
+use crate::domain::entity::grade::Grade;
+use crate::domain::entity::id::Id;
+use crate::domain::entity::score::{Points, Score};
+use crate::domain::entity::test::ItemResult;
+use crate::domain::entity::user::User;
 use crate::domain::repository::score::{
-    NotFoundSnafu, Record, ScoreRepository, ScoreRepositoryError,
+    AttemptKind, CertificateNotFoundSnafu, GradeRecord, ItemGrade, ItemStatistics, NotFoundSnafu,
+    NotPassedSnafu, PendingItem, PendingItemNotFoundSnafu, Record, RecordFilter, ScoreRepository,
+    ScoreRepositoryError,
 };
 
-use super::connection::AsyncSqlitePool;
+use super::connection::{with_connection, DatabasePool};
 
-pub struct ScoreSqliteRepository {
-    pool: Arc<AsyncSqlitePool>,
+pub struct ScoreSqlRepository {
+    pool: DatabasePool,
 }
 
-impl ScoreSqliteRepository {
-    pub fn new(pool: Arc<AsyncSqlitePool>) -> Self {
+impl ScoreSqlRepository {
+    pub fn new(pool: DatabasePool) -> Self {
         Self { pool }
     }
 
@@ -29,79 +37,131 @@ impl ScoreSqliteRepository {
         user: &User,
         limit: i64,
         score_desc: bool,
+        counted_only: bool,
     ) -> Result<Vec<Record>, ScoreRepositoryError> {
         use crate::repository::schema::scores::dsl;
 
-        let mut connection = self
+        let connection = self
             .pool
             .get()
             .await
             .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
             .whatever_context("Could not connect to database")?;
 
-        let stmt = dsl::scores
-            .select(DbScore::as_select())
-            .filter(dsl::name.eq(user.inner()))
-            .limit(limit);
+        with_connection!(connection, |connection| {
+            let mut stmt = dsl::scores
+                .select(DbScore::as_select())
+                .filter(dsl::name.eq(user.inner()))
+                .limit(limit)
+                .into_boxed();
 
-        let res = if score_desc {
-            stmt.order_by(dsl::score.desc()).load(&mut connection).await
-        } else {
-            stmt.order_by(dsl::id.desc()).load(&mut connection).await
-        };
+            // Scores of attempts still pending lack the points of the items left to grade, and
+            // adaptive attempts are not tests.
+            if counted_only {
+                stmt = stmt
+                    .filter(dsl::pending.eq(false))
+                    .filter(dsl::adaptive.eq(false));
+            }
 
-        let scores = res
-            .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
-            .whatever_context("Could not load scores from database")?
-            .into_iter()
-            .map(Into::into)
-            .collect();
+            let res = if score_desc {
+                stmt.order_by(dsl::score.desc()).load(&mut connection).await
+            } else {
+                stmt.order_by(dsl::id.desc()).load(&mut connection).await
+            };
+
+            let scores = res
+                .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
+                .whatever_context("Could not load scores from database")?
+                .into_iter()
+                .map(Into::into)
+                .collect();
 
-        Ok(scores)
+            Ok(scores)
+        })
     }
 }
 
 #[async_trait::async_trait]
-impl ScoreRepository for ScoreSqliteRepository {
+impl ScoreRepository for ScoreSqlRepository {
+    #[allow(clippy::too_many_arguments)]
     async fn insert(
         &self,
         user: User,
-        score: Score,
+        kind: AttemptKind,
+        points: Points,
+        grade: Option<Grade>,
         end_time: DateTime<Utc>,
         duration: Duration,
+        items: Vec<ItemResult>,
     ) -> Result<(), ScoreRepositoryError> {
+        use crate::repository::schema::attempt_items::dsl as items_dsl;
         use crate::repository::schema::scores::dsl;
 
-        let mut connection = self
+        let connection = self
             .pool
             .get()
             .await
             .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
             .whatever_context("Could not connect to database")?;
 
-        let insertion = DbScoreInsertion {
-            name: user.inner().to_owned(),
-            score: score.inner(),
-            end_time: end_time.to_rfc3339(),
-            duration: duration.as_secs() as i32,
-        };
+        with_connection!(connection, |connection| {
+            let insertion = DbScoreInsertion {
+                name: user.inner().to_owned(),
+                score: points.score().inner(),
+                end_time: end_time.to_rfc3339(),
+                duration: duration.as_secs() as i32,
+                pending: items.iter().any(|i| i.pending),
+                points: points.earned(),
+                total_points: points.total() as i32,
+                penalty: points.penalty(),
+                grade: grade.as_ref().map(|g| g.letter().to_owned()),
+                passed: grade.as_ref().map(Grade::passed),
+                adaptive: kind == AttemptKind::Adaptive,
+            };
 
-        diesel::insert_into(dsl::scores)
-            .values(insertion)
-            .execute(&mut connection)
-            .await
-            .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
-            .whatever_context("Could not save score to database")?;
+            connection
+                .transaction(|connection| {
+                    async move {
+                        let score_id: i32 = diesel::insert_into(dsl::scores)
+                            .values(insertion)
+                            .returning(dsl::id)
+                            .get_result(connection)
+                            .await?;
 
-        Ok(())
+                        for item in items {
+                            diesel::insert_into(items_dsl::attempt_items)
+                                .values(DbAttemptItemInsertion {
+                                    score_id,
+                                    revision_id: item.revision.inner() as i32,
+                                    correct: item.correct,
+                                    credit: item.credit,
+                                    points: item.points as i32,
+                                    penalty: item.penalty,
+                                    pending: item.pending,
+                                    response: item.response,
+                                })
+                                .execute(connection)
+                                .await?;
+                        }
+
+                        Ok(())
+                    }
+                    .scope_boxed()
+                })
+                .await
+                .map_err(|err: diesel::result::Error| -> Box<dyn Error + Send> { Box::new(err) })
+                .whatever_context("Could not save score to database")?;
+
+            Ok(())
+        })
     }
 
     async fn query_all_sorted(&self, user: &User) -> Result<Vec<Record>, ScoreRepositoryError> {
-        self.query_impl(&user, 100, true).await
+        self.query_impl(user, 100, true, false).await
     }
 
     async fn query_best(&self, user: &User) -> Result<Record, ScoreRepositoryError> {
-        self.query_impl(&user, 1, true)
+        self.query_impl(user, 1, true, true)
             .await?
             .into_iter()
             .next()
@@ -109,27 +169,476 @@ impl ScoreRepository for ScoreSqliteRepository {
     }
 
     async fn query_latest(&self, user: &User) -> Result<Record, ScoreRepositoryError> {
-        self.query_impl(&user, 1, false)
+        self.query_impl(user, 1, false, false)
             .await?
             .into_iter()
             .next()
             .context(NotFoundSnafu { user: user.clone() })
     }
+
+    async fn list_pending(&self) -> Result<Vec<PendingItem>, ScoreRepositoryError> {
+        use crate::repository::schema::attempt_items::dsl as items_dsl;
+        use crate::repository::schema::scores::dsl;
+
+        let connection = self
+            .pool
+            .get()
+            .await
+            .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
+            .whatever_context("Could not connect to database")?;
+
+        with_connection!(connection, |connection| {
+            let items: Vec<DbPendingItem> = items_dsl::attempt_items
+                .select(DbPendingItem::as_select())
+                .filter(items_dsl::pending.eq(true))
+                .order_by(items_dsl::id.asc())
+                .load(&mut connection)
+                .await
+                .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
+                .whatever_context("Could not load pending items from database")?;
+
+            let score_ids: Vec<i32> = items.iter().map(|i| i.score_id).collect();
+            let scores: HashMap<i32, (String, String)> = dsl::scores
+                .select((dsl::id, (dsl::name, dsl::end_time)))
+                .filter(dsl::id.eq_any(score_ids))
+                .load::<(i32, (String, String))>(&mut connection)
+                .await
+                .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
+                .whatever_context("Could not load scores from database")?
+                .into_iter()
+                .collect();
+
+            let items = items
+                .into_iter()
+                .map(|item| {
+                    let (name, end_time) = scores
+                        .get(&item.score_id)
+                        .unwrap_or_else(|| unreachable!("Item should belong to a score"));
+
+                    PendingItem {
+                        id: (item.id as usize).into(),
+                        score: (item.score_id as usize).into(),
+                        revision: (item.revision_id as usize).into(),
+                        points: item.points as u32,
+                        user: User::try_new(name)
+                            .unwrap_or_else(|_| unreachable!("User should be already validated")),
+                        response: item.response.unwrap_or_default(),
+                        end_time: DateTime::parse_from_rfc3339(end_time)
+                            .unwrap_or_else(|_| {
+                                unreachable!("End time should be already validated")
+                            })
+                            .into(),
+                    }
+                })
+                .collect();
+
+            Ok(items)
+        })
+    }
+
+    async fn grade_item(&self, id: Id, grade: ItemGrade) -> Result<Id, ScoreRepositoryError> {
+        use crate::repository::schema::attempt_items::dsl;
+
+        let connection = self
+            .pool
+            .get()
+            .await
+            .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
+            .whatever_context("Could not connect to database")?;
+
+        with_connection!(connection, |connection| {
+            let item_id = id.inner() as i32;
+
+            let score_id = connection
+                .transaction(|connection| {
+                    async move {
+                        let updated = diesel::update(dsl::attempt_items)
+                            .filter(dsl::id.eq(item_id))
+                            .filter(dsl::pending.eq(true))
+                            .set((
+                                dsl::credit.eq(grade.credit),
+                                dsl::correct.eq(grade.credit >= 1.0),
+                                dsl::pending.eq(false),
+                                dsl::comment.eq(grade.comment),
+                                dsl::grader.eq(grade.grader),
+                                dsl::graded_at.eq(grade.graded_at.to_rfc3339()),
+                            ))
+                            .execute(connection)
+                            .await?;
+
+                        if updated == 0 {
+                            return Ok(None);
+                        }
+
+                        dsl::attempt_items
+                            .select(dsl::score_id)
+                            .filter(dsl::id.eq(item_id))
+                            .get_result::<i32>(connection)
+                            .await
+                            .map(Some)
+                    }
+                    .scope_boxed()
+                })
+                .await
+                .map_err(|err: diesel::result::Error| -> Box<dyn Error + Send> { Box::new(err) })
+                .whatever_context("Could not save grade to database")?
+                .context(PendingItemNotFoundSnafu { id })?;
+
+            Ok((score_id as usize).into())
+        })
+    }
+
+    async fn query_items(&self, score: Id) -> Result<Vec<ItemResult>, ScoreRepositoryError> {
+        use crate::repository::schema::attempt_items::dsl;
+        use crate::repository::schema::question_revisions::dsl as revisions_dsl;
+
+        let connection = self
+            .pool
+            .get()
+            .await
+            .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
+            .whatever_context("Could not connect to database")?;
+
+        with_connection!(connection, |connection| {
+            let items: Vec<DbAttemptItem> = dsl::attempt_items
+                .select(DbAttemptItem::as_select())
+                .filter(dsl::score_id.eq(score.inner() as i32))
+                .order_by(dsl::id.asc())
+                .load(&mut connection)
+                .await
+                .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
+                .whatever_context("Could not load items from database")?;
+
+            let revision_ids: Vec<i32> = items.iter().map(|i| i.revision_id).collect();
+            let questions: HashMap<i32, i32> = revisions_dsl::question_revisions
+                .select((revisions_dsl::id, revisions_dsl::question_id))
+                .filter(revisions_dsl::id.eq_any(revision_ids))
+                .load::<(i32, i32)>(&mut connection)
+                .await
+                .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
+                .whatever_context("Could not load question revisions from database")?
+                .into_iter()
+                .collect();
+
+            items
+                .into_iter()
+                .map(|item| {
+                    let question =
+                        questions.get(&item.revision_id).with_whatever_context(|| {
+                            format!("Could not find question revision {}", item.revision_id)
+                        })?;
+
+                    Ok(ItemResult {
+                        question: (*question as usize).into(),
+                        revision: (item.revision_id as usize).into(),
+                        correct: item.correct,
+                        credit: item.credit,
+                        points: item.points as u32,
+                        penalty: item.penalty,
+                        pending: item.pending,
+                        response: item.response,
+                    })
+                })
+                .collect()
+        })
+    }
+
+    async fn query_item_statistics(
+        &self,
+        questions: Vec<Id>,
+    ) -> Result<HashMap<Id, ItemStatistics>, ScoreRepositoryError> {
+        use crate::repository::schema::attempt_items::dsl;
+        use crate::repository::schema::question_revisions::dsl as revisions_dsl;
+
+        let connection = self
+            .pool
+            .get()
+            .await
+            .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
+            .whatever_context("Could not connect to database")?;
+
+        with_connection!(connection, |connection| {
+            let question_ids: Vec<i32> = questions.iter().map(|q| q.inner() as i32).collect();
+            let revisions: HashMap<i32, i32> = revisions_dsl::question_revisions
+                .select((revisions_dsl::id, revisions_dsl::question_id))
+                .filter(revisions_dsl::question_id.eq_any(question_ids))
+                .load::<(i32, i32)>(&mut connection)
+                .await
+                .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
+                .whatever_context("Could not load question revisions from database")?
+                .into_iter()
+                .collect();
+
+            let attempts: Vec<(i32, bool)> = dsl::attempt_items
+                .select((dsl::revision_id, dsl::correct))
+                .filter(dsl::revision_id.eq_any(revisions.keys().copied().collect::<Vec<_>>()))
+                .filter(dsl::pending.eq(false))
+                .load(&mut connection)
+                .await
+                .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
+                .whatever_context("Could not load items from database")?;
+
+            let mut statistics: HashMap<Id, ItemStatistics> = HashMap::new();
+            for (revision_id, correct) in attempts {
+                let question = revisions.get(&revision_id).with_whatever_context(|| {
+                    format!("Could not find question revision {revision_id}")
+                })?;
+                let entry = statistics.entry((*question as usize).into()).or_default();
+                entry.attempts += 1;
+                entry.correct += usize::from(correct);
+            }
+
+            Ok(statistics)
+        })
+    }
+
+    async fn finalize(
+        &self,
+        id: Id,
+        points: Points,
+        grade: Grade,
+    ) -> Result<(), ScoreRepositoryError> {
+        use crate::repository::schema::scores::dsl;
+
+        let connection = self
+            .pool
+            .get()
+            .await
+            .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
+            .whatever_context("Could not connect to database")?;
+
+        with_connection!(connection, |connection| {
+            diesel::update(dsl::scores)
+                .filter(dsl::id.eq(id.inner() as i32))
+                .set((
+                    dsl::score.eq(points.score().inner()),
+                    dsl::points.eq(points.earned()),
+                    dsl::total_points.eq(points.total() as i32),
+                    dsl::penalty.eq(points.penalty()),
+                    dsl::grade.eq(grade.letter()),
+                    dsl::passed.eq(grade.passed()),
+                    dsl::pending.eq(false),
+                ))
+                .execute(&mut connection)
+                .await
+                .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
+                .whatever_context("Could not save score to database")?;
+
+            Ok(())
+        })
+    }
+
+    async fn list_records(
+        &self,
+        filter: RecordFilter,
+    ) -> Result<Vec<(User, Record)>, ScoreRepositoryError> {
+        use crate::repository::schema::scores::dsl;
+
+        let connection = self
+            .pool
+            .get()
+            .await
+            .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
+            .whatever_context("Could not connect to database")?;
+
+        with_connection!(connection, |connection| {
+            let mut query = dsl::scores
+                .select((dsl::name, DbScore::as_select()))
+                .order_by(dsl::id.asc())
+                .into_boxed();
+
+            if let Some(users) = filter.users {
+                let names: Vec<String> = users.into_iter().map(Into::into).collect();
+                query = query.filter(dsl::name.eq_any(names));
+            }
+
+            // End times are stored as RFC 3339 text, so the range is checked once they are parsed.
+            let records = query
+                .load::<(String, DbScore)>(&mut connection)
+                .await
+                .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
+                .whatever_context("Could not load scores from database")?
+                .into_iter()
+                .map(|(name, score)| {
+                    let user = User::try_new(name)
+                        .unwrap_or_else(|_| unreachable!("User should be already validated"));
+                    (user, Record::from(score))
+                })
+                .filter(|(_, record)| {
+                    filter.since.is_none_or(|since| record.end_time >= since)
+                        && filter.until.is_none_or(|until| record.end_time < until)
+                })
+                .collect();
+
+            Ok(records)
+        })
+    }
+
+    async fn list_grades(&self) -> Result<Vec<GradeRecord>, ScoreRepositoryError> {
+        use crate::repository::schema::scores::dsl;
+
+        let connection = self
+            .pool
+            .get()
+            .await
+            .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
+            .whatever_context("Could not connect to database")?;
+
+        with_connection!(connection, |connection| {
+            let grades = dsl::scores
+                .select((dsl::name, dsl::grade, dsl::passed, dsl::end_time))
+                .filter(dsl::pending.eq(false))
+                .filter(dsl::adaptive.eq(false))
+                .order_by(dsl::id.asc())
+                .load::<(String, Option<String>, Option<bool>, String)>(&mut connection)
+                .await
+                .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
+                .whatever_context("Could not load grades from database")?
+                .into_iter()
+                .filter_map(|(name, grade, passed, end_time)| {
+                    Some(GradeRecord {
+                        user: User::try_new(name)
+                            .unwrap_or_else(|_| unreachable!("User should be already validated")),
+                        grade: Grade::new(grade?, passed?),
+                        end_time: DateTime::parse_from_rfc3339(&end_time)
+                            .unwrap_or_else(|_| {
+                                unreachable!("End time should be already validated")
+                            })
+                            .into(),
+                    })
+                })
+                .collect();
+
+            Ok(grades)
+        })
+    }
+
+    async fn issue_certificate(
+        &self,
+        user: &User,
+        code: VerificationCode,
+    ) -> Result<Certificate, ScoreRepositoryError> {
+        use crate::repository::schema::scores::dsl;
+
+        let connection = self
+            .pool
+            .get()
+            .await
+            .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
+            .whatever_context("Could not connect to database")?;
+
+        with_connection!(connection, |connection| {
+            let name = user.inner().to_owned();
+            let issued = connection
+                .transaction(|connection| {
+                    async move {
+                        let best = dsl::scores
+                            .select((dsl::id, dsl::certificate, DbScore::as_select()))
+                            .filter(dsl::name.eq(name))
+                            .filter(dsl::pending.eq(false))
+                            .filter(dsl::adaptive.eq(false))
+                            .filter(dsl::passed.eq(true))
+                            .order_by((dsl::score.desc(), dsl::id.asc()))
+                            .first::<(i32, Option<String>, DbScore)>(connection)
+                            .await
+                            .optional()?;
+
+                        let Some((id, certificate, score)) = best else {
+                            return Ok(None);
+                        };
+
+                        let certificate = match certificate {
+                            Some(certificate) => certificate,
+                            None => {
+                                diesel::update(dsl::scores)
+                                    .filter(dsl::id.eq(id))
+                                    .set(dsl::certificate.eq(code.inner()))
+                                    .execute(connection)
+                                    .await?;
+                                code.inner().to_owned()
+                            }
+                        };
+
+                        Ok(Some((certificate, score)))
+                    }
+                    .scope_boxed()
+                })
+                .await
+                .map_err(|err: diesel::result::Error| -> Box<dyn Error + Send> { Box::new(err) })
+                .whatever_context("Could not save certificate to database")?
+                .context(NotPassedSnafu { user: user.clone() })?;
+
+            let (certificate, score) = issued;
+            Ok(into_certificate(user.clone(), certificate, score))
+        })
+    }
+
+    async fn find_certificate(
+        &self,
+        code: &VerificationCode,
+    ) -> Result<Certificate, ScoreRepositoryError> {
+        use crate::repository::schema::scores::dsl;
+
+        let connection = self
+            .pool
+            .get()
+            .await
+            .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
+            .whatever_context("Could not connect to database")?;
+
+        with_connection!(connection, |connection| {
+            let (name, score) = dsl::scores
+                .select((dsl::name, DbScore::as_select()))
+                .filter(dsl::certificate.eq(code.inner()))
+                .first::<(String, DbScore)>(&mut connection)
+                .await
+                .optional()
+                .map_err(|err| -> Box<dyn Error + Send> { Box::new(err) })
+                .whatever_context("Could not load certificate from database")?
+                .context(CertificateNotFoundSnafu { code: code.clone() })?;
+
+            let user = User::try_new(name)
+                .unwrap_or_else(|_| unreachable!("User should be already validated"));
+            Ok(into_certificate(user, code.inner().to_owned(), score))
+        })
+    }
 }
 
-impl Debug for ScoreSqliteRepository {
+fn into_certificate(user: User, code: String, score: DbScore) -> Certificate {
+    let record = Record::from(score);
+
+    Certificate {
+        code: VerificationCode::try_new(code)
+            .unwrap_or_else(|_| unreachable!("Certificate should be already validated")),
+        user,
+        score: record.score,
+        points: record.points,
+        grade: record
+            .grade
+            .unwrap_or_else(|| unreachable!("Certified attempt should be graded")),
+        end_time: record.end_time,
+    }
+}
+
+impl Debug for ScoreSqlRepository {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
-        write!(f, "ScoreSqliteRepository {{ pool: Arc<AsyncSqlitePool> }}")
+        write!(f, "ScoreSqlRepository {{ pool: DatabasePool }}")
     }
 }
 
 #[derive(Debug, Queryable, Selectable)]
 #[diesel(table_name = crate::repository::schema::scores)]
-#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite, diesel::pg::Pg))]
 struct DbScore {
     score: f32,
     end_time: String,
     duration: i32,
+    pending: bool,
+    points: f32,
+    total_points: i32,
+    penalty: f32,
+    grade: Option<String>,
+    passed: Option<bool>,
 }
 
 impl From<DbScore> for Record {
@@ -137,10 +646,14 @@ impl From<DbScore> for Record {
         Self {
             score: Score::try_new(value.score)
                 .unwrap_or_else(|_| unreachable!("Score should be already validated")),
+            points: Points::new(value.points, value.total_points as u32)
+                .with_penalty(value.penalty),
+            grade: value.grade.zip(value.passed).map(|(g, p)| Grade::new(g, p)),
             end_time: DateTime::parse_from_rfc3339(&value.end_time)
                 .unwrap_or_else(|_| unreachable!("End time should be already validated"))
                 .into(),
             duration: Duration::from_secs(value.duration as u64),
+            pending: value.pending,
         }
     }
 }
@@ -152,4 +665,48 @@ struct DbScoreInsertion {
     score: f32,
     end_time: String,
     duration: i32,
+    pending: bool,
+    points: f32,
+    total_points: i32,
+    penalty: f32,
+    grade: Option<String>,
+    passed: Option<bool>,
+    adaptive: bool,
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = crate::repository::schema::attempt_items)]
+struct DbAttemptItemInsertion {
+    score_id: i32,
+    revision_id: i32,
+    correct: bool,
+    credit: f32,
+    points: i32,
+    penalty: f32,
+    pending: bool,
+    response: Option<String>,
+}
+
+#[derive(Debug, Queryable, Selectable)]
+#[diesel(table_name = crate::repository::schema::attempt_items)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite, diesel::pg::Pg))]
+struct DbAttemptItem {
+    revision_id: i32,
+    correct: bool,
+    credit: f32,
+    points: i32,
+    penalty: f32,
+    pending: bool,
+    response: Option<String>,
+}
+
+#[derive(Debug, Queryable, Selectable)]
+#[diesel(table_name = crate::repository::schema::attempt_items)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite, diesel::pg::Pg))]
+struct DbPendingItem {
+    id: i32,
+    score_id: i32,
+    revision_id: i32,
+    points: i32,
+    response: Option<String>,
 }
@@ -0,0 +1,805 @@
+// Tests shared by every backend. Each test runs on a database of its own, which is dropped
+// afterwards. PostgreSQL tests are ignored by default. Run them with `cargo test -- --ignored` and
+// `TEST_POSTGRES_URL` pointing at a server they may create databases on, such as
+// `postgres://postgres@localhost/postgres`.
+
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use chrono::{Duration as ChronoDuration, Utc};
+use diesel::connection::SimpleConnection;
+use diesel::pg::PgConnection;
+use diesel::Connection;
+use tokio::time::Duration;
+
+use crate::domain::entity::certificate::VerificationCode;
+use crate::domain::entity::content::ContentFormat;
+use crate::domain::entity::grade::Grade;
+use crate::domain::entity::id::Id;
+use crate::domain::entity::question::{AbstractQuestion, QuestionKind};
+use crate::domain::entity::review::ReviewCard;
+use crate::domain::entity::score::Points;
+use crate::domain::entity::test::ItemResult;
+use crate::domain::entity::user::User;
+use crate::domain::repository::question::{
+    ListFilter, QuestionDraft, QuestionRepository, QuestionRepositoryError, SelectCount,
+};
+use crate::domain::repository::review::ReviewRepository;
+use crate::domain::repository::score::{
+    AttemptKind, ItemGrade, ItemStatistics, RecordFilter, ScoreRepository, ScoreRepositoryError,
+};
+use crate::repository::connection::DatabasePool;
+use crate::repository::migration::Migrator;
+use crate::repository::question::QuestionSqlRepository;
+use crate::repository::review::ReviewSqlRepository;
+use crate::repository::score::ScoreSqlRepository;
+
+const POSTGRES_URL_VAR: &str = "TEST_POSTGRES_URL";
+
+struct TestRepositories {
+    url: String,
+    question: Arc<dyn QuestionRepository>,
+    score: Arc<dyn ScoreRepository>,
+    review: Arc<dyn ReviewRepository>,
+    // Declared last, so that the pools are closed before the database is dropped.
+    _database: TestDatabase,
+}
+
+impl TestRepositories {
+    async fn sqlite() -> Self {
+        let path = std::env::temp_dir().join(format!("online-test-{:016x}.db", fastrand::u64(..)));
+        let url = path.to_str().unwrap().to_owned();
+        let database = TestDatabase::Sqlite(path);
+
+        Self::connect(&url, database).await
+    }
+
+    async fn postgres() -> Self {
+        let server_url = std::env::var(POSTGRES_URL_VAR)
+            .unwrap_or_else(|_| panic!("{POSTGRES_URL_VAR} should point at a PostgreSQL server"));
+
+        let name = format!("online_test_{:016x}", fastrand::u64(..));
+        PgConnection::establish(&server_url)
+            .unwrap()
+            .batch_execute(&format!("CREATE DATABASE {name}"))
+            .unwrap();
+        let (server, _) = server_url.rsplit_once('/').unwrap();
+        let url = format!("{server}/{name}");
+        let database = TestDatabase::Postgres { server_url, name };
+
+        Self::connect(&url, database).await
+    }
+
+    async fn connect(url: &str, database: TestDatabase) -> Self {
+        Migrator::new(url).run_pending().await.unwrap();
+        let pool = DatabasePool::build(url).unwrap();
+
+        Self {
+            url: url.to_owned(),
+            question: Arc::new(QuestionSqlRepository::new(pool.clone())),
+            score: Arc::new(ScoreSqlRepository::new(pool.clone())),
+            review: Arc::new(ReviewSqlRepository::new(pool)),
+            _database: database,
+        }
+    }
+}
+
+enum TestDatabase {
+    Sqlite(PathBuf),
+    Postgres { server_url: String, name: String },
+}
+
+impl Drop for TestDatabase {
+    fn drop(&mut self) {
+        match self {
+            Self::Sqlite(path) => {
+                let _ = std::fs::remove_file(path);
+            }
+            Self::Postgres { server_url, name } => {
+                if let Ok(mut connection) = PgConnection::establish(server_url) {
+                    let _ = connection
+                        .batch_execute(&format!("DROP DATABASE IF EXISTS {name} WITH (FORCE)"));
+                }
+            }
+        }
+    }
+}
+
+fn true_false(content: &str, answer: bool) -> QuestionDraft {
+    QuestionDraft::TrueFalse {
+        format: ContentFormat::Plain,
+        content: content.to_owned(),
+        answer,
+        explanation: None,
+        points: None,
+    }
+}
+
+fn single_selection(content: &str, options: [&str; 4]) -> QuestionDraft {
+    QuestionDraft::SingleSelection {
+        format: ContentFormat::Plain,
+        content: content.to_owned(),
+        options: options.map(str::to_owned).to_vec(),
+        answer: 1,
+        explanation: Some("Because it is".to_owned()),
+        points: Some(3),
+    }
+}
+
+fn completion(content: &str) -> QuestionDraft {
+    QuestionDraft::Completion {
+        format: ContentFormat::Plain,
+        content: content.to_owned(),
+        answer: "ls".to_owned(),
+        explanation: None,
+        points: None,
+    }
+}
+
+fn filter() -> ListFilter {
+    ListFilter {
+        kind: None,
+        tag: None,
+        search: None,
+        limit: 20,
+        offset: 0,
+    }
+}
+
+fn select_count(true_false: usize, completion: usize) -> SelectCount {
+    SelectCount {
+        single_selection: 0,
+        multiple_selection: 0,
+        completion,
+        true_false,
+        ordering: 0,
+        matching: 0,
+        essay: 0,
+        cloze: 0,
+    }
+}
+
+fn item(question: Id, revision: Id, credit: f32, pending: bool) -> ItemResult {
+    ItemResult {
+        question,
+        revision,
+        correct: credit >= 1.0,
+        credit,
+        points: 1,
+        penalty: 0.0,
+        pending,
+        response: pending.then(|| "It depends".to_owned()),
+    }
+}
+
+async fn latest_revision(repositories: &TestRepositories, id: Id) -> Id {
+    let revisions = repositories.question.list_revisions(id).await.unwrap();
+    revisions.last().unwrap().question.revision()
+}
+
+async fn check_question_revisions(repositories: &TestRepositories) {
+    let repository = &repositories.question;
+
+    let id = repository
+        .insert_question(
+            single_selection("Which shell?", ["sh", "bash", "zsh", "fish"]),
+            "alice".to_owned(),
+        )
+        .await
+        .unwrap();
+    repository
+        .update_question(
+            id,
+            single_selection("Which shell is default?", ["sh", "bash", "zsh", "fish"]),
+            "bob".to_owned(),
+        )
+        .await
+        .unwrap();
+
+    let revisions = repository.list_revisions(id).await.unwrap();
+    let authors: Vec<_> = revisions.iter().map(|r| r.author.as_str()).collect();
+    assert_eq!(authors, ["alice", "bob"]);
+
+    let first = revisions[0].question.revision();
+    let old = repository
+        .select_questions_by_revision(vec![first])
+        .await
+        .unwrap();
+    assert_eq!(old[0].id(), id);
+    assert_eq!(old[0].content().source(), "Which shell?");
+
+    let current = repository.select_questions_by_id(vec![id]).await.unwrap();
+    assert_eq!(current[0].content().source(), "Which shell is default?");
+    assert_eq!(current[0].revision(), revisions[1].question.revision());
+    assert_eq!(current[0].points(), Some(3));
+    assert_eq!(
+        current[0].explanation().map(|e| e.source()),
+        Some("Because it is")
+    );
+
+    repository.remove_questions(vec![id]).await.unwrap();
+    // Retired questions are still loaded by ID for grading the tests holding them.
+    let retired = repository.select_questions_by_id(vec![id]).await.unwrap();
+    assert_eq!(retired[0].id(), id);
+    assert!(repository
+        .select_active_questions_by_id(vec![id, Id::from(999)])
+        .await
+        .unwrap()
+        .is_empty());
+    assert!(matches!(
+        repository
+            .select_questions_by_id(vec![id, Id::from(999)])
+            .await,
+        Err(QuestionRepositoryError::NotFound { id }) if id == Id::from(999)
+    ));
+    assert!(matches!(
+        repository
+            .update_question(id, true_false("Gone", true), "bob".to_owned())
+            .await,
+        Err(QuestionRepositoryError::NotFound { .. })
+    ));
+    assert!(repository
+        .list_questions(filter())
+        .await
+        .unwrap()
+        .is_empty());
+}
+
+async fn check_question_update_clears_columns(repositories: &TestRepositories) {
+    let repository = &repositories.question;
+
+    let id = repository
+        .insert_question(
+            single_selection("Which shell?", ["sh", "bash", "zsh", "fish"]),
+            "alice".to_owned(),
+        )
+        .await
+        .unwrap();
+    // A draft without options, explanation or points replaces the columns they were stored in.
+    repository
+        .update_question(id, true_false("Bash is a shell", true), "bob".to_owned())
+        .await
+        .unwrap();
+
+    let current = repository.select_questions_by_id(vec![id]).await.unwrap();
+    assert_eq!(current[0].kind(), QuestionKind::TrueFalse);
+    assert_eq!(current[0].points(), None);
+    assert!(current[0].explanation().is_none());
+
+    let listed = repository.list_questions(filter()).await.unwrap();
+    assert_eq!(listed[0].question.kind(), QuestionKind::TrueFalse);
+
+    let revisions = repository.list_revisions(id).await.unwrap();
+    assert_eq!(revisions[0].question.points(), Some(3));
+    assert_eq!(revisions[1].question.points(), None);
+}
+
+async fn check_question_listing(repositories: &TestRepositories) {
+    let repository = &repositories.question;
+
+    let shell = repository
+        .insert_question(
+            single_selection("Which shell?", ["sh", "bash", "zsh", "fish"]),
+            "alice".to_owned(),
+        )
+        .await
+        .unwrap();
+    let kernel = repository
+        .insert_question(true_false("Linux is a kernel", true), "alice".to_owned())
+        .await
+        .unwrap();
+    let listing = repository
+        .insert_question(completion("List files with ___"), "alice".to_owned())
+        .await
+        .unwrap();
+
+    repository
+        .set_tags(shell, vec!["shell".to_owned(), " basics ".to_owned()])
+        .await
+        .unwrap();
+    repository
+        .set_tags(listing, vec!["basics".to_owned()])
+        .await
+        .unwrap();
+
+    let all = repository.list_questions(filter()).await.unwrap();
+    let ids: Vec<_> = all.iter().map(|s| s.question.id()).collect();
+    assert_eq!(ids, [shell, kernel, listing]);
+    assert_eq!(all[0].tags, ["basics", "shell"]);
+
+    let by_kind = repository
+        .list_questions(ListFilter {
+            kind: Some(QuestionKind::TrueFalse),
+            ..filter()
+        })
+        .await
+        .unwrap();
+    assert_eq!(by_kind.len(), 1);
+    assert_eq!(by_kind[0].question.id(), kernel);
+
+    let by_tag = repository
+        .list_questions(ListFilter {
+            tag: Some("basics".to_owned()),
+            ..filter()
+        })
+        .await
+        .unwrap();
+    let ids: Vec<_> = by_tag.iter().map(|s| s.question.id()).collect();
+    assert_eq!(ids, [shell, listing]);
+
+    // Options are searched as well as the content.
+    let by_search = repository
+        .list_questions(ListFilter {
+            search: Some("ZSH".to_owned()),
+            ..filter()
+        })
+        .await
+        .unwrap();
+    assert_eq!(by_search.len(), 1);
+    assert_eq!(by_search[0].question.id(), shell);
+
+    let page = repository
+        .list_questions(ListFilter {
+            limit: 1,
+            offset: 1,
+            ..filter()
+        })
+        .await
+        .unwrap();
+    assert_eq!(page.len(), 1);
+    assert_eq!(page[0].question.id(), kernel);
+
+    assert!(matches!(
+        repository.set_tags(Id::from(999), vec![]).await,
+        Err(QuestionRepositoryError::NotFound { .. })
+    ));
+
+    // Nothing is retired when any of the questions cannot be.
+    assert!(matches!(
+        repository.remove_questions(vec![kernel, Id::from(999)]).await,
+        Err(QuestionRepositoryError::NotFound { id }) if id == Id::from(999)
+    ));
+    assert_eq!(repository.list_questions(filter()).await.unwrap().len(), 3);
+    repository
+        .remove_questions(vec![kernel, listing])
+        .await
+        .unwrap();
+    let ids: Vec<_> = repository
+        .list_questions(filter())
+        .await
+        .unwrap()
+        .iter()
+        .map(|s| s.question.id())
+        .collect();
+    assert_eq!(ids, [shell]);
+}
+
+async fn check_question_search(repositories: &TestRepositories) {
+    let repository = &repositories.question;
+
+    let linked = repository
+        .insert_question(
+            QuestionDraft::Completion {
+                format: ContentFormat::Html,
+                content: r#"<p>Print files with <a href="https://example.com/cat" title="concatenate">cat</a> ___</p>"#.to_owned(),
+                answer: "cat".to_owned(),
+                explanation: None,
+                points: None,
+            },
+            "alice".to_owned(),
+        )
+        .await
+        .unwrap();
+
+    let search = |search: &str| {
+        repository.list_questions(ListFilter {
+            search: Some(search.to_owned()),
+            ..filter()
+        })
+    };
+
+    let found = search("print CAT").await.unwrap();
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].question.id(), linked);
+
+    // Markup is not indexed, only the text that is displayed.
+    for markup in ["href", "example", "concatenate", "p"] {
+        assert!(search(markup).await.unwrap().is_empty(), "{markup}");
+    }
+
+    // Query syntax is searched for as text rather than failing.
+    assert!(search(r#"print" OR (cat* NOT"#).await.unwrap().is_empty());
+
+    // Seeded questions are inserted by SQL and indexed afterwards.
+    Migrator::new(&repositories.url).seed(true).await.unwrap();
+    assert!(!search("running processes").await.unwrap().is_empty());
+    assert!(search("code").await.unwrap().is_empty());
+}
+
+async fn check_random_selection(repositories: &TestRepositories) {
+    let repository = &repositories.question;
+
+    let mut true_false_ids = HashSet::new();
+    for i in 0..5 {
+        let id = repository
+            .insert_question(
+                true_false(&format!("Statement {i}"), true),
+                "alice".to_owned(),
+            )
+            .await
+            .unwrap();
+        true_false_ids.insert(id);
+    }
+    for i in 0..2 {
+        repository
+            .insert_question(completion(&format!("Command {i} ___")), "alice".to_owned())
+            .await
+            .unwrap();
+    }
+
+    let retired = *true_false_ids.iter().next().unwrap();
+    repository.remove_questions(vec![retired]).await.unwrap();
+    true_false_ids.remove(&retired);
+
+    let selected = repository
+        .select_questions(select_count(3, 2))
+        .await
+        .unwrap();
+    assert_eq!(selected.len(), 5);
+    let ids: HashSet<_> = selected.iter().map(|q| q.id()).collect();
+    assert_eq!(ids.len(), 5);
+
+    let chosen: Vec<_> = selected
+        .iter()
+        .filter(|q| q.kind() == QuestionKind::TrueFalse)
+        .map(|q| q.id())
+        .collect();
+    assert_eq!(chosen.len(), 3);
+    assert!(chosen.iter().all(|id| true_false_ids.contains(id)));
+
+    // Every remaining question is eventually drawn.
+    let mut drawn = HashSet::new();
+    for _ in 0..50 {
+        let selected = repository
+            .select_questions(select_count(1, 0))
+            .await
+            .unwrap();
+        drawn.insert(selected[0].id());
+    }
+    assert_eq!(drawn, true_false_ids);
+
+    assert!(matches!(
+        repository.select_questions(select_count(5, 0)).await,
+        Err(QuestionRepositoryError::Insufficient {
+            expected: 5,
+            total: 4,
+            ..
+        })
+    ));
+}
+
+async fn check_scores(repositories: &TestRepositories) {
+    let repository = &repositories.score;
+    let alice = User::try_new("alice").unwrap();
+    let bob = User::try_new("bob").unwrap();
+
+    let first = repositories
+        .question
+        .insert_question(true_false("Linux is a kernel", true), "alice".to_owned())
+        .await
+        .unwrap();
+    let second = repositories
+        .question
+        .insert_question(completion("List files with ___"), "alice".to_owned())
+        .await
+        .unwrap();
+    let first_revision = latest_revision(repositories, first).await;
+    let second_revision = latest_revision(repositories, second).await;
+
+    let now = Utc::now();
+    repository
+        .insert(
+            alice.clone(),
+            AttemptKind::Test,
+            Points::new(1.0, 2),
+            None,
+            now - ChronoDuration::hours(2),
+            Duration::from_secs(300),
+            vec![
+                item(first, first_revision, 1.0, false),
+                item(second, second_revision, 0.0, true),
+            ],
+        )
+        .await
+        .unwrap();
+    repository
+        .insert(
+            bob.clone(),
+            AttemptKind::Test,
+            Points::new(0.0, 2),
+            Some(Grade::new("F", false)),
+            now - ChronoDuration::hours(1),
+            Duration::from_secs(200),
+            vec![
+                item(first, first_revision, 0.0, false),
+                item(second, second_revision, 0.0, false),
+            ],
+        )
+        .await
+        .unwrap();
+    // Passing an adaptive attempt counts for neither the best score, grades nor certificates.
+    repository
+        .insert(
+            bob.clone(),
+            AttemptKind::Adaptive,
+            Points::new(1.0, 1),
+            Some(Grade::new("A", true)),
+            now - ChronoDuration::hours(3),
+            Duration::from_secs(100),
+            vec![item(first, first_revision, 1.0, false)],
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        repository.query_best(&bob).await.unwrap().score.inner(),
+        0.0
+    );
+
+    let pending = repository.list_pending().await.unwrap();
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].user, alice);
+    assert_eq!(pending[0].revision, second_revision);
+    assert_eq!(pending[0].points, 1);
+    assert_eq!(pending[0].response, "It depends");
+    assert!(repository.query_latest(&alice).await.unwrap().pending);
+    assert!(matches!(
+        repository.query_best(&alice).await,
+        Err(ScoreRepositoryError::NotFound { .. })
+    ));
+
+    let score = repository
+        .grade_item(
+            pending[0].id,
+            ItemGrade {
+                credit: 1.0,
+                comment: Some("Fine".to_owned()),
+                grader: "carol".to_owned(),
+                graded_at: now,
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(score, pending[0].score);
+    assert!(matches!(
+        repository
+            .grade_item(
+                pending[0].id,
+                ItemGrade {
+                    credit: 0.0,
+                    comment: None,
+                    grader: "carol".to_owned(),
+                    graded_at: now,
+                },
+            )
+            .await,
+        Err(ScoreRepositoryError::PendingItemNotFound { .. })
+    ));
+
+    let items = repository.query_items(score).await.unwrap();
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[1].question, second);
+    assert!(items[1].correct && !items[1].pending);
+
+    repository
+        .finalize(score, Points::new(2.0, 2), Grade::new("A", true))
+        .await
+        .unwrap();
+    let best = repository.query_best(&alice).await.unwrap();
+    assert!(!best.pending);
+    assert_eq!(best.score.inner(), 100.0);
+    assert_eq!(best.grade, Some(Grade::new("A", true)));
+    assert_eq!(best.duration, Duration::from_secs(300));
+
+    let statistics = repository
+        .query_item_statistics(vec![first, second])
+        .await
+        .unwrap();
+    assert_eq!(
+        statistics[&second],
+        ItemStatistics {
+            attempts: 2,
+            correct: 1
+        }
+    );
+
+    let records = repository
+        .list_records(RecordFilter {
+            since: Some(now - ChronoDuration::minutes(90)),
+            ..RecordFilter::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].0, bob);
+
+    let grades = repository.list_grades().await.unwrap();
+    let users: Vec<_> = grades.iter().map(|g| g.user.inner()).collect();
+    assert_eq!(users, ["alice", "bob"]);
+
+    // The certificate is issued once, so a second code is not recorded.
+    let certificate = repository
+        .issue_certificate(&alice, VerificationCode::generate().unwrap())
+        .await
+        .unwrap();
+    let again = repository
+        .issue_certificate(&alice, VerificationCode::generate().unwrap())
+        .await
+        .unwrap();
+    assert_eq!(again.code, certificate.code);
+    let found = repository
+        .find_certificate(&certificate.code)
+        .await
+        .unwrap();
+    assert_eq!(found.user, alice);
+    assert_eq!(found.grade, Grade::new("A", true));
+
+    assert!(matches!(
+        repository
+            .issue_certificate(&bob, VerificationCode::generate().unwrap())
+            .await,
+        Err(ScoreRepositoryError::NotPassed { .. })
+    ));
+}
+
+async fn check_review_cards(repositories: &TestRepositories) {
+    let repository = &repositories.review;
+    let alice = User::try_new("alice").unwrap();
+    let bob = User::try_new("bob").unwrap();
+    let now = Utc::now();
+
+    let mut ids = Vec::new();
+    for content in ["Is ls a command?", "Is cd a command?", "Is dir a command?"] {
+        let id = repositories
+            .question
+            .insert_question(true_false(content, true), "carol".to_owned())
+            .await
+            .unwrap();
+        ids.push(id);
+    }
+    let [missed, known, retired] = ids[..] else {
+        unreachable!()
+    };
+
+    let card = |question: Id, results: &[bool]| {
+        let mut card = ReviewCard::new(question, now - ChronoDuration::days(3));
+        for correct in results {
+            card.review(*correct, now - ChronoDuration::days(2));
+        }
+        card
+    };
+    repository
+        .save_cards(
+            alice.clone(),
+            vec![
+                card(missed, &[false]),
+                card(known, &[true]),
+                card(retired, &[false]),
+            ],
+        )
+        .await
+        .unwrap();
+    repository
+        .save_cards(bob.clone(), vec![card(known, &[false])])
+        .await
+        .unwrap();
+    repositories
+        .question
+        .remove_questions(vec![retired])
+        .await
+        .unwrap();
+    repository.remove_stale_cards(&alice).await.unwrap();
+    assert!(repository
+        .find_cards(&alice, vec![retired])
+        .await
+        .unwrap()
+        .is_empty());
+
+    // Questions answered correctly every time are tracked without coming up for review.
+    assert_eq!(repository.list_due(&alice, now).await.unwrap(), [missed]);
+    assert_eq!(repository.list_due(&bob, now).await.unwrap(), [known]);
+    assert!(repository
+        .list_due(&alice, now - ChronoDuration::days(2))
+        .await
+        .unwrap()
+        .is_empty());
+
+    repository
+        .save_cards(alice.clone(), vec![card(missed, &[false, true])])
+        .await
+        .unwrap();
+    let mut cards = repository
+        .find_cards(&alice, vec![missed, known])
+        .await
+        .unwrap();
+    cards.sort_by_key(|c| c.question.inner());
+    let counts: Vec<_> = cards.iter().map(|c| (c.attempts, c.correct)).collect();
+    assert_eq!(counts, [(2, 1), (1, 1)]);
+}
+
+#[tokio::test]
+async fn sqlite_question_revisions() {
+    check_question_revisions(&TestRepositories::sqlite().await).await;
+}
+
+#[tokio::test]
+#[ignore = "needs a PostgreSQL server at $TEST_POSTGRES_URL"]
+async fn postgres_question_revisions() {
+    check_question_revisions(&TestRepositories::postgres().await).await;
+}
+
+#[tokio::test]
+async fn sqlite_question_update_clears_columns() {
+    check_question_update_clears_columns(&TestRepositories::sqlite().await).await;
+}
+
+#[tokio::test]
+#[ignore = "needs a PostgreSQL server at $TEST_POSTGRES_URL"]
+async fn postgres_question_update_clears_columns() {
+    check_question_update_clears_columns(&TestRepositories::postgres().await).await;
+}
+
+#[tokio::test]
+async fn sqlite_question_listing() {
+    check_question_listing(&TestRepositories::sqlite().await).await;
+}
+
+#[tokio::test]
+#[ignore = "needs a PostgreSQL server at $TEST_POSTGRES_URL"]
+async fn postgres_question_listing() {
+    check_question_listing(&TestRepositories::postgres().await).await;
+}
+
+#[tokio::test]
+async fn sqlite_question_search() {
+    check_question_search(&TestRepositories::sqlite().await).await;
+}
+
+#[tokio::test]
+#[ignore = "needs a PostgreSQL server at $TEST_POSTGRES_URL"]
+async fn postgres_question_search() {
+    check_question_search(&TestRepositories::postgres().await).await;
+}
+
+#[tokio::test]
+async fn sqlite_random_selection() {
+    check_random_selection(&TestRepositories::sqlite().await).await;
+}
+
+#[tokio::test]
+#[ignore = "needs a PostgreSQL server at $TEST_POSTGRES_URL"]
+async fn postgres_random_selection() {
+    check_random_selection(&TestRepositories::postgres().await).await;
+}
+
+#[tokio::test]
+async fn sqlite_scores() {
+    check_scores(&TestRepositories::sqlite().await).await;
+}
+
+#[tokio::test]
+#[ignore = "needs a PostgreSQL server at $TEST_POSTGRES_URL"]
+async fn postgres_scores() {
+    check_scores(&TestRepositories::postgres().await).await;
+}
+
+#[tokio::test]
+async fn sqlite_review_cards() {
+    check_review_cards(&TestRepositories::sqlite().await).await;
+}
+
+#[tokio::test]
+#[ignore = "needs a PostgreSQL server at $TEST_POSTGRES_URL"]
+async fn postgres_review_cards() {
+    check_review_cards(&TestRepositories::postgres().await).await;
+}
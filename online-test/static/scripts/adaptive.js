@@ -0,0 +1,205 @@
+const progress = document.querySelector(".adaptive-progress p");
+
+const buttonAdaptiveAnswer = document.querySelector(".adaptive-answer button");
+const buttonAdaptiveFinish = document.querySelector(".adaptive-finish button");
+const buttonAdaptiveLeave = document.querySelector(".adaptive-leave button");
+
+const questionContainer = document.querySelector(".question-container");
+
+let testId;
+let finished = false;
+
+function requireLogin() {
+  const loginId = sessionStorage.getItem("loginId");
+
+  if (!loginId) {
+    window.alert("Error: Not logined. You must login first to participate in the test!");
+    location.assign("../login.html");
+    return null;
+  }
+
+  return Number(loginId);
+}
+
+async function postAdaptive(path, req) {
+  const resp = await fetch(`../api/adaptive/${path}`, {
+    method: "POST",
+    headers: { 'Content-Type': 'application/json' },
+    body: JSON.stringify(req),
+    credentials: "same-origin",
+  });
+
+  if (resp.ok) {
+    return await resp.json();
+  }
+
+  const text = await resp.text();
+
+  if (text.search("Could not serve without logging in") != -1) {
+    window.alert("Error: Not logined. You must login first to participate in the test!");
+    location.assign("../login.html");
+  } else if (text.search("Test is not authencated by system or expired") != -1) {
+    window.alert("Error: Test is not authencated by OTSS or expired. Please refresh the page");
+  } else {
+    console.error(text);
+    window.alert(`Internal Server Error: ${text}`);
+  }
+
+  return null;
+}
+
+async function startTest() {
+  const loginId = requireLogin();
+
+  if (loginId === null) {
+    return;
+  }
+
+  const res = await postAdaptive("start", { login_id: loginId });
+
+  if (res) {
+    testId = res.test_id;
+  }
+}
+
+async function nextQuestion() {
+  const loginId = requireLogin();
+
+  if (loginId === null || testId === undefined) {
+    return;
+  }
+
+  const res = await postAdaptive("next", { login_id: loginId, test_id: testId });
+
+  if (!res) {
+    return;
+  }
+
+  if (!res.question) {
+    await finishTest();
+    return;
+  }
+
+  questionContainer.replaceChildren(createQuestion(res.question));
+}
+
+// Each question is picked from the answers so far, so nothing is revealed until the end.
+async function finishTest() {
+  const loginId = requireLogin();
+
+  if (finished || loginId === null || testId === undefined) {
+    return;
+  }
+
+  const res = await postAdaptive("finish", { login_id: loginId, test_id: testId });
+
+  if (!res) {
+    return;
+  }
+
+  finished = true;
+  buttonAdaptiveAnswer.disabled = true;
+  buttonAdaptiveFinish.textContent = "Result";
+
+  const section = document.createElement("section");
+  section.setAttribute("class", "question-entry introduction");
+
+  const h1 = document.createElement("h1");
+  h1.textContent = "Adaptive test finished";
+  section.appendChild(h1);
+
+  const score = document.createElement("p");
+  const net = Math.round(Math.max(res.points - res.penalty, 0) * 10) / 10;
+  score.textContent = `Your score is ${net} / ${res.total_points} pts (${Math.round(res.score * 10) / 10}%).`;
+  section.appendChild(score);
+
+  if (res.penalty > 0) {
+    const penalty = document.createElement("p");
+    penalty.textContent = `${Math.round(res.points * 10) / 10} pts earned, ${Math.round(res.penalty * 100) / 100} pts deducted for wrong answers.`;
+    section.appendChild(penalty);
+  }
+
+  const grade = document.createElement("p");
+  grade.textContent = `Grade ${res.grade}, ${res.passed ? "passed" : "not passed"}.`;
+  section.appendChild(grade);
+
+  const ability = document.createElement("p");
+  ability.textContent = `Your estimated ability is ${res.ability.toFixed(2)} on a scale where 0 is average.`;
+  section.appendChild(ability);
+
+  questionContainer.replaceChildren(section);
+}
+
+function registerControlButton() {
+  buttonAdaptiveAnswer.addEventListener("click", (_event) => {
+    questionContainer.requestSubmit();
+  });
+
+  buttonAdaptiveFinish.addEventListener("click", async (_event) => {
+    if (finished) {
+      location.assign("../result.html?query=latest");
+      return;
+    }
+
+    const res = window.confirm("Are you sure to finish the test? Unanswered questions are not counted.");
+
+    if (res) {
+      await finishTest();
+    }
+  });
+
+  buttonAdaptiveLeave.addEventListener("click", (_event) => {
+    const res = window.confirm("Are you sure to quit the test? All answers will be lost!");
+
+    if (res) {
+      location.assign("../index.html");
+    }
+  });
+}
+
+function registerFormSubmit() {
+  questionContainer.addEventListener("submit", async (event) => {
+    event.preventDefault();
+
+    const loginId = requireLogin();
+    const section = questionContainer.querySelector(".question-entry");
+
+    if (finished || loginId === null || !section) {
+      return;
+    }
+
+    const answer = readAnswer(section);
+
+    if (!answer) {
+      window.alert("Please answer the question first");
+      return;
+    }
+
+    try {
+      const res = await postAdaptive("answer", {
+        login_id: loginId,
+        test_id: testId,
+        type: answer.type,
+        answer: answer.answer,
+      });
+
+      if (res) {
+        progress.textContent = `${res.answered}`;
+        await nextQuestion();
+      }
+    } catch (error) {
+      console.error(error);
+      window.alert("Error: Could not submit the answer");
+    }
+  });
+}
+
+async function main() {
+  registerControlButton();
+  registerFormSubmit();
+
+  await startTest();
+  await nextQuestion();
+}
+
+main();
@@ -0,0 +1,218 @@
+const tally = document.querySelector(".practice-tally p");
+
+const buttonPracticeCheck = document.querySelector(".practice-check button");
+const buttonPracticeNext = document.querySelector(".practice-next button");
+const buttonPracticeLeave = document.querySelector(".practice-leave button");
+
+const questionContainer = document.querySelector(".question-container");
+
+// Reviews serve only the questions due for the user, as scheduled from earlier answers.
+const review = new URLSearchParams(location.search).get("mode") == "review";
+
+let practiceId;
+let checked = false;
+
+function requireLogin() {
+  const loginId = sessionStorage.getItem("loginId");
+
+  if (!loginId) {
+    window.alert("Error: Not logined. You must login first to practice!");
+    location.assign("../login.html");
+    return null;
+  }
+
+  return Number(loginId);
+}
+
+async function postPractice(path, req) {
+  const resp = await fetch(`../api/practice/${path}`, {
+    method: "POST",
+    headers: { 'Content-Type': 'application/json' },
+    body: JSON.stringify(req),
+    credentials: "same-origin",
+  });
+
+  if (resp.ok) {
+    return await resp.json();
+  }
+
+  const text = await resp.text();
+
+  if (text.search("Could not serve without logging in") != -1) {
+    window.alert("Error: Not logined. You must login first to practice!");
+    location.assign("../login.html");
+  } else if (text.search("Test is not authencated by system or expired") != -1) {
+    window.alert("Error: Practice has expired. Please refresh the page");
+  } else {
+    console.error(text);
+    window.alert(`Internal Server Error: ${text}`);
+  }
+
+  return null;
+}
+
+async function startPractice() {
+  const loginId = requireLogin();
+
+  if (loginId === null) {
+    return;
+  }
+
+  const res = await postPractice("start", { login_id: loginId, review: review });
+
+  if (res) {
+    practiceId = res.practice_id;
+  }
+}
+
+async function nextQuestion() {
+  const loginId = requireLogin();
+
+  if (loginId === null || practiceId === undefined) {
+    return;
+  }
+
+  const res = await postPractice("next", { login_id: loginId, practice_id: practiceId });
+
+  if (!res) {
+    return;
+  }
+
+  questionContainer.replaceChildren();
+  checked = false;
+
+  if (!res.question) {
+    const section = document.createElement("section");
+    section.setAttribute("class", "question-entry introduction");
+
+    const h1 = document.createElement("h1");
+    h1.textContent = review ? "Review finished" : "Practice finished";
+    section.appendChild(h1);
+
+    const p = document.createElement("p");
+    if (review && tally.textContent == "0 / 0") {
+      p.textContent = "No question is due for review. Come back later!";
+    } else {
+      p.textContent = `You answered ${tally.textContent} questions correctly.`;
+    }
+    section.appendChild(p);
+
+    questionContainer.appendChild(section);
+    buttonPracticeCheck.disabled = true;
+    buttonPracticeNext.disabled = true;
+    return;
+  }
+
+  questionContainer.appendChild(createQuestion(res.question));
+}
+
+// Describes the correct answer in terms of what the student saw on the page.
+function describeAnswer(section, answer) {
+  if (answer.type == "SingleSelection" || answer.type == "MultipleSelection") {
+    const values = answer.type == "SingleSelection" ? [answer.value] : answer.value;
+    const labels = values.map((value) => section.querySelector(`label[for$="-${String.fromCharCode(97 + value)}"]`));
+    return labels.filter((label) => label).map((label) => label.innerHTML).join(", ");
+  } else if (answer.type == "TrueFalse") {
+    return answer.value ? "True" : "False";
+  } else if (answer.type == "Cloze") {
+    return answer.value.map((blank) => blank.join(" / ")).join(", ");
+  } else if (typeof answer.value == "string") {
+    const code = document.createElement("code");
+    code.textContent = answer.value;
+    return code.outerHTML;
+  }
+
+  return null;
+}
+
+function showFeedback(res) {
+  const section = questionContainer.querySelector(".question-entry");
+
+  if (!section) {
+    return;
+  }
+
+  section.querySelectorAll("input, select, textarea, button").forEach((element) => {
+    element.disabled = true;
+  });
+  section.classList.add(res.correct ? "feedback-correct" : "feedback-wrong");
+
+  const description = describeAnswer(section, res.answer);
+  if (description) {
+    const p = document.createElement("p");
+    p.setAttribute("class", "practice-answer");
+    p.innerHTML = `Correct answer: ${description}`;
+    section.appendChild(p);
+  }
+
+  if (res.explanation) {
+    const explanation = document.createElement("div");
+    explanation.setAttribute("class", "question-explanation");
+    explanation.innerHTML = res.explanation;
+    resolveMedia(explanation);
+    section.appendChild(explanation);
+  }
+
+  tally.textContent = `${res.correct_count} / ${res.answered}`;
+}
+
+function registerControlButton() {
+  buttonPracticeCheck.addEventListener("click", (_event) => {
+    questionContainer.requestSubmit();
+  });
+
+  buttonPracticeNext.addEventListener("click", async (_event) => {
+    await nextQuestion();
+  });
+
+  buttonPracticeLeave.addEventListener("click", (_event) => {
+    location.assign("../index.html");
+  });
+}
+
+function registerFormSubmit() {
+  questionContainer.addEventListener("submit", async (event) => {
+    event.preventDefault();
+
+    const loginId = requireLogin();
+    const section = questionContainer.querySelector(".question-entry");
+
+    if (checked || loginId === null || !section) {
+      return;
+    }
+
+    const answer = readAnswer(section);
+
+    if (!answer) {
+      window.alert("Please answer the question first");
+      return;
+    }
+
+    try {
+      const res = await postPractice("answer", {
+        login_id: loginId,
+        practice_id: practiceId,
+        type: answer.type,
+        answer: answer.answer,
+      });
+
+      if (res) {
+        checked = true;
+        showFeedback(res);
+      }
+    } catch (error) {
+      console.error(error);
+      window.alert("Error: Could not check the answer");
+    }
+  });
+}
+
+async function main() {
+  registerControlButton();
+  registerFormSubmit();
+
+  await startPractice();
+  await nextQuestion();
+}
+
+main();
@@ -0,0 +1,326 @@
+function createSelectionAnswer(child, componentType) {
+  const ul = document.createElement("ul");
+  ul.className = "question-answer selection-list";
+
+  Array.from(child.options).forEach((option, index) => {
+    const li = document.createElement("li");
+
+    const id = child.id;
+    const input = document.createElement("input");
+    input.setAttribute("type", componentType);
+    input.setAttribute("id", `option-${id}-${String.fromCharCode(97 + index)}`);
+    input.setAttribute("name", `question-${id}`);
+    input.setAttribute("value", index);
+    li.appendChild(input);
+
+    const label = document.createElement("label");
+    label.setAttribute("for", input.getAttribute("id"));
+    label.innerHTML = option;
+    li.appendChild(label);
+
+    ul.appendChild(li);
+  });
+
+  return ul;
+}
+
+function createTrueFalseAnswer(child) {
+  return createSelectionAnswer({ id: child.id, options: ["True", "False"] }, "radio");
+}
+
+function createOrderingAnswer(child) {
+  const ol = document.createElement("ol");
+  ol.className = "question-answer ordering-list";
+
+  Array.from(child.items).forEach((item, index) => {
+    const li = document.createElement("li");
+    li.dataset.index = index;
+
+    const label = document.createElement("label");
+    label.innerHTML = item;
+    li.appendChild(label);
+
+    const up = document.createElement("button");
+    up.setAttribute("type", "button");
+    up.setAttribute("aria-label", "Move up");
+    up.textContent = "\u25B2";
+    up.addEventListener("click", (_event) => {
+      if (li.previousElementSibling) {
+        ol.insertBefore(li, li.previousElementSibling);
+      }
+    });
+    li.appendChild(up);
+
+    const down = document.createElement("button");
+    down.setAttribute("type", "button");
+    down.setAttribute("aria-label", "Move down");
+    down.textContent = "\u25BC";
+    down.addEventListener("click", (_event) => {
+      if (li.nextElementSibling) {
+        ol.insertBefore(li.nextElementSibling, li);
+      }
+    });
+    li.appendChild(down);
+
+    ol.appendChild(li);
+  });
+
+  return ol;
+}
+
+function createMatchingAnswer(child) {
+  const ol = document.createElement("ol");
+  ol.className = "question-answer matching-list";
+
+  Array.from(child.items).forEach((item, index) => {
+    const li = document.createElement("li");
+
+    const id = child.id;
+    const label = document.createElement("label");
+    label.setAttribute("for", `matching-${id}-${index}`);
+    label.innerHTML = item;
+    li.appendChild(label);
+
+    const select = document.createElement("select");
+    select.setAttribute("id", `matching-${id}-${index}`);
+    select.setAttribute("name", `matching-${id}-${index}`);
+
+    const placeholder = document.createElement("option");
+    placeholder.setAttribute("value", "");
+    placeholder.textContent = "--";
+    select.appendChild(placeholder);
+
+    Array.from(child.targets).forEach((target, targetIndex) => {
+      const option = document.createElement("option");
+      option.setAttribute("value", targetIndex);
+      // Targets may carry markup, while options of a select only show text.
+      const content = document.createElement("div");
+      content.innerHTML = target;
+      option.textContent = content.textContent;
+      select.appendChild(option);
+    });
+
+    li.appendChild(select);
+    ol.appendChild(li);
+  });
+
+  return ol;
+}
+
+function createEssayAnswer(child) {
+  const inputBox = document.createElement("div");
+  inputBox.className = "question-answer input-box";
+
+  const id = child.id;
+  const textarea = document.createElement("textarea");
+  textarea.setAttribute("id", `essay-${id}`);
+  textarea.setAttribute("name", `essay-${id}`);
+  textarea.setAttribute("rows", 8);
+
+  inputBox.appendChild(textarea);
+  return inputBox;
+}
+
+// Blanks are runs of at least three underscores in the content, each replaced by an input.
+function fillClozeBlanks(p, child) {
+  const walker = document.createTreeWalker(p, NodeFilter.SHOW_TEXT);
+  const nodes = new Array();
+  while (walker.nextNode()) {
+    nodes.push(walker.currentNode);
+  }
+
+  let index = 0;
+  nodes.forEach((node) => {
+    const parts = node.textContent.split(/_{3,}/);
+    if (parts.length == 1) {
+      return;
+    }
+
+    const fragment = document.createDocumentFragment();
+    parts.forEach((part, i) => {
+      if (i > 0) {
+        const input = document.createElement("input");
+        input.setAttribute("type", "input");
+        input.setAttribute("class", "cloze-blank");
+        input.setAttribute("name", `cloze-${child.id}-${index}`);
+        input.setAttribute("aria-label", `Blank ${index + 1}`);
+        fragment.appendChild(input);
+        index += 1;
+      }
+      fragment.appendChild(document.createTextNode(part));
+    });
+    node.replaceWith(fragment);
+  });
+}
+
+function createCompletionAnswer(child) {
+  const inputBox = document.createElement("div");
+  inputBox.className = "question-answer input-box";
+
+  const id = child.id;
+  const input = document.createElement("input");
+  input.setAttribute("type", "input");
+  input.setAttribute("id", `completion-${id}`);
+  input.setAttribute("name", `completion-${id}`);
+
+  inputBox.appendChild(input);
+  return inputBox;
+}
+
+function createQuestion(child) {
+  let className;
+
+  if (child.type == "SingleSelection") {
+    className = "single-selection"
+  } else if (child.type == "MultipleSelection") {
+    className = "multiple-selection"
+  } else if (child.type == "TrueFalse") {
+    className = "true-false"
+  } else if (child.type == "Ordering") {
+    className = "ordering"
+  } else if (child.type == "Matching") {
+    className = "matching"
+  } else if (child.type == "Essay") {
+    className = "essay"
+  } else if (child.type == "Cloze") {
+    className = "cloze"
+  } else {
+    className = "completion"
+  }
+
+  const section = document.createElement("section");
+  section.setAttribute("class", `question-entry ${className}`);
+  section.setAttribute("id", `question-${child.id}`);
+
+  const h1 = document.createElement("h1");
+  h1.setAttribute("class", "question-number");
+  section.appendChild(h1);
+
+  const p = document.createElement("p");
+  p.innerHTML = child.content;
+  section.appendChild(p);
+
+  if (className == "single-selection") {
+    const answer = createSelectionAnswer(child, "radio");
+    section.appendChild(answer);
+  } else if (className == "multiple-selection") {
+    const answer = createSelectionAnswer(child, "checkbox");
+    section.appendChild(answer);
+  } else if (className == "true-false") {
+    const answer = createTrueFalseAnswer(child);
+    section.appendChild(answer);
+  } else if (className == "ordering") {
+    const answer = createOrderingAnswer(child);
+    section.appendChild(answer);
+  } else if (className == "matching") {
+    const answer = createMatchingAnswer(child);
+    section.appendChild(answer);
+  } else if (className == "essay") {
+    const answer = createEssayAnswer(child);
+    section.appendChild(answer);
+  } else if (className == "cloze") {
+    fillClozeBlanks(p, child);
+  } else {
+    const answer = createCompletionAnswer(child);
+    section.appendChild(answer);
+  }
+
+  resolveMedia(section);
+  return section;
+}
+
+// Content references attachments as `media:<id>`, which are served only to
+// the session whose test includes the question.
+function resolveMedia(element) {
+  const loginId = sessionStorage.getItem("loginId");
+
+  element.querySelectorAll('img[src^="media:"]').forEach((img) => {
+    const id = img.getAttribute("src").slice("media:".length);
+    img.setAttribute("src", `/api/media/${id}?login_id=${loginId}`);
+  });
+
+  element.querySelectorAll('a[href^="media:"]').forEach((a) => {
+    const id = a.getAttribute("href").slice("media:".length);
+    a.setAttribute("href", `/api/media/${id}?login_id=${loginId}`);
+    a.setAttribute("target", "_blank");
+  });
+}
+
+// Reads the answer given to a rendered question, or null when nothing was chosen.
+function readAnswer(question) {
+  if (question.classList.contains("single-selection")) {
+    const id = question.id.replace("question-", "");
+    const radio = question.querySelector("input:checked");
+    if (radio) {
+      return {
+        type: "SingleSelection",
+        id: Number(id),
+        answer: Number(radio.value)
+      };
+    }
+  } else if (question.classList.contains("multiple-selection")) {
+    const id = question.id.replace("question-", "");
+    const checkboxs = question.querySelectorAll("input:checked");
+    const answer = Array.from(checkboxs).map((ck) => Number(ck.value));
+    // Nothing checked is left unanswered, as for other selection questions.
+    if (answer.length > 0) {
+      return {
+        type: "MultipleSelection",
+        id: Number(id),
+        answer: answer
+      };
+    }
+  } else if (question.classList.contains("true-false")) {
+    const id = question.id.replace("question-", "");
+    const radio = question.querySelector("input:checked");
+    if (radio) {
+      return {
+        type: "TrueFalse",
+        id: Number(id),
+        answer: radio.value == "0"
+      };
+    }
+  } else if (question.classList.contains("ordering")) {
+    const id = question.id.replace("question-", "");
+    const items = question.querySelectorAll(".ordering-list li");
+    return {
+      type: "Ordering",
+      id: Number(id),
+      answer: Array.from(items).map((li) => Number(li.dataset.index))
+    };
+  } else if (question.classList.contains("matching")) {
+    const id = question.id.replace("question-", "");
+    const selects = question.querySelectorAll(".matching-list select");
+    return {
+      type: "Matching",
+      id: Number(id),
+      answer: Array.from(selects).map((select) => select.value === "" ? null : Number(select.value))
+    };
+  } else if (question.classList.contains("essay")) {
+    const id = question.id.replace("question-", "");
+    const textarea = question.querySelector("textarea");
+    return {
+      type: "Essay",
+      id: Number(id),
+      answer: textarea.value
+    };
+  } else if (question.classList.contains("cloze")) {
+    const id = question.id.replace("question-", "");
+    const inputs = question.querySelectorAll(".cloze-blank");
+    return {
+      type: "Cloze",
+      id: Number(id),
+      answer: Array.from(inputs).map((input) => input.value)
+    };
+  } else if (question.classList.contains("completion")) {
+    const id = question.id.replace("question-", "");
+    const inputbox = question.querySelector("input");
+    return {
+      type: "Completion",
+      id: Number(id),
+      answer: inputbox.value,
+    };
+  }
+
+  return null;
+}
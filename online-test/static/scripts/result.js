@@ -54,20 +54,34 @@ async function generateDemonstration() {
   const paraTime = demonstration.querySelector("#card-time p:last-child");
   paraTime.textContent = (sec !== 0 ? `${min} min ${sec} s` : `${min} min`);
 
+  // Scores recorded before points existed have no total to show.
   const paraScore = demonstration.querySelector("#card-score p:last-child");
-  paraScore.textContent = `${score} pts`;
+  const points = Math.round(Math.max(res.result[0].points - res.result[0].penalty, 0) * 10) / 10;
+  const penalty = Math.round(res.result[0].penalty * 100) / 100;
+  const total = res.result[0].total_points;
+  let scoreText = (total > 0 ? `${points} / ${total} pts (${score}%)` : `${score}%`);
+  if (penalty > 0) {
+    scoreText += `, -${penalty} pts for wrong answers`;
+  }
+  paraScore.textContent = scoreText;
 
   const paraLevelHeader = demonstration.querySelector("#card-level p:first-child");
   const paraLevelValue = demonstration.querySelector("#card-level p:last-child");
 
-  if (score >= 90) {
-    paraLevelHeader.textContent = "Congratulations! You are now";
-    paraLevelValue.textContent = "ADVANCED CERTIFIED SYSTEM ADMINISTRATOR";
-  } else if (score >= 75) {
-    paraLevelHeader.textContent = "Congratulations! You are now";
-    paraLevelValue.textContent = "CERTIFIED SYSTEM ADMINISTRATOR";
+  if (res.result[0].pending) {
+    paraScore.textContent = `${scoreText} (pending review)`;
+    paraLevelHeader.textContent = "Some answers are waiting for";
+    paraLevelValue.textContent = "REVIEW BY A TEACHER";
+  } else if (res.result[0].passed) {
+    paraLevelHeader.textContent = `Grade ${res.result[0].grade}. Congratulations! You are now`;
+    paraLevelValue.textContent = (res.result[0].grade == "A" ? "ADVANCED CERTIFIED SYSTEM ADMINISTRATOR" : "CERTIFIED SYSTEM ADMINISTRATOR");
+
+    // The certificate is issued for the best passed attempt, whichever result is shown.
+    const certificateLink = document.querySelector("#certificate-link");
+    certificateLink.href = `../api/certificate?login_id=${sessionStorage.getItem("loginId")}`;
+    certificateLink.hidden = false;
   } else {
-    paraLevelHeader.textContent = "Never mind. You have already made";
+    paraLevelHeader.textContent = `Grade ${res.result[0].grade}. Never mind. You have already made`;
     paraLevelValue.textContent = "A GREAT PROGRESS";
   }
 }
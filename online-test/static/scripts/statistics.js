@@ -52,6 +52,9 @@ function createTableRow(record, num) {
 
   const score = document.createElement("td");
   score.textContent = `${Math.round(record.score * 10) / 10} pts`;
+  if (record.pending) {
+    score.textContent += " (pending review)";
+  }
   row.appendChild(score);
 
   const endTime = document.createElement("td");
@@ -14,7 +14,9 @@ const TOTAL_DURATION_SECONDS = 1800;
 
 let testId;
 let askSubmit = true;
+let submitted = false;
 let intervalId;
+let draftTimeoutId;
 
 async function fetchQuestion() {
   try {
@@ -60,83 +62,6 @@ async function fetchQuestion() {
   }
 }
 
-function createSelectionAnswer(child, componentType) {
-  const ul = document.createElement("ul");
-  ul.className = "question-answer selection-list";
-
-  Array.from(child.options).forEach((option, index) => {
-    const li = document.createElement("li");
-
-    const id = child.id;
-    const input = document.createElement("input");
-    input.setAttribute("type", componentType);
-    input.setAttribute("id", `option-${id}-${String.fromCharCode(97 + index)}`);
-    input.setAttribute("name", `question-${id}`);
-    input.setAttribute("value", index);
-    li.appendChild(input);
-
-    const label = document.createElement("label");
-    label.setAttribute("for", input.getAttribute("id"));
-    label.innerHTML = option;
-    li.appendChild(label);
-
-    ul.appendChild(li);
-  });
-
-  return ul;
-}
-
-function createCompletionAnswer(child) {
-  const inputBox = document.createElement("div");
-  inputBox.className = "question-answer input-box";
-
-  const id = child.id;
-  const input = document.createElement("input");
-  input.setAttribute("type", "input");
-  input.setAttribute("id", `completion-${id}`);
-  input.setAttribute("name", `completion-${id}`);
-
-  inputBox.appendChild(input);
-  return inputBox;
-}
-
-function createQuestion(child) {
-  let className;
-
-  if (child.type == "SingleSelection") {
-    className = "single-selection"
-  } else if (child.type == "MultipleSelection") {
-    className = "multiple-selection"
-  } else {
-    className = "completion"
-  }
-
-  const section = document.createElement("section");
-  section.setAttribute("class", `question-entry ${className}`);
-  section.setAttribute("id", `question-${child.id}`);
-
-  const h1 = document.createElement("h1");
-  h1.setAttribute("class", "question-number");
-  section.appendChild(h1);
-
-  const p = document.createElement("p");
-  p.innerHTML = child.content;
-  section.appendChild(p);
-
-  if (className == "single-selection") {
-    const answer = createSelectionAnswer(child, "radio");
-    section.appendChild(answer);
-  } else if (className == "multiple-selection") {
-    const answer = createSelectionAnswer(child, "checkbox");
-    section.appendChild(answer);
-  } else {
-    const answer = createCompletionAnswer(child);
-    section.appendChild(answer);
-  }
-
-  return section;
-}
-
 async function generateQuestion() {
   const text = await fetchQuestion();
 
@@ -251,33 +176,9 @@ function registerControlButton() {
 function createSubmissionJson() {
   let answers = new Array();
   for (const question of questionContainer.children) {
-    if (question.classList.contains("single-selection")) {
-      const id = question.id.replace("question-", "");
-      const radio = question.querySelector("input:checked");
-      if (radio) {
-        answers.push({
-          type: "SingleSelection",
-          id: Number(id),
-          answer: Number(radio.value)
-        });
-      }
-    } else if (question.classList.contains("multiple-selection")) {
-      const id = question.id.replace("question-", "");
-      const checkboxs = question.querySelectorAll("input:checked");
-      const answer = Array.from(checkboxs).map((ck) => Number(ck.value));
-      answers.push({
-        type: "MultipleSelection",
-        id: Number(id),
-        answer: answer
-      });
-    } else if (question.classList.contains("completion")) {
-      const id = question.id.replace("question-", "");
-      const inputbox = question.querySelector("input");
-      answers.push({
-        type: "Completion",
-        id: Number(id),
-        answer: inputbox.value,
-      });
+    const answer = readAnswer(question);
+    if (answer) {
+      answers.push(answer);
     }
   }
 
@@ -296,9 +197,53 @@ function createSubmissionJson() {
   });
 }
 
+// Marks every question as right or wrong and reveals its explanation once graded.
+function showFeedback(items) {
+  submitted = true;
+  questionContainer.querySelectorAll("input, select, textarea, button").forEach((element) => {
+    element.disabled = true;
+  });
+  buttonTestSubmit.textContent = "Result";
+
+  for (const item of items) {
+    const section = document.getElementById(`question-${item.id}`);
+
+    if (!section) {
+      continue;
+    }
+
+    if (item.pending) {
+      section.classList.add("feedback-pending");
+    } else {
+      section.classList.add(item.correct ? "feedback-correct" : "feedback-wrong");
+    }
+
+    if (item.penalty > 0) {
+      const penalty = document.createElement("p");
+      penalty.setAttribute("class", "question-penalty");
+      penalty.textContent = `Wrong answer: -${Math.round(item.penalty * 100) / 100} pts`;
+      section.appendChild(penalty);
+    }
+
+    if (item.explanation) {
+      const explanation = document.createElement("div");
+      explanation.setAttribute("class", "question-explanation");
+      explanation.innerHTML = item.explanation;
+      resolveMedia(explanation);
+      section.appendChild(explanation);
+    }
+  }
+}
+
 function registerFormSubmit() {
   questionContainer.addEventListener("submit", async (event) => {
     event.preventDefault();
+
+    if (submitted) {
+      location.assign("../result.html?query=latest");
+      return;
+    }
+
     const res = !askSubmit || window.confirm("Are you sure to submit the answers?");
 
     if (res) {
@@ -332,24 +277,20 @@ function registerFormSubmit() {
           intervalId = null;
         }
 
-        const request = {
-          login_id: Number(sessionStorage.getItem("loginId")),
-          kind: "latest",
-        };
-
-        const resp2 = await fetch("../api/query", {
-          method: "POST",
-          headers: { 'Content-Type': 'application/json' },
-          body: JSON.stringify(request),
-          credentials: "same-origin",
-        });
-
-        const text = await resp2.text();
-        const res = JSON.parse(text);
-        const score = Math.round(res.result[0].score * 10) / 10;
-        window.alert(`Your score is ${score} pts`);
+        if (draftTimeoutId) {
+          clearTimeout(draftTimeoutId);
+          draftTimeoutId = null;
+        }
 
-        location.assign("../result.html?query=latest");
+        const res = await resp.json();
+        const score = Math.round(res.score * 10) / 10;
+        const points = Math.round(res.points * 10) / 10;
+        const penalty = Math.round(res.penalty * 100) / 100;
+        const breakdown = (penalty > 0 ? `, ${points} earned and ${penalty} deducted for wrong answers` : "");
+        const net = Math.round(Math.max(res.points - res.penalty, 0) * 10) / 10;
+        showFeedback(res.items);
+        const grade = (res.grade ? ` Grade ${res.grade}, ${res.passed ? "passed" : "not passed"}.` : "");
+        window.alert(`Your score is ${net} / ${res.total_points} pts (${score}%)${breakdown}.${grade} Review the explanations, then press Result`);
       } catch (error) {
         console.error(error);
         window.alert("Error: Could not submit answers");
@@ -358,6 +299,40 @@ function registerFormSubmit() {
   });
 }
 
+// Answers are saved as a draft shortly after each change, so that they are graded even if the
+// server shuts down before the test is submitted.
+function registerDraftSaving() {
+  const DRAFT_DELAY_MILLISECONDS = 1000;
+
+  async function saveDraft() {
+    draftTimeoutId = null;
+
+    if (submitted) {
+      return;
+    }
+
+    try {
+      await fetch("../api/draft", {
+        method: "POST",
+        headers: { 'Content-Type': 'application/json' },
+        body: createSubmissionJson(),
+        credentials: "same-origin",
+      });
+    } catch (error) {
+      console.error(error);
+    }
+  }
+
+  for (const type of ["input", "change"]) {
+    questionContainer.addEventListener(type, (_event) => {
+      if (draftTimeoutId) {
+        clearTimeout(draftTimeoutId);
+      }
+      draftTimeoutId = setTimeout(saveDraft, DRAFT_DELAY_MILLISECONDS);
+    });
+  }
+}
+
 async function main() {
   await generateQuestion();
   generateQuestionNavigation();
@@ -366,6 +341,7 @@ async function main() {
   registerCountdown();
   registerControlButton();
   registerFormSubmit();
+  registerDraftSaving();
 }
 
 main();
@@ -0,0 +1,77 @@
+use std::path::PathBuf;
+use std::process::{Command, Output, Stdio};
+
+// Runs the binary against a fresh SQLite database with stdin closed, as in scripts.
+fn run(database: &PathBuf, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_online-test"))
+        .arg("--database-url")
+        .arg(database)
+        .args(["cli", "--author", "alice"])
+        .args(args)
+        .stdin(Stdio::null())
+        .output()
+        .unwrap()
+}
+
+fn temp_database() -> PathBuf {
+    std::env::temp_dir().join(format!("online-test-cli-{:016x}.db", fastrand::u64(..)))
+}
+
+#[test]
+fn add_without_stdin() {
+    let database = temp_database();
+
+    let output = run(
+        &database,
+        &[
+            "add",
+            "completion",
+            "--content",
+            "What lists files?",
+            "--answer",
+            "ls",
+            "--json",
+        ],
+    );
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(json["id"].is_u64());
+
+    // Duplicates are refused unless confirmed up front.
+    let duplicate = [
+        "add",
+        "completion",
+        "--content",
+        "What lists files?",
+        "--answer",
+        "ls",
+        "--json",
+    ];
+    let output = run(&database, &duplicate);
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("duplicate of #1, pass --yes"));
+    let output = run(&database, &[&duplicate[..], &["--yes"]].concat());
+    assert!(output.status.success());
+
+    // Values left out are reported rather than prompted for.
+    let output = run(
+        &database,
+        &[
+            "add",
+            "completion",
+            "--content",
+            "What prints the working directory?",
+            "--json",
+        ],
+    );
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Missing --answer"));
+
+    let _ = std::fs::remove_file(database);
+}